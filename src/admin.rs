@@ -0,0 +1,60 @@
+//! The [Admin API](https://docs.anthropic.com/en/api/administration-api) implementations.
+//!
+//! The Admin API manages the organization, i.e. users, invites, workspaces, workspace members and API keys,
+//! and reports the usage and the cost of the organization. It requires an admin API key by [`AdminKey`] instead of a standard API key.
+
+mod admin_client;
+mod admin_key;
+mod api_key;
+mod cost_report;
+mod error;
+mod invite;
+mod object_type;
+mod page;
+mod report;
+mod usage_report;
+mod user;
+mod workspace;
+mod workspace_member;
+
+pub(crate) mod api;
+
+pub use admin_client::AdminClient;
+pub use admin_key::AdminKey;
+pub use api_key::ApiKeyCreator;
+pub use api_key::ApiKeyStatus;
+pub use api_key::ListApiKeysParameters;
+pub use api_key::OrganizationApiKey;
+pub use api_key::UpdateApiKeyRequestBody;
+pub use cost_report::CostBucket;
+pub use cost_report::CostGroupBy;
+pub use cost_report::CostReportPage;
+pub use cost_report::CostReportParameters;
+pub use cost_report::CostResult;
+pub use error::AdminError;
+pub use invite::CreateInviteRequestBody;
+pub use invite::Invite;
+pub use invite::InviteStatus;
+pub use object_type::AdminObjectType;
+pub use object_type::DeletedObject;
+pub use page::AdminList;
+pub use page::ListParameters;
+pub use report::BucketWidth;
+pub use report::ReportBucket;
+pub use report::ReportPage;
+pub use usage_report::UsageBucket;
+pub use usage_report::UsageCacheCreation;
+pub use usage_report::UsageGroupBy;
+pub use usage_report::UsageReportPage;
+pub use usage_report::UsageReportParameters;
+pub use usage_report::UsageResult;
+pub use usage_report::UsageServerToolUse;
+pub use user::OrganizationRole;
+pub use user::UpdateUserRequestBody;
+pub use user::User;
+pub use workspace::Workspace;
+pub use workspace::WorkspaceRequestBody;
+pub use workspace_member::AddWorkspaceMemberRequestBody;
+pub use workspace_member::UpdateWorkspaceMemberRequestBody;
+pub use workspace_member::WorkspaceMember;
+pub use workspace_member::WorkspaceRole;
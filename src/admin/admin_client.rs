@@ -0,0 +1,927 @@
+use crate::admin::api::{self, ORGANIZATIONS_PATH};
+use crate::admin::{
+    AddWorkspaceMemberRequestBody, AdminError, AdminKey, AdminList, CostBucket,
+    CostReportPage, CostReportParameters, CreateInviteRequestBody,
+    DeletedObject, Invite, ListApiKeysParameters, ListParameters,
+    OrganizationApiKey, UpdateApiKeyRequestBody, UpdateUserRequestBody,
+    UpdateWorkspaceMemberRequestBody, UsageBucket, UsageReportPage,
+    UsageReportParameters, User, Workspace, WorkspaceMember,
+    WorkspaceRequestBody,
+};
+use crate::request::encode_path_segment;
+use crate::{
+    Client, ClientBuilder, Page, PageStream, Paginate, RequestOptions,
+};
+
+/// The client of the Admin API to manage the organization, authenticated with an admin API key.
+///
+/// ## Example
+/// ```no_run
+/// use clust::admin::{AdminClient, ListParameters};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let admin = AdminClient::from_env()?;
+///
+///     let users = admin
+///         .list_users(ListParameters::new().limit(100))
+///         .await?;
+///     for user in users.data {
+///         println!("{}: {}", user.email, user.role);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// To customize the client, e.g. the base URL or the retry policy, build a client with the admin API key:
+/// ```
+/// use clust::admin::{AdminClient, AdminKey};
+/// use clust::{ClientBuilder, RetryPolicy};
+///
+/// let client = ClientBuilder::with_credentials(AdminKey::new("sk-ant-admin-key"))
+///     .retry_policy(RetryPolicy::default())
+///     .build();
+///
+/// let admin = AdminClient::from_client(client);
+/// ```
+#[derive(Clone)]
+pub struct AdminClient {
+    client: Client,
+}
+
+impl AdminClient {
+    /// Creates a new Admin API client with the admin API key and default options.
+    pub fn new(admin_key: AdminKey) -> Self {
+        Self::from_client(ClientBuilder::with_credentials(admin_key).build())
+    }
+
+    /// Creates a new Admin API client with the admin API key loaded from the environment variable: `ANTHROPIC_ADMIN_KEY`.
+    pub fn from_env() -> Result<Self, std::env::VarError> {
+        Ok(Self::new(AdminKey::from_env()?))
+    }
+
+    /// Creates a new Admin API client from the client authenticated with an admin API key.
+    pub fn from_client(client: Client) -> Self {
+        Self {
+            client,
+        }
+    }
+
+    /// Paginate a list endpoint of the Admin API as a stream of the objects, fetching the pages lazily.
+    ///
+    /// See also [`Client::paginate`].
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::admin::{AdminClient, AdminList, ListParameters};
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let admin = AdminClient::from_env()?;
+    ///
+    ///     let mut workspaces =
+    ///         admin.paginate(AdminList::workspaces(ListParameters::new()));
+    ///     while let Some(workspace) = workspaces.next().await {
+    ///         println!("{}", workspace?.name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn paginate<P>(
+        &self,
+        parameters: P,
+    ) -> PageStream<'_, P>
+    where
+        P: Paginate<Error = AdminError>,
+    {
+        self.client.paginate(parameters)
+    }
+
+    /// List users of the organization.
+    ///
+    /// See also [List Users](https://docs.anthropic.com/en/api/admin-api/users/list-users).
+    pub async fn list_users(
+        &self,
+        parameters: ListParameters,
+    ) -> Result<Page<User>, AdminError> {
+        AdminList::users(parameters)
+            .list(&self.client, RequestOptions::default())
+            .await
+    }
+
+    /// Get a user of the organization.
+    ///
+    /// See also [Get User](https://docs.anthropic.com/en/api/admin-api/users/get-user).
+    pub async fn get_user(
+        &self,
+        user_id: &str,
+    ) -> Result<User, AdminError> {
+        api::get(
+            &self.client,
+            "admin.users.get",
+            &format!(
+                "{ORGANIZATIONS_PATH}/users/{}",
+                encode_path_segment(user_id),
+            ),
+            &[],
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Update the organization role of a user.
+    ///
+    /// See also [Update User](https://docs.anthropic.com/en/api/admin-api/users/update-user).
+    pub async fn update_user(
+        &self,
+        user_id: &str,
+        request_body: UpdateUserRequestBody,
+    ) -> Result<User, AdminError> {
+        api::post(
+            &self.client,
+            "admin.users.update",
+            &format!(
+                "{ORGANIZATIONS_PATH}/users/{}",
+                encode_path_segment(user_id),
+            ),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Remove a user from the organization.
+    ///
+    /// See also [Remove User](https://docs.anthropic.com/en/api/admin-api/users/remove-user).
+    pub async fn remove_user(
+        &self,
+        user_id: &str,
+    ) -> Result<DeletedObject, AdminError> {
+        api::delete(
+            &self.client,
+            "admin.users.remove",
+            &format!(
+                "{ORGANIZATIONS_PATH}/users/{}",
+                encode_path_segment(user_id),
+            ),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Invite a user to the organization.
+    ///
+    /// See also [Create Invite](https://docs.anthropic.com/en/api/admin-api/invites/create-invite).
+    pub async fn create_invite(
+        &self,
+        request_body: CreateInviteRequestBody,
+    ) -> Result<Invite, AdminError> {
+        api::post(
+            &self.client,
+            "admin.invites.create",
+            &format!("{ORGANIZATIONS_PATH}/invites"),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// List invites of the organization.
+    ///
+    /// See also [List Invites](https://docs.anthropic.com/en/api/admin-api/invites/list-invites).
+    pub async fn list_invites(
+        &self,
+        parameters: ListParameters,
+    ) -> Result<Page<Invite>, AdminError> {
+        AdminList::invites(parameters)
+            .list(&self.client, RequestOptions::default())
+            .await
+    }
+
+    /// Get an invite of the organization.
+    ///
+    /// See also [Get Invite](https://docs.anthropic.com/en/api/admin-api/invites/get-invite).
+    pub async fn get_invite(
+        &self,
+        invite_id: &str,
+    ) -> Result<Invite, AdminError> {
+        api::get(
+            &self.client,
+            "admin.invites.get",
+            &format!(
+                "{ORGANIZATIONS_PATH}/invites/{}",
+                encode_path_segment(invite_id),
+            ),
+            &[],
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Delete an invite of the organization.
+    ///
+    /// See also [Delete Invite](https://docs.anthropic.com/en/api/admin-api/invites/delete-invite).
+    pub async fn delete_invite(
+        &self,
+        invite_id: &str,
+    ) -> Result<DeletedObject, AdminError> {
+        api::delete(
+            &self.client,
+            "admin.invites.delete",
+            &format!(
+                "{ORGANIZATIONS_PATH}/invites/{}",
+                encode_path_segment(invite_id),
+            ),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Create a workspace.
+    ///
+    /// See also [Create Workspace](https://docs.anthropic.com/en/api/admin-api/workspaces/create-workspace).
+    pub async fn create_workspace(
+        &self,
+        request_body: WorkspaceRequestBody,
+    ) -> Result<Workspace, AdminError> {
+        api::post(
+            &self.client,
+            "admin.workspaces.create",
+            &format!("{ORGANIZATIONS_PATH}/workspaces"),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// List workspaces of the organization.
+    ///
+    /// See also [List Workspaces](https://docs.anthropic.com/en/api/admin-api/workspaces/list-workspaces).
+    pub async fn list_workspaces(
+        &self,
+        parameters: ListParameters,
+    ) -> Result<Page<Workspace>, AdminError> {
+        AdminList::workspaces(parameters)
+            .list(&self.client, RequestOptions::default())
+            .await
+    }
+
+    /// Get a workspace.
+    ///
+    /// See also [Get Workspace](https://docs.anthropic.com/en/api/admin-api/workspaces/get-workspace).
+    pub async fn get_workspace(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Workspace, AdminError> {
+        api::get(
+            &self.client,
+            "admin.workspaces.get",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}",
+                encode_path_segment(workspace_id),
+            ),
+            &[],
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Update the name of a workspace.
+    ///
+    /// See also [Update Workspace](https://docs.anthropic.com/en/api/admin-api/workspaces/update-workspace).
+    pub async fn update_workspace(
+        &self,
+        workspace_id: &str,
+        request_body: WorkspaceRequestBody,
+    ) -> Result<Workspace, AdminError> {
+        api::post(
+            &self.client,
+            "admin.workspaces.update",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}",
+                encode_path_segment(workspace_id),
+            ),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Archive a workspace.
+    ///
+    /// See also [Archive Workspace](https://docs.anthropic.com/en/api/admin-api/workspaces/archive-workspace).
+    pub async fn archive_workspace(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Workspace, AdminError> {
+        api::post(
+            &self.client,
+            "admin.workspaces.archive",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/archive",
+                encode_path_segment(workspace_id),
+            ),
+            &serde_json::json!({}),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Add a user to a workspace.
+    ///
+    /// See also [Create Workspace Member](https://docs.anthropic.com/en/api/admin-api/workspace_members/create-workspace-member).
+    pub async fn add_workspace_member(
+        &self,
+        workspace_id: &str,
+        request_body: AddWorkspaceMemberRequestBody,
+    ) -> Result<WorkspaceMember, AdminError> {
+        api::post(
+            &self.client,
+            "admin.workspace_members.add",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/members",
+                encode_path_segment(workspace_id),
+            ),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// List members of a workspace.
+    ///
+    /// See also [List Workspace Members](https://docs.anthropic.com/en/api/admin-api/workspace_members/list-workspace-members).
+    pub async fn list_workspace_members(
+        &self,
+        workspace_id: &str,
+        parameters: ListParameters,
+    ) -> Result<Page<WorkspaceMember>, AdminError> {
+        AdminList::workspace_members(workspace_id, parameters)
+            .list(&self.client, RequestOptions::default())
+            .await
+    }
+
+    /// Get a member of a workspace.
+    ///
+    /// See also [Get Workspace Member](https://docs.anthropic.com/en/api/admin-api/workspace_members/get-workspace-member).
+    pub async fn get_workspace_member(
+        &self,
+        workspace_id: &str,
+        user_id: &str,
+    ) -> Result<WorkspaceMember, AdminError> {
+        api::get(
+            &self.client,
+            "admin.workspace_members.get",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/members/{}",
+                encode_path_segment(workspace_id),
+                encode_path_segment(user_id),
+            ),
+            &[],
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Update the role of a member of a workspace.
+    ///
+    /// See also [Update Workspace Member](https://docs.anthropic.com/en/api/admin-api/workspace_members/update-workspace-member).
+    pub async fn update_workspace_member(
+        &self,
+        workspace_id: &str,
+        user_id: &str,
+        request_body: UpdateWorkspaceMemberRequestBody,
+    ) -> Result<WorkspaceMember, AdminError> {
+        api::post(
+            &self.client,
+            "admin.workspace_members.update",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/members/{}",
+                encode_path_segment(workspace_id),
+                encode_path_segment(user_id),
+            ),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Remove a member from a workspace.
+    ///
+    /// See also [Delete Workspace Member](https://docs.anthropic.com/en/api/admin-api/workspace_members/delete-workspace-member).
+    pub async fn remove_workspace_member(
+        &self,
+        workspace_id: &str,
+        user_id: &str,
+    ) -> Result<DeletedObject, AdminError> {
+        api::delete(
+            &self.client,
+            "admin.workspace_members.remove",
+            &format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/members/{}",
+                encode_path_segment(workspace_id),
+                encode_path_segment(user_id),
+            ),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// List API keys of the organization.
+    ///
+    /// See also [List API Keys](https://docs.anthropic.com/en/api/admin-api/apikeys/list-api-keys).
+    pub async fn list_api_keys(
+        &self,
+        parameters: ListApiKeysParameters,
+    ) -> Result<Page<OrganizationApiKey>, AdminError> {
+        parameters
+            .list(&self.client, RequestOptions::default())
+            .await
+    }
+
+    /// Get an API key of the organization.
+    ///
+    /// See also [Get API Key](https://docs.anthropic.com/en/api/admin-api/apikeys/get-api-key).
+    pub async fn get_api_key(
+        &self,
+        api_key_id: &str,
+    ) -> Result<OrganizationApiKey, AdminError> {
+        api::get(
+            &self.client,
+            "admin.api_keys.get",
+            &format!(
+                "{ORGANIZATIONS_PATH}/api_keys/{}",
+                encode_path_segment(api_key_id),
+            ),
+            &[],
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Update the name or the status of an API key.
+    ///
+    /// See also [Update API Key](https://docs.anthropic.com/en/api/admin-api/apikeys/update-api-key).
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::admin::{AdminClient, ApiKeyStatus, UpdateApiKeyRequestBody};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let admin = AdminClient::from_env()?;
+    ///
+    ///     admin
+    ///         .update_api_key(
+    ///             "apikey_01Rj2N8SVvo6BePZj99NhmiT",
+    ///             UpdateApiKeyRequestBody {
+    ///                 status: Some(ApiKeyStatus::Inactive),
+    ///                 ..Default::default()
+    ///             },
+    ///         )
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_api_key(
+        &self,
+        api_key_id: &str,
+        request_body: UpdateApiKeyRequestBody,
+    ) -> Result<OrganizationApiKey, AdminError> {
+        api::post(
+            &self.client,
+            "admin.api_keys.update",
+            &format!(
+                "{ORGANIZATIONS_PATH}/api_keys/{}",
+                encode_path_segment(api_key_id),
+            ),
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Get a page of the usage report of the Messages API.
+    ///
+    /// See also [Get Usage Report for the Messages API](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-messages-usage-report).
+    pub async fn get_usage_report_page(
+        &self,
+        parameters: UsageReportParameters,
+    ) -> Result<UsageReportPage, AdminError> {
+        api::get(
+            &self.client,
+            "admin.usage_report.messages",
+            &format!("{ORGANIZATIONS_PATH}/usage_report/messages"),
+            &parameters.query(),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Get the usage report of the Messages API by following all pages.
+    ///
+    /// See also [Get Usage Report for the Messages API](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-messages-usage-report).
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::admin::{
+    ///     AdminClient, BucketWidth, UsageGroupBy, UsageReportParameters,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let admin = AdminClient::from_env()?;
+    ///
+    ///     let buckets = admin
+    ///         .get_usage_report(
+    ///             UsageReportParameters::new("2025-08-01T00:00:00Z")
+    ///                 .ending_at("2025-08-08T00:00:00Z")
+    ///                 .bucket_width(BucketWidth::OneDay)
+    ///                 .group_by(UsageGroupBy::WorkspaceId)
+    ///                 .group_by(UsageGroupBy::Model),
+    ///         )
+    ///         .await?;
+    ///     for bucket in buckets {
+    ///         for result in bucket.results {
+    ///             println!(
+    ///                 "{} {:?} {:?}: {} input, {} output tokens",
+    ///                 bucket.starting_at,
+    ///                 result.workspace_id,
+    ///                 result.model,
+    ///                 result.input_tokens,
+    ///                 result.output_tokens,
+    ///             );
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_usage_report(
+        &self,
+        mut parameters: UsageReportParameters,
+    ) -> Result<Vec<UsageBucket>, AdminError> {
+        let mut buckets = Vec::new();
+        loop {
+            let page = self
+                .get_usage_report_page(parameters.clone())
+                .await?;
+            buckets.extend(page.data);
+            match page.next_page {
+                | Some(next_page) if page.has_more => {
+                    parameters.page = Some(next_page);
+                },
+                | _ => return Ok(buckets),
+            }
+        }
+    }
+
+    /// Get a page of the cost report.
+    ///
+    /// See also [Get Cost Report](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-cost-report).
+    pub async fn get_cost_report_page(
+        &self,
+        parameters: CostReportParameters,
+    ) -> Result<CostReportPage, AdminError> {
+        api::get(
+            &self.client,
+            "admin.cost_report",
+            &format!("{ORGANIZATIONS_PATH}/cost_report"),
+            &parameters.query(),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Get the cost report by following all pages.
+    ///
+    /// See also [Get Cost Report](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-cost-report).
+    pub async fn get_cost_report(
+        &self,
+        mut parameters: CostReportParameters,
+    ) -> Result<Vec<CostBucket>, AdminError> {
+        let mut buckets = Vec::new();
+        loop {
+            let page = self
+                .get_cost_report_page(parameters.clone())
+                .await?;
+            buckets.extend(page.data);
+            match page.next_page {
+                | Some(next_page) if page.has_more => {
+                    parameters.page = Some(next_page);
+                },
+                | _ => return Ok(buckets),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::{
+        AdminObjectType, ApiKeyStatus, OrganizationRole, UsageGroupBy,
+        WorkspaceRole,
+    };
+    use crate::test_server::{StubResponse, StubServer};
+    use futures_util::StreamExt;
+
+    async fn admin_client(
+        responses: Vec<StubResponse>
+    ) -> (AdminClient, StubServer) {
+        let server = StubServer::start(responses).await;
+        let client = ClientBuilder::with_credentials(AdminKey::new("admin-key"))
+            .base_url(server.url(""))
+            .build();
+
+        (AdminClient::from_client(client), server)
+    }
+
+    #[tokio::test]
+    async fn users() {
+        let user = r#"{"id":"user_01","type":"user","email":"user@example.com","name":"User","role":"developer","added_at":"2024-10-30T23:58:27.427722Z"}"#;
+        let (admin, server) = admin_client(vec![
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{user}],"has_more":false,"first_id":"user_01","last_id":"user_01"}}"#
+                ),
+            ),
+            StubResponse::json(200, user),
+            StubResponse::json(200, r#"{"id":"user_01","type":"user_deleted"}"#),
+        ])
+        .await;
+
+        let users = admin
+            .list_users(ListParameters::new().limit(10))
+            .await
+            .unwrap();
+        assert_eq!(users.data[0].role, OrganizationRole::Developer);
+        admin
+            .update_user(
+                "user_01",
+                UpdateUserRequestBody {
+                    role: OrganizationRole::Admin,
+                },
+            )
+            .await
+            .unwrap();
+        let deleted = admin
+            .remove_user("user_01")
+            .await
+            .unwrap();
+        assert_eq!(deleted._type, AdminObjectType::UserDeleted);
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "GET /v1/organizations/users?limit=10 HTTP/1.1"
+        );
+        assert_eq!(
+            requests[0].header("x-api-key"),
+            Some("admin-key")
+        );
+        assert_eq!(
+            requests[1].request_line,
+            "POST /v1/organizations/users/user_01 HTTP/1.1"
+        );
+        assert_eq!(requests[1].body_text(), r#"{"role":"admin"}"#);
+        assert_eq!(
+            requests[2].request_line,
+            "DELETE /v1/organizations/users/user_01 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn encode_ids() {
+        let (admin, server) = admin_client(vec![StubResponse::json(
+            200,
+            r#"{"type":"workspace_member_deleted","user_id":"user_01","workspace_id":"wrkspc_01"}"#,
+        )])
+        .await;
+
+        admin
+            .remove_workspace_member("wrkspc/01", "user?01")
+            .await
+            .unwrap();
+        assert_eq!(
+            server.requests()[0].request_line,
+            "DELETE /v1/organizations/workspaces/wrkspc%2F01/members/user%3F01 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn workspace_members() {
+        let (admin, server) = admin_client(vec![
+            StubResponse::json(
+                200,
+                r#"{"type":"workspace_member","user_id":"user_01","workspace_id":"wrkspc_01","workspace_role":"workspace_developer"}"#,
+            ),
+            StubResponse::json(
+                200,
+                r#"{"type":"workspace_member_deleted","user_id":"user_01","workspace_id":"wrkspc_01"}"#,
+            ),
+        ])
+        .await;
+
+        let member = admin
+            .add_workspace_member(
+                "wrkspc_01",
+                AddWorkspaceMemberRequestBody {
+                    user_id: "user_01".to_string(),
+                    workspace_role: WorkspaceRole::WorkspaceDeveloper,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            member.workspace_role,
+            WorkspaceRole::WorkspaceDeveloper
+        );
+        let deleted = admin
+            .remove_workspace_member("wrkspc_01", "user_01")
+            .await
+            .unwrap();
+        assert_eq!(deleted.id, "user_01");
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "POST /v1/organizations/workspaces/wrkspc_01/members HTTP/1.1"
+        );
+        assert_eq!(
+            requests[1].request_line,
+            "DELETE /v1/organizations/workspaces/wrkspc_01/members/user_01 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn paginate_workspace_members() {
+        let member = |user_id: &str| {
+            format!(
+                r#"{{"type":"workspace_member","user_id":"{user_id}","workspace_id":"wrkspc_01","workspace_role":"workspace_user"}}"#
+            )
+        };
+        let (admin, server) = admin_client(vec![
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{}],"has_more":true,"first_id":"user_01","last_id":"user_01"}}"#,
+                    member("user_01")
+                ),
+            ),
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{}],"has_more":false,"first_id":"user_02","last_id":"user_02"}}"#,
+                    member("user_02")
+                ),
+            ),
+        ])
+        .await;
+
+        let user_ids = admin
+            .paginate(AdminList::workspace_members(
+                "wrkspc_01",
+                ListParameters::new().limit(1),
+            ))
+            .map(|member| member.unwrap().user_id)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(user_ids, ["user_01", "user_02"]);
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[1].request_line,
+            "GET /v1/organizations/workspaces/wrkspc_01/members?after_id=user_01&limit=1 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn api_keys() {
+        let api_key = r#"{"id":"apikey_01","type":"api_key","name":"Key","workspace_id":null,"created_at":"2024-10-30T23:58:27.427722Z","created_by":{"id":"user_01","type":"user"},"partial_key_hint":"sk-ant-api03-R2D...igAA","status":"inactive"}"#;
+        let (admin, server) = admin_client(vec![
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{api_key}],"has_more":true,"first_id":"apikey_01","last_id":"apikey_01"}}"#
+                ),
+            ),
+            StubResponse::json(200, api_key),
+        ])
+        .await;
+
+        let api_keys = admin
+            .list_api_keys(
+                ListApiKeysParameters::new(ListParameters::new().limit(1))
+                    .status(ApiKeyStatus::Active),
+            )
+            .await
+            .unwrap();
+        assert!(api_keys.has_more);
+        let updated = admin
+            .update_api_key(
+                "apikey_01",
+                UpdateApiKeyRequestBody {
+                    status: Some(ApiKeyStatus::Inactive),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.status, ApiKeyStatus::Inactive);
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "GET /v1/organizations/api_keys?limit=1&status=active HTTP/1.1"
+        );
+        assert_eq!(
+            requests[1].body_text(),
+            r#"{"status":"inactive"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn usage_report_follows_pages() {
+        let result = r#"{"uncached_input_tokens":100,"cache_creation":{"ephemeral_1h_input_tokens":0,"ephemeral_5m_input_tokens":20},"cache_read_input_tokens":30,"output_tokens":40,"server_tool_use":null,"api_key_id":null,"workspace_id":"wrkspc_01","model":null,"service_tier":null,"context_window":null}"#;
+        let (admin, server) = admin_client(vec![
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{{"starting_at":"2025-08-01T00:00:00Z","ending_at":"2025-08-02T00:00:00Z","results":[{result}]}}],"has_more":true,"next_page":"page_02"}}"#
+                ),
+            ),
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{{"starting_at":"2025-08-02T00:00:00Z","ending_at":"2025-08-03T00:00:00Z","results":[{result}]}}],"has_more":false,"next_page":null}}"#
+                ),
+            ),
+        ])
+        .await;
+
+        let buckets = admin
+            .get_usage_report(
+                UsageReportParameters::new("2025-08-01T00:00:00Z")
+                    .group_by(UsageGroupBy::WorkspaceId),
+            )
+            .await
+            .unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[1].starting_at, "2025-08-02T00:00:00Z");
+        assert_eq!(buckets[1].results[0].input_tokens, 100);
+        assert_eq!(
+            buckets[1].results[0].cache_creation_input_tokens(),
+            20
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "GET /v1/organizations/usage_report/messages?starting_at=2025-08-01T00%3A00%3A00Z&group_by%5B%5D=workspace_id HTTP/1.1"
+        );
+        assert_eq!(
+            requests[1].request_line,
+            "GET /v1/organizations/usage_report/messages?starting_at=2025-08-01T00%3A00%3A00Z&group_by%5B%5D=workspace_id&page=page_02 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn cost_report() {
+        let (admin, server) = admin_client(vec![StubResponse::json(
+            200,
+            r#"{"data":[{"starting_at":"2025-08-01T00:00:00Z","ending_at":"2025-08-02T00:00:00Z","results":[{"currency":"USD","amount":"1234.5","workspace_id":null,"description":null,"cost_type":null,"context_window":null,"model":null,"service_tier":null,"token_type":null}]}],"has_more":false,"next_page":null}"#,
+        )])
+        .await;
+
+        let buckets = admin
+            .get_cost_report(CostReportParameters::new(
+                "2025-08-01T00:00:00Z",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(buckets[0].results[0].amount, "1234.5");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn api_error() {
+        let (admin, _server) = admin_client(vec![StubResponse::json(
+            401,
+            r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#,
+        )])
+        .await;
+
+        let result = admin.get_workspace("wrkspc_01").await;
+        assert!(matches!(
+            result,
+            Err(AdminError::ApiError(error))
+                if error._type == crate::ApiErrorType::AuthenticationError
+        ));
+    }
+}
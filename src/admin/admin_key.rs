@@ -0,0 +1,67 @@
+use std::env::VarError;
+
+use http::HeaderMap;
+
+use crate::credentials::{api_key_headers, CredentialError, CredentialProvider};
+use crate::transport::BoxFuture;
+
+/// The admin API key of an organization, starting with `sk-ant-admin`.
+///
+/// The value is redacted in the `Debug` format.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct AdminKey {
+    value: String,
+}
+
+impl AdminKey {
+    /// Creates a new admin API key.
+    pub fn new<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    /// Loads the admin API key from the environment variable: `ANTHROPIC_ADMIN_KEY`.
+    pub fn from_env() -> Result<Self, VarError> {
+        let value = std::env::var("ANTHROPIC_ADMIN_KEY")?;
+        Ok(Self::new(value))
+    }
+}
+
+impl std::fmt::Debug for AdminKey {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_tuple("AdminKey")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+impl CredentialProvider for AdminKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async { api_key_headers(&self.value) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn headers() {
+        let admin_key = AdminKey::new("sk-ant-admin-secret");
+        assert_eq!(
+            format!("{admin_key:?}"),
+            r#"AdminKey("[REDACTED]")"#
+        );
+        assert_eq!(
+            admin_key.headers().await.unwrap()["x-api-key"],
+            "sk-ant-admin-secret"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use http::Method;
+use serde::de::DeserializeOwned;
+
+use crate::admin::AdminError;
+use crate::request::{call_json, with_query};
+use crate::{Client, RequestOptions};
+
+/// The path of the organization endpoints of the Admin API.
+pub(crate) const ORGANIZATIONS_PATH: &str = "/v1/organizations";
+
+/// Gets the object at the path with the query parameters.
+pub(crate) async fn get<T>(
+    client: &Client,
+    operation: &'static str,
+    path: &str,
+    query: &[(&str, String)],
+    options: &RequestOptions,
+) -> Result<T, AdminError>
+where
+    T: DeserializeOwned,
+{
+    call_json(
+        client,
+        operation,
+        path,
+        |endpoint| {
+            client.request(Method::GET, &with_query(endpoint, query), &[])
+        },
+        options,
+    )
+    .await
+}
+
+/// Posts the JSON body to the path.
+pub(crate) async fn post<T, B>(
+    client: &Client,
+    operation: &'static str,
+    path: &str,
+    body: &B,
+    options: &RequestOptions,
+) -> Result<T, AdminError>
+where
+    T: DeserializeOwned,
+    B: serde::Serialize,
+{
+    call_json(
+        client,
+        operation,
+        path,
+        |endpoint| client.post(endpoint, body, &[]),
+        options,
+    )
+    .await
+}
+
+/// Deletes the object at the path.
+pub(crate) async fn delete<T>(
+    client: &Client,
+    operation: &'static str,
+    path: &str,
+    options: &RequestOptions,
+) -> Result<T, AdminError>
+where
+    T: DeserializeOwned,
+{
+    call_json(
+        client,
+        operation,
+        path,
+        |endpoint| client.request(Method::DELETE, endpoint, &[]),
+        options,
+    )
+    .await
+}
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use crate::admin::api::{self, ORGANIZATIONS_PATH};
+use crate::admin::{AdminError, AdminObjectType, ListParameters};
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization_with_other,
+};
+use crate::transport::BoxFuture;
+use crate::{Client, Page, Paginate, RequestOptions};
+
+/// An API key of the organization, with only a hint of the key value.
+///
+/// See also [Get API Key](https://docs.anthropic.com/en/api/admin-api/apikeys/get-api-key).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct OrganizationApiKey {
+    /// ID of the API key.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For API keys, this is always "api_key".
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+    /// Name of the API key.
+    pub name: String,
+    /// ID of the workspace of the API key, or `None` for the default workspace.
+    pub workspace_id: Option<String>,
+    /// RFC 3339 datetime string indicating when the API key was created.
+    pub created_at: String,
+    /// The creator of the API key.
+    pub created_by: ApiKeyCreator,
+    /// Partially redacted hint of the API key.
+    pub partial_key_hint: Option<String>,
+    /// Status of the API key.
+    pub status: ApiKeyStatus,
+}
+
+impl_display_for_serialize!(OrganizationApiKey);
+
+/// The creator of an API key.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ApiKeyCreator {
+    /// ID of the creator.
+    pub id: String,
+    /// Type of the creator, e.g. `user`.
+    #[serde(rename = "type")]
+    pub _type: String,
+}
+
+/// The status of an API key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ApiKeyStatus {
+    /// active
+    Active,
+    /// inactive
+    Inactive,
+    /// archived
+    Archived,
+    /// Other status not defined in this crate.
+    Other(String),
+}
+
+impl Default for ApiKeyStatus {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl Display for ApiKeyStatus {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ApiKeyStatus::Active => write!(f, "active"),
+            | ApiKeyStatus::Inactive => write!(f, "inactive"),
+            | ApiKeyStatus::Archived => write!(f, "archived"),
+            | ApiKeyStatus::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl_enum_string_serialization_with_other!(
+    ApiKeyStatus,
+    Active => "active",
+    Inactive => "inactive",
+    Archived => "archived";
+    Other(String)
+);
+
+/// The request body to update an API key.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct UpdateApiKeyRequestBody {
+    /// New name of the API key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New status of the API key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ApiKeyStatus>,
+}
+
+impl_display_for_serialize!(UpdateApiKeyRequestBody);
+
+/// The query parameters to list API keys.
+///
+/// ## Example
+/// ```
+/// use clust::admin::{ApiKeyStatus, ListApiKeysParameters, ListParameters};
+///
+/// let parameters = ListApiKeysParameters::new(ListParameters::new().limit(100))
+///     .workspace_id("wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ")
+///     .status(ApiKeyStatus::Active);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ListApiKeysParameters {
+    /// The pagination parameters.
+    pub pagination: ListParameters,
+    /// Filters by the workspace ID.
+    pub workspace_id: Option<String>,
+    /// Filters by the status.
+    pub status: Option<ApiKeyStatus>,
+    /// Filters by the ID of the user who created the API key.
+    pub created_by_user_id: Option<String>,
+}
+
+impl ListApiKeysParameters {
+    /// Creates new parameters with the pagination parameters.
+    pub fn new(pagination: ListParameters) -> Self {
+        Self {
+            pagination,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the workspace ID to filter by.
+    pub fn workspace_id<S>(
+        mut self,
+        workspace_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.workspace_id = Some(workspace_id.into());
+        self
+    }
+
+    /// Sets the status to filter by.
+    pub fn status(
+        mut self,
+        status: ApiKeyStatus,
+    ) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets the ID of the user who created the API key to filter by.
+    pub fn created_by_user_id<S>(
+        mut self,
+        created_by_user_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.created_by_user_id = Some(created_by_user_id.into());
+        self
+    }
+
+    /// Gets the query pairs.
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = self.pagination.query();
+        if let Some(workspace_id) = &self.workspace_id {
+            query.push(("workspace_id", workspace_id.clone()));
+        }
+        if let Some(status) = &self.status {
+            query.push(("status", status.to_string()));
+        }
+        if let Some(created_by_user_id) = &self.created_by_user_id {
+            query.push((
+                "created_by_user_id",
+                created_by_user_id.clone(),
+            ));
+        }
+        query
+    }
+}
+
+impl Paginate for ListApiKeysParameters {
+    type Item = OrganizationApiKey;
+    type Error = AdminError;
+
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>> {
+        Box::pin(async move {
+            api::get(
+                client,
+                "admin.api_keys.list",
+                &format!("{ORGANIZATIONS_PATH}/api_keys"),
+                &self.query(),
+                &options,
+            )
+            .await
+        })
+    }
+
+    fn next_page(
+        mut self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self> {
+        self.pagination = self.pagination.next_page(page)?;
+        Some(self)
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt::Display;
+
+use crate::admin::{BucketWidth, ReportBucket, ReportPage};
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+
+/// A time bucket of the cost report.
+pub type CostBucket = ReportBucket<CostResult>;
+
+/// A page of the cost report.
+pub type CostReportPage = ReportPage<CostResult>;
+
+/// The cost of a combination of the grouped dimensions in a time bucket.
+///
+/// See also [Get Cost Report](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-cost-report).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CostResult {
+    /// The currency code of the cost, e.g. `USD`.
+    pub currency: String,
+    /// The cost as a decimal string in the lowest units of the currency, e.g. cents for `USD`.
+    pub amount: String,
+    /// The workspace ID when grouped by `workspace_id`.
+    pub workspace_id: Option<String>,
+    /// The description of the cost item when grouped by `description`.
+    pub description: Option<String>,
+    /// The type of the cost, e.g. `tokens` or `web_search`, when grouped by `description`.
+    pub cost_type: Option<String>,
+    /// The context window when grouped by `description`.
+    pub context_window: Option<String>,
+    /// The model when grouped by `description`.
+    pub model: Option<String>,
+    /// The service tier when grouped by `description`.
+    pub service_tier: Option<String>,
+    /// The type of the tokens, e.g. `uncached_input_tokens`, when grouped by `description`.
+    pub token_type: Option<String>,
+}
+
+impl_display_for_serialize!(CostResult);
+
+/// The dimension to group the cost report by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CostGroupBy {
+    /// workspace_id
+    WorkspaceId,
+    /// description
+    Description,
+}
+
+impl Display for CostGroupBy {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CostGroupBy::WorkspaceId => write!(f, "workspace_id"),
+            | CostGroupBy::Description => write!(f, "description"),
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CostGroupBy,
+    WorkspaceId => "workspace_id",
+    Description => "description"
+);
+
+/// The query parameters of the cost report.
+///
+/// ## Example
+/// ```
+/// use clust::admin::{CostGroupBy, CostReportParameters};
+///
+/// let parameters = CostReportParameters::new("2025-08-01T00:00:00Z")
+///     .ending_at("2025-09-01T00:00:00Z")
+///     .group_by(CostGroupBy::WorkspaceId)
+///     .group_by(CostGroupBy::Description);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CostReportParameters {
+    /// Start of the report (inclusive) in RFC 3339 format.
+    pub starting_at: String,
+    /// End of the report (exclusive) in RFC 3339 format.
+    pub ending_at: Option<String>,
+    /// The width of the time buckets, only [`BucketWidth::OneDay`] is supported.
+    pub bucket_width: Option<BucketWidth>,
+    /// The dimensions to group the results by.
+    pub group_by: Vec<CostGroupBy>,
+    /// The maximum number of time buckets in a page.
+    pub limit: Option<u32>,
+    /// The cursor of the page to get.
+    pub page: Option<String>,
+}
+
+impl CostReportParameters {
+    /// Creates new parameters starting at the time in RFC 3339 format.
+    pub fn new<S>(starting_at: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            starting_at: starting_at.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the end of the report (exclusive) in RFC 3339 format.
+    pub fn ending_at<S>(
+        mut self,
+        ending_at: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ending_at = Some(ending_at.into());
+        self
+    }
+
+    /// Sets the width of the time buckets.
+    pub fn bucket_width(
+        mut self,
+        bucket_width: BucketWidth,
+    ) -> Self {
+        self.bucket_width = Some(bucket_width);
+        self
+    }
+
+    /// Adds the dimension to group the results by.
+    pub fn group_by(
+        mut self,
+        group_by: CostGroupBy,
+    ) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Sets the maximum number of time buckets in a page.
+    pub fn limit(
+        mut self,
+        limit: u32,
+    ) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the cursor of the page to get.
+    pub fn page<S>(
+        mut self,
+        page: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// Gets the query pairs.
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", self.starting_at.clone())];
+        if let Some(ending_at) = &self.ending_at {
+            query.push(("ending_at", ending_at.clone()));
+        }
+        if let Some(bucket_width) = self.bucket_width {
+            query.push(("bucket_width", bucket_width.to_string()));
+        }
+        for group_by in &self.group_by {
+            query.push(("group_by[]", group_by.to_string()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_cost_result() {
+        let result: CostResult = serde_json::from_str(
+            r#"{"currency":"USD","amount":"123.78912","workspace_id":"wrkspc_01","description":"Claude Sonnet 4 Usage - Input Tokens","cost_type":"tokens","context_window":"0-200k","model":"claude-sonnet-4-20250514","service_tier":"standard","token_type":"uncached_input_tokens"}"#,
+        )
+        .unwrap();
+        assert_eq!(result.amount, "123.78912");
+        assert_eq!(result.cost_type, Some("tokens".to_string()));
+    }
+
+    #[test]
+    fn query() {
+        let parameters = CostReportParameters::new("2025-08-01T00:00:00Z")
+            .ending_at("2025-09-01T00:00:00Z")
+            .group_by(CostGroupBy::Description);
+
+        assert_eq!(
+            parameters.query(),
+            vec![
+                ("starting_at", "2025-08-01T00:00:00Z".to_string()),
+                ("ending_at", "2025-09-01T00:00:00Z".to_string()),
+                ("group_by[]", "description".to_string()),
+            ]
+        );
+    }
+}
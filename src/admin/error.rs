@@ -0,0 +1,26 @@
+use crate::request::impl_call_error;
+use crate::transport::TransportError;
+use crate::{ApiError, ClientError};
+use std::time::Duration;
+
+/// The error type for the Admin API.
+#[derive(Debug, thiserror::Error)]
+pub enum AdminError {
+    /// The client error.
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    /// The API error.
+    #[error(transparent)]
+    ApiError(#[from] ApiError),
+    /// Timed out connecting to the server.
+    #[error("Connect timed out: {0}")]
+    ConnectTimeout(TransportError),
+    /// Timed out until the response headers are received.
+    #[error("Response headers timed out after {0:?}")]
+    HeadersTimeout(Duration),
+    /// Timed out the whole call.
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+impl_call_error!(AdminError);
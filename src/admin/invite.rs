@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use crate::admin::{AdminObjectType, OrganizationRole};
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization_with_other,
+};
+
+/// An invite to the organization.
+///
+/// See also [Get Invite](https://docs.anthropic.com/en/api/admin-api/invites/get-invite).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct Invite {
+    /// ID of the invite.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For invites, this is always "invite".
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+    /// Email of the user being invited.
+    pub email: String,
+    /// Organization role of the user.
+    pub role: OrganizationRole,
+    /// RFC 3339 datetime string indicating when the invite was created.
+    pub invited_at: String,
+    /// RFC 3339 datetime string indicating when the invite expires.
+    pub expires_at: String,
+    /// Status of the invite.
+    pub status: InviteStatus,
+}
+
+impl_display_for_serialize!(Invite);
+
+/// The status of an invite.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InviteStatus {
+    /// pending
+    Pending,
+    /// accepted
+    Accepted,
+    /// expired
+    Expired,
+    /// deleted
+    Deleted,
+    /// Other status not defined in this crate.
+    Other(String),
+}
+
+impl Default for InviteStatus {
+    fn default() -> Self {
+        Self::Pending
+    }
+}
+
+impl Display for InviteStatus {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | InviteStatus::Pending => write!(f, "pending"),
+            | InviteStatus::Accepted => write!(f, "accepted"),
+            | InviteStatus::Expired => write!(f, "expired"),
+            | InviteStatus::Deleted => write!(f, "deleted"),
+            | InviteStatus::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl_enum_string_serialization_with_other!(
+    InviteStatus,
+    Pending => "pending",
+    Accepted => "accepted",
+    Expired => "expired",
+    Deleted => "deleted";
+    Other(String)
+);
+
+/// The request body to create an invite.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CreateInviteRequestBody {
+    /// Email of the user.
+    pub email: String,
+    /// Role for the invited user. Cannot be `admin`.
+    pub role: OrganizationRole,
+}
+
+impl_display_for_serialize!(CreateInviteRequestBody);
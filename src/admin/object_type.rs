@@ -0,0 +1,87 @@
+use std::fmt::Display;
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization_with_other,
+};
+
+/// The object type of the Admin API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AdminObjectType {
+    /// user
+    User,
+    /// user_deleted
+    UserDeleted,
+    /// invite
+    Invite,
+    /// invite_deleted
+    InviteDeleted,
+    /// workspace
+    Workspace,
+    /// workspace_member
+    WorkspaceMember,
+    /// workspace_member_deleted
+    WorkspaceMemberDeleted,
+    /// api_key
+    ApiKey,
+    /// Other object type not defined in this crate.
+    Other(String),
+}
+
+impl Default for AdminObjectType {
+    fn default() -> Self {
+        Self::User
+    }
+}
+
+impl Display for AdminObjectType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | AdminObjectType::User => write!(f, "user"),
+            | AdminObjectType::UserDeleted => write!(f, "user_deleted"),
+            | AdminObjectType::Invite => write!(f, "invite"),
+            | AdminObjectType::InviteDeleted => write!(f, "invite_deleted"),
+            | AdminObjectType::Workspace => write!(f, "workspace"),
+            | AdminObjectType::WorkspaceMember => {
+                write!(f, "workspace_member")
+            },
+            | AdminObjectType::WorkspaceMemberDeleted => {
+                write!(f, "workspace_member_deleted")
+            },
+            | AdminObjectType::ApiKey => write!(f, "api_key"),
+            | AdminObjectType::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl_enum_string_serialization_with_other!(
+    AdminObjectType,
+    User => "user",
+    UserDeleted => "user_deleted",
+    Invite => "invite",
+    InviteDeleted => "invite_deleted",
+    Workspace => "workspace",
+    WorkspaceMember => "workspace_member",
+    WorkspaceMemberDeleted => "workspace_member_deleted",
+    ApiKey => "api_key";
+    Other(String)
+);
+
+/// The response body of deleting or removing an object by the Admin API.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct DeletedObject {
+    /// ID of the deleted object.
+    ///
+    /// For workspace members, this is the ID of the user.
+    #[serde(alias = "user_id")]
+    pub id: String,
+    /// Deleted object type, e.g. `user_deleted`.
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+}
+
+impl_display_for_serialize!(DeletedObject);
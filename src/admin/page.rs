@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::admin::api::{self, ORGANIZATIONS_PATH};
+use crate::admin::{AdminError, Invite, User, Workspace, WorkspaceMember};
+use crate::request::encode_path_segment;
+use crate::transport::BoxFuture;
+use crate::{Client, CursorParameters, Page, Paginate, RequestOptions};
+
+/// The pagination parameters shared by the list endpoints of the Admin API.
+///
+/// ## Example
+/// ```
+/// use clust::admin::ListParameters;
+///
+/// let parameters = ListParameters::new()
+///     .after_id("user_01WCz1FkmYMm4gnmykNKUu3Q")
+///     .limit(100);
+/// ```
+pub type ListParameters = CursorParameters;
+
+/// A list endpoint of the Admin API with the pagination parameters,
+/// to be paginated by [`crate::admin::AdminClient::paginate`].
+///
+/// ## Example
+/// ```
+/// use clust::admin::{AdminList, ListParameters};
+///
+/// let users = AdminList::users(ListParameters::new().limit(100));
+/// let members = AdminList::workspace_members(
+///     "wrkspc_01JwQvzr7rXLA5AGx3HKfFUJ",
+///     ListParameters::new(),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdminList<T> {
+    operation: &'static str,
+    path: String,
+    /// The pagination parameters.
+    pub parameters: ListParameters,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> AdminList<T> {
+    fn new(
+        operation: &'static str,
+        path: String,
+        parameters: ListParameters,
+    ) -> Self {
+        Self {
+            operation,
+            path,
+            parameters,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl AdminList<User> {
+    /// Lists users of the organization.
+    pub fn users(parameters: ListParameters) -> Self {
+        Self::new(
+            "admin.users.list",
+            format!("{ORGANIZATIONS_PATH}/users"),
+            parameters,
+        )
+    }
+}
+
+impl AdminList<Invite> {
+    /// Lists invites of the organization.
+    pub fn invites(parameters: ListParameters) -> Self {
+        Self::new(
+            "admin.invites.list",
+            format!("{ORGANIZATIONS_PATH}/invites"),
+            parameters,
+        )
+    }
+}
+
+impl AdminList<Workspace> {
+    /// Lists workspaces of the organization.
+    pub fn workspaces(parameters: ListParameters) -> Self {
+        Self::new(
+            "admin.workspaces.list",
+            format!("{ORGANIZATIONS_PATH}/workspaces"),
+            parameters,
+        )
+    }
+}
+
+impl AdminList<WorkspaceMember> {
+    /// Lists members of the workspace.
+    pub fn workspace_members(
+        workspace_id: &str,
+        parameters: ListParameters,
+    ) -> Self {
+        Self::new(
+            "admin.workspace_members.list",
+            format!(
+                "{ORGANIZATIONS_PATH}/workspaces/{}/members",
+                encode_path_segment(workspace_id),
+            ),
+            parameters,
+        )
+    }
+}
+
+impl<T> Paginate for AdminList<T>
+where
+    T: DeserializeOwned + Clone + Send + 'static,
+{
+    type Item = T;
+    type Error = AdminError;
+
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>> {
+        Box::pin(async move {
+            api::get(
+                client,
+                self.operation,
+                &self.path,
+                &self.parameters.query(),
+                &options,
+            )
+            .await
+        })
+    }
+
+    fn next_page(
+        mut self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self> {
+        self.parameters = self.parameters.next_page(page)?;
+        Some(self)
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use crate::macros::impl_enum_string_serialization;
+
+/// A page of a report of the Admin API.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ReportPage<T> {
+    /// The time buckets of this page.
+    pub data: Vec<ReportBucket<T>>,
+    /// Indicates if there are more results.
+    pub has_more: bool,
+    /// The cursor of the next page. Can be used as the `page` parameter for the next page.
+    pub next_page: Option<String>,
+}
+
+impl<T> Display for ReportPage<T>
+where
+    T: serde::Serialize,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
+
+/// A time bucket of a report.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ReportBucket<T> {
+    /// Start of the time bucket (inclusive) in RFC 3339 format.
+    pub starting_at: String,
+    /// End of the time bucket (exclusive) in RFC 3339 format.
+    pub ending_at: String,
+    /// The results in the time bucket, one per combination of the grouped dimensions.
+    pub results: Vec<T>,
+}
+
+/// The width of the time buckets of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BucketWidth {
+    /// 1m
+    OneMinute,
+    /// 1h
+    OneHour,
+    /// 1d
+    OneDay,
+}
+
+impl Default for BucketWidth {
+    fn default() -> Self {
+        Self::OneDay
+    }
+}
+
+impl Display for BucketWidth {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | BucketWidth::OneMinute => write!(f, "1m"),
+            | BucketWidth::OneHour => write!(f, "1h"),
+            | BucketWidth::OneDay => write!(f, "1d"),
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    BucketWidth,
+    OneMinute => "1m",
+    OneHour => "1h",
+    OneDay => "1d"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_report_page() {
+        let page: ReportPage<serde_json::Value> = serde_json::from_str(
+            r#"{"data":[{"starting_at":"2025-08-01T00:00:00Z","ending_at":"2025-08-02T00:00:00Z","results":[]}],"has_more":true,"next_page":"page_01"}"#,
+        )
+        .unwrap();
+        assert_eq!(page.data[0].ending_at, "2025-08-02T00:00:00Z");
+        assert_eq!(page.next_page, Some("page_01".to_string()));
+    }
+
+    #[test]
+    fn display_bucket_width() {
+        assert_eq!(BucketWidth::OneMinute.to_string(), "1m");
+        assert_eq!(BucketWidth::OneHour.to_string(), "1h");
+        assert_eq!(BucketWidth::OneDay.to_string(), "1d");
+    }
+}
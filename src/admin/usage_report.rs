@@ -0,0 +1,379 @@
+use std::fmt::Display;
+
+use crate::admin::{BucketWidth, ReportBucket, ReportPage};
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+
+/// A time bucket of the usage report of the Messages API.
+pub type UsageBucket = ReportBucket<UsageResult>;
+
+/// A page of the usage report of the Messages API.
+pub type UsageReportPage = ReportPage<UsageResult>;
+
+/// The token usage of a combination of the grouped dimensions in a time bucket.
+///
+/// The token fields line up with [`crate::messages::Usage`].
+///
+/// See also [Get Usage Report for the Messages API](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-messages-usage-report).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct UsageResult {
+    /// The number of input tokens which were not read from or written to the cache.
+    #[serde(rename = "uncached_input_tokens")]
+    pub input_tokens: u64,
+    /// The number of output tokens.
+    pub output_tokens: u64,
+    /// The number of input tokens read from the cache.
+    pub cache_read_input_tokens: u64,
+    /// The number of input tokens written to the cache by the TTL.
+    pub cache_creation: UsageCacheCreation,
+    /// The server tool usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_tool_use: Option<UsageServerToolUse>,
+    /// The API key ID when grouped by `api_key_id`.
+    pub api_key_id: Option<String>,
+    /// The workspace ID when grouped by `workspace_id`.
+    pub workspace_id: Option<String>,
+    /// The model when grouped by `model`.
+    pub model: Option<String>,
+    /// The service tier when grouped by `service_tier`.
+    pub service_tier: Option<String>,
+    /// The context window when grouped by `context_window`.
+    pub context_window: Option<String>,
+}
+
+impl_display_for_serialize!(UsageResult);
+
+impl UsageResult {
+    /// The total number of input tokens written to the cache.
+    pub fn cache_creation_input_tokens(&self) -> u64 {
+        self.cache_creation
+            .ephemeral_5m_input_tokens
+            + self
+                .cache_creation
+                .ephemeral_1h_input_tokens
+    }
+}
+
+/// The number of input tokens written to the cache by the TTL in a usage report.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct UsageCacheCreation {
+    /// The number of input tokens written to the 5 minutes cache.
+    pub ephemeral_5m_input_tokens: u64,
+    /// The number of input tokens written to the 1 hour cache.
+    pub ephemeral_1h_input_tokens: u64,
+}
+
+/// The server tool usage in a usage report.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct UsageServerToolUse {
+    /// The number of web search requests.
+    pub web_search_requests: u64,
+}
+
+/// The dimension to group the usage report by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageGroupBy {
+    /// api_key_id
+    ApiKeyId,
+    /// workspace_id
+    WorkspaceId,
+    /// model
+    Model,
+    /// service_tier
+    ServiceTier,
+    /// context_window
+    ContextWindow,
+}
+
+impl Display for UsageGroupBy {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | UsageGroupBy::ApiKeyId => write!(f, "api_key_id"),
+            | UsageGroupBy::WorkspaceId => write!(f, "workspace_id"),
+            | UsageGroupBy::Model => write!(f, "model"),
+            | UsageGroupBy::ServiceTier => write!(f, "service_tier"),
+            | UsageGroupBy::ContextWindow => write!(f, "context_window"),
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    UsageGroupBy,
+    ApiKeyId => "api_key_id",
+    WorkspaceId => "workspace_id",
+    Model => "model",
+    ServiceTier => "service_tier",
+    ContextWindow => "context_window"
+);
+
+/// The query parameters of the usage report of the Messages API.
+///
+/// ## Example
+/// ```
+/// use clust::admin::{BucketWidth, UsageGroupBy, UsageReportParameters};
+///
+/// let parameters = UsageReportParameters::new("2025-08-01T00:00:00Z")
+///     .ending_at("2025-08-08T00:00:00Z")
+///     .bucket_width(BucketWidth::OneDay)
+///     .group_by(UsageGroupBy::WorkspaceId)
+///     .group_by(UsageGroupBy::Model)
+///     .model("claude-sonnet-4-20250514");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UsageReportParameters {
+    /// Start of the report (inclusive) in RFC 3339 format.
+    pub starting_at: String,
+    /// End of the report (exclusive) in RFC 3339 format.
+    pub ending_at: Option<String>,
+    /// The width of the time buckets.
+    pub bucket_width: Option<BucketWidth>,
+    /// The dimensions to group the results by.
+    pub group_by: Vec<UsageGroupBy>,
+    /// Filters by the API key IDs.
+    pub api_key_ids: Vec<String>,
+    /// Filters by the workspace IDs.
+    pub workspace_ids: Vec<String>,
+    /// Filters by the models.
+    pub models: Vec<String>,
+    /// Filters by the service tiers.
+    pub service_tiers: Vec<String>,
+    /// Filters by the context windows.
+    pub context_window: Vec<String>,
+    /// The maximum number of time buckets in a page.
+    pub limit: Option<u32>,
+    /// The cursor of the page to get.
+    pub page: Option<String>,
+}
+
+impl UsageReportParameters {
+    /// Creates new parameters starting at the time in RFC 3339 format.
+    pub fn new<S>(starting_at: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            starting_at: starting_at.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the end of the report (exclusive) in RFC 3339 format.
+    pub fn ending_at<S>(
+        mut self,
+        ending_at: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ending_at = Some(ending_at.into());
+        self
+    }
+
+    /// Sets the width of the time buckets.
+    pub fn bucket_width(
+        mut self,
+        bucket_width: BucketWidth,
+    ) -> Self {
+        self.bucket_width = Some(bucket_width);
+        self
+    }
+
+    /// Adds the dimension to group the results by.
+    pub fn group_by(
+        mut self,
+        group_by: UsageGroupBy,
+    ) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Adds the API key ID to filter by.
+    pub fn api_key_id<S>(
+        mut self,
+        api_key_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.api_key_ids
+            .push(api_key_id.into());
+        self
+    }
+
+    /// Adds the workspace ID to filter by.
+    pub fn workspace_id<S>(
+        mut self,
+        workspace_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.workspace_ids
+            .push(workspace_id.into());
+        self
+    }
+
+    /// Adds the model to filter by.
+    pub fn model<S>(
+        mut self,
+        model: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.models.push(model.into());
+        self
+    }
+
+    /// Adds the service tier to filter by.
+    pub fn service_tier<S>(
+        mut self,
+        service_tier: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.service_tiers
+            .push(service_tier.into());
+        self
+    }
+
+    /// Adds the context window to filter by.
+    pub fn context_window<S>(
+        mut self,
+        context_window: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.context_window
+            .push(context_window.into());
+        self
+    }
+
+    /// Sets the maximum number of time buckets in a page.
+    pub fn limit(
+        mut self,
+        limit: u32,
+    ) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the cursor of the page to get.
+    pub fn page<S>(
+        mut self,
+        page: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// Gets the query pairs.
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", self.starting_at.clone())];
+        if let Some(ending_at) = &self.ending_at {
+            query.push(("ending_at", ending_at.clone()));
+        }
+        if let Some(bucket_width) = self.bucket_width {
+            query.push(("bucket_width", bucket_width.to_string()));
+        }
+        for group_by in &self.group_by {
+            query.push(("group_by[]", group_by.to_string()));
+        }
+        for api_key_id in &self.api_key_ids {
+            query.push(("api_key_ids[]", api_key_id.clone()));
+        }
+        for workspace_id in &self.workspace_ids {
+            query.push(("workspace_ids[]", workspace_id.clone()));
+        }
+        for model in &self.models {
+            query.push(("models[]", model.clone()));
+        }
+        for service_tier in &self.service_tiers {
+            query.push(("service_tiers[]", service_tier.clone()));
+        }
+        for context_window in &self.context_window {
+            query.push(("context_window[]", context_window.clone()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_usage_result() {
+        let result: UsageResult = serde_json::from_str(
+            r#"{"uncached_input_tokens":1500,"cache_creation":{"ephemeral_1h_input_tokens":1000,"ephemeral_5m_input_tokens":500},"cache_read_input_tokens":200,"output_tokens":500,"server_tool_use":{"web_search_requests":10},"api_key_id":null,"workspace_id":"wrkspc_01","model":"claude-sonnet-4-20250514","service_tier":null,"context_window":null}"#,
+        )
+        .unwrap();
+        assert_eq!(result.input_tokens, 1500);
+        assert_eq!(result.output_tokens, 500);
+        assert_eq!(result.cache_read_input_tokens, 200);
+        assert_eq!(result.cache_creation_input_tokens(), 1500);
+        assert_eq!(result.workspace_id, Some("wrkspc_01".to_string()));
+        assert_eq!(result.api_key_id, None);
+    }
+
+    #[test]
+    fn query() {
+        let parameters = UsageReportParameters::new("2025-08-01T00:00:00Z")
+            .bucket_width(BucketWidth::OneHour)
+            .group_by(UsageGroupBy::WorkspaceId)
+            .group_by(UsageGroupBy::Model)
+            .workspace_id("wrkspc_01")
+            .limit(24)
+            .page("page_01");
+
+        assert_eq!(
+            parameters.query(),
+            vec![
+                ("starting_at", "2025-08-01T00:00:00Z".to_string()),
+                ("bucket_width", "1h".to_string()),
+                ("group_by[]", "workspace_id".to_string()),
+                ("group_by[]", "model".to_string()),
+                ("workspace_ids[]", "wrkspc_01".to_string()),
+                ("limit", "24".to_string()),
+                ("page", "page_01".to_string()),
+            ]
+        );
+    }
+}
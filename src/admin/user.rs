@@ -0,0 +1,88 @@
+use std::fmt::Display;
+
+use crate::admin::AdminObjectType;
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization_with_other,
+};
+
+/// A user of the organization.
+///
+/// See also [Get User](https://docs.anthropic.com/en/api/admin-api/users/get-user).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct User {
+    /// ID of the user.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For users, this is always "user".
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+    /// Email of the user.
+    pub email: String,
+    /// Name of the user.
+    pub name: String,
+    /// Organization role of the user.
+    pub role: OrganizationRole,
+    /// RFC 3339 datetime string indicating when the user joined the organization.
+    pub added_at: String,
+}
+
+impl_display_for_serialize!(User);
+
+/// The role of a user in the organization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrganizationRole {
+    /// user
+    User,
+    /// developer
+    Developer,
+    /// billing
+    Billing,
+    /// admin
+    Admin,
+    /// Other role not defined in this crate.
+    Other(String),
+}
+
+impl Default for OrganizationRole {
+    fn default() -> Self {
+        Self::User
+    }
+}
+
+impl Display for OrganizationRole {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | OrganizationRole::User => write!(f, "user"),
+            | OrganizationRole::Developer => write!(f, "developer"),
+            | OrganizationRole::Billing => write!(f, "billing"),
+            | OrganizationRole::Admin => write!(f, "admin"),
+            | OrganizationRole::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl_enum_string_serialization_with_other!(
+    OrganizationRole,
+    User => "user",
+    Developer => "developer",
+    Billing => "billing",
+    Admin => "admin";
+    Other(String)
+);
+
+/// The request body to update a user.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct UpdateUserRequestBody {
+    /// New role for the user.
+    pub role: OrganizationRole,
+}
+
+impl_display_for_serialize!(UpdateUserRequestBody);
@@ -0,0 +1,39 @@
+use crate::admin::AdminObjectType;
+use crate::macros::impl_display_for_serialize;
+
+/// A workspace of the organization.
+///
+/// See also [Get Workspace](https://docs.anthropic.com/en/api/admin-api/workspaces/get-workspace).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct Workspace {
+    /// ID of the workspace.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For workspaces, this is always "workspace".
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+    /// Name of the workspace.
+    pub name: String,
+    /// RFC 3339 datetime string indicating when the workspace was created.
+    pub created_at: String,
+    /// RFC 3339 datetime string indicating when the workspace was archived, or `None` if not archived.
+    pub archived_at: Option<String>,
+    /// Hex color code representing the workspace in the console.
+    pub display_color: String,
+}
+
+impl_display_for_serialize!(Workspace);
+
+/// The request body to create or update a workspace.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct WorkspaceRequestBody {
+    /// Name of the workspace.
+    pub name: String,
+}
+
+impl_display_for_serialize!(WorkspaceRequestBody);
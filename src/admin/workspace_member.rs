@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+use crate::admin::AdminObjectType;
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization_with_other,
+};
+
+/// A member of a workspace.
+///
+/// See also [Get Workspace Member](https://docs.anthropic.com/en/api/admin-api/workspace_members/get-workspace-member).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct WorkspaceMember {
+    /// Object type.
+    ///
+    /// For workspace members, this is always "workspace_member".
+    #[serde(rename = "type")]
+    pub _type: AdminObjectType,
+    /// ID of the user.
+    pub user_id: String,
+    /// ID of the workspace.
+    pub workspace_id: String,
+    /// Role of the user in the workspace.
+    pub workspace_role: WorkspaceRole,
+}
+
+impl_display_for_serialize!(WorkspaceMember);
+
+/// The role of a user in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WorkspaceRole {
+    /// workspace_user
+    WorkspaceUser,
+    /// workspace_developer
+    WorkspaceDeveloper,
+    /// workspace_admin
+    WorkspaceAdmin,
+    /// workspace_billing
+    WorkspaceBilling,
+    /// Other role not defined in this crate.
+    Other(String),
+}
+
+impl Default for WorkspaceRole {
+    fn default() -> Self {
+        Self::WorkspaceUser
+    }
+}
+
+impl Display for WorkspaceRole {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | WorkspaceRole::WorkspaceUser => write!(f, "workspace_user"),
+            | WorkspaceRole::WorkspaceDeveloper => {
+                write!(f, "workspace_developer")
+            },
+            | WorkspaceRole::WorkspaceAdmin => write!(f, "workspace_admin"),
+            | WorkspaceRole::WorkspaceBilling => {
+                write!(f, "workspace_billing")
+            },
+            | WorkspaceRole::Other(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl_enum_string_serialization_with_other!(
+    WorkspaceRole,
+    WorkspaceUser => "workspace_user",
+    WorkspaceDeveloper => "workspace_developer",
+    WorkspaceAdmin => "workspace_admin",
+    WorkspaceBilling => "workspace_billing";
+    Other(String)
+);
+
+/// The request body to add a member to a workspace.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct AddWorkspaceMemberRequestBody {
+    /// ID of the user.
+    pub user_id: String,
+    /// Role of the user in the workspace. Cannot be `workspace_billing`.
+    pub workspace_role: WorkspaceRole,
+}
+
+impl_display_for_serialize!(AddWorkspaceMemberRequestBody);
+
+/// The request body to update a member of a workspace.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct UpdateWorkspaceMemberRequestBody {
+    /// New role of the user in the workspace.
+    pub workspace_role: WorkspaceRole,
+}
+
+impl_display_for_serialize!(UpdateWorkspaceMemberRequestBody);
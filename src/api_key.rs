@@ -1,6 +1,8 @@
 use std::env::VarError;
 
 /// The API key of the Anthropic API.
+///
+/// The value is redacted in the `Debug` format.
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct ApiKey {
     value: String,
@@ -28,6 +30,17 @@ impl ApiKey {
     }
 }
 
+impl std::fmt::Debug for ApiKey {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_tuple("ApiKey")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,4 +50,13 @@ mod tests {
         let api_key = ApiKey::new("api-key");
         assert_eq!(api_key.value, "api-key");
     }
+
+    #[test]
+    fn debug_redacted() {
+        let api_key = ApiKey::new("sk-ant-secret");
+        assert_eq!(
+            format!("{api_key:?}"),
+            r#"ApiKey("[REDACTED]")"#
+        );
+    }
 }
@@ -0,0 +1,29 @@
+//! The [Message Batches API](https://docs.anthropic.com/en/api/creating-message-batches) implementations.
+//!
+//! Message Batches process many Messages API requests asynchronously at a discount.
+
+mod create_message_batch_request_body;
+mod deleted_message_batch;
+mod error;
+mod list_message_batches_parameters;
+mod list_message_batches_response_body;
+mod message_batch;
+mod message_batch_result;
+mod result_stream;
+
+pub(crate) mod api;
+
+pub use create_message_batch_request_body::CreateMessageBatchRequestBody;
+pub use create_message_batch_request_body::MessageBatchRequest;
+pub use deleted_message_batch::DeletedMessageBatch;
+pub use deleted_message_batch::DeletedMessageBatchObjectType;
+pub use error::BatchesError;
+pub use list_message_batches_parameters::{ListMessageBatches, ListMessageBatchesParameters};
+pub use list_message_batches_response_body::ListMessageBatchesResponseBody;
+pub use message_batch::MessageBatch;
+pub use message_batch::MessageBatchObjectType;
+pub use message_batch::ProcessingStatus;
+pub use message_batch::RequestCounts;
+pub use message_batch_result::MessageBatchResult;
+pub use message_batch_result::MessageBatchResultType;
+pub use result_stream::MessageBatchResultStream;
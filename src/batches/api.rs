@@ -0,0 +1,309 @@
+use http::Method;
+
+use crate::batches::{
+    BatchesError, CreateMessageBatchRequestBody, DeletedMessageBatch,
+    ListMessageBatchesParameters, ListMessageBatchesResponseBody,
+    MessageBatch, MessageBatchResultStream,
+};
+use crate::instrument::CallSpan;
+use crate::request::{
+    call_json, encode_path_segment, send_request, with_query, with_timeout,
+    CallError,
+};
+use crate::{Client, RequestOptions};
+
+/// The path of the Message Batches API.
+const BATCHES_PATH: &str = "/v1/messages/batches";
+
+pub(crate) async fn create_a_message_batch(
+    client: &Client,
+    request_body: &CreateMessageBatchRequestBody,
+    options: &RequestOptions,
+) -> Result<MessageBatch, BatchesError> {
+    call_json(
+        client,
+        "batches.create",
+        BATCHES_PATH,
+        |endpoint| client.post(endpoint, request_body, &[]),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn retrieve_a_message_batch(
+    client: &Client,
+    batch_id: &str,
+    options: &RequestOptions,
+) -> Result<MessageBatch, BatchesError> {
+    call_json(
+        client,
+        "batches.retrieve",
+        &format!(
+            "{BATCHES_PATH}/{}",
+            encode_path_segment(batch_id),
+        ),
+        |endpoint| client.request(Method::GET, endpoint, &[]),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn list_message_batches(
+    client: &Client,
+    parameters: &ListMessageBatchesParameters,
+    options: &RequestOptions,
+) -> Result<ListMessageBatchesResponseBody, BatchesError> {
+    let query = parameters.query();
+
+    call_json(
+        client,
+        "batches.list",
+        BATCHES_PATH,
+        |endpoint| {
+            client.request(Method::GET, &with_query(endpoint, &query), &[])
+        },
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn cancel_a_message_batch(
+    client: &Client,
+    batch_id: &str,
+    options: &RequestOptions,
+) -> Result<MessageBatch, BatchesError> {
+    call_json(
+        client,
+        "batches.cancel",
+        &format!(
+            "{BATCHES_PATH}/{}/cancel",
+            encode_path_segment(batch_id),
+        ),
+        |endpoint| client.request(Method::POST, endpoint, &[]),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn delete_a_message_batch(
+    client: &Client,
+    batch_id: &str,
+    options: &RequestOptions,
+) -> Result<DeletedMessageBatch, BatchesError> {
+    call_json(
+        client,
+        "batches.delete",
+        &format!(
+            "{BATCHES_PATH}/{}",
+            encode_path_segment(batch_id),
+        ),
+        |endpoint| client.request(Method::DELETE, endpoint, &[]),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn retrieve_message_batch_results(
+    client: &Client,
+    batch_id: &str,
+    options: &RequestOptions,
+) -> Result<MessageBatchResultStream, BatchesError> {
+    let span = CallSpan::api("batches.results");
+    let path = format!(
+        "{BATCHES_PATH}/{}/results",
+        encode_path_segment(batch_id),
+    );
+    let call = with_timeout(options.timeout, async {
+        send_request(
+            client,
+            &path,
+            |endpoint| client.request(Method::GET, endpoint, &[]),
+            options,
+            &span,
+        )
+        .await
+    });
+
+    let response = match span.instrument(call).await {
+        | Ok(result) => result.map_err(BatchesError::from),
+        | Err(timeout) => Err(BatchesError::timeout(timeout)),
+    }
+    .inspect_err(|error| {
+        span.record_error(error);
+    })?;
+
+    Ok(MessageBatchResultStream::new(response.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batches::{
+        MessageBatchRequest, MessageBatchResultType, ProcessingStatus,
+    };
+    use crate::messages::{Message, MessagesRequestBody};
+    use crate::test_server::{StubResponse, StubServer};
+    use crate::{ApiKey, ClientBuilder};
+    use futures_util::StreamExt;
+
+    const MESSAGE_BATCH: &str = r#"{"id":"msgbatch_01","type":"message_batch","processing_status":"in_progress","request_counts":{"processing":2,"succeeded":0,"errored":0,"canceled":0,"expired":0},"ended_at":null,"created_at":"2024-08-20T18:37:24.100435Z","expires_at":"2024-08-21T18:37:24.100435Z","archived_at":null,"cancel_initiated_at":null,"results_url":null}"#;
+
+    async fn client(responses: Vec<StubResponse>) -> (Client, StubServer) {
+        let server = StubServer::start(responses).await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn create_a_message_batch() {
+        let (client, server) =
+            client(vec![StubResponse::json(200, MESSAGE_BATCH)]).await;
+
+        let request_body = [
+            MessageBatchRequest::new(
+                "request-1",
+                MessagesRequestBody {
+                    messages: vec![Message::user("Hello!")],
+                    ..Default::default()
+                },
+            ),
+            MessageBatchRequest::new(
+                "request-2",
+                MessagesRequestBody {
+                    messages: vec![Message::user("Hi!")],
+                    ..Default::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let message_batch = super::create_a_message_batch(
+            &client,
+            &request_body,
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(message_batch.id, "msgbatch_01");
+        assert_eq!(
+            message_batch.processing_status,
+            ProcessingStatus::InProgress
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "POST /v1/messages/batches HTTP/1.1"
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["requests"][1]["custom_id"], "request-2");
+        assert_eq!(
+            body["requests"][1]["params"]["messages"][0]["content"],
+            "Hi!"
+        );
+    }
+
+    #[tokio::test]
+    async fn operations() {
+        let (client, server) = client(vec![
+            StubResponse::json(200, MESSAGE_BATCH),
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{MESSAGE_BATCH}],"has_more":false,"first_id":"msgbatch_01","last_id":"msgbatch_01"}}"#
+                ),
+            ),
+            StubResponse::json(200, MESSAGE_BATCH),
+            StubResponse::json(
+                200,
+                r#"{"id":"msgbatch_01","type":"message_batch_deleted"}"#,
+            ),
+        ])
+        .await;
+        let options = RequestOptions::default();
+
+        super::retrieve_a_message_batch(&client, "msgbatch_01", &options)
+            .await
+            .unwrap();
+        let list = super::list_message_batches(
+            &client,
+            &ListMessageBatchesParameters::new().limit(1),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(list.data.len(), 1);
+        super::cancel_a_message_batch(&client, "msgbatch_01", &options)
+            .await
+            .unwrap();
+        let deleted =
+            super::delete_a_message_batch(&client, "msgbatch_01", &options)
+                .await
+                .unwrap();
+        assert_eq!(deleted.id, "msgbatch_01");
+
+        let request_lines = server
+            .requests()
+            .into_iter()
+            .map(|request| request.request_line)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            request_lines,
+            [
+                "GET /v1/messages/batches/msgbatch_01 HTTP/1.1",
+                "GET /v1/messages/batches?limit=1 HTTP/1.1",
+                "POST /v1/messages/batches/msgbatch_01/cancel HTTP/1.1",
+                "DELETE /v1/messages/batches/msgbatch_01 HTTP/1.1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn retrieve_message_batch_results() {
+        let (client, server) = client(vec![StubResponse::Http {
+            status: 200,
+            headers: vec![(
+                "content-type".to_string(),
+                "application/binary".to_string(),
+            )],
+            body: concat!(
+                r#"{"custom_id":"request-1","result":{"type":"succeeded","message":{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"text","text":"Hello!"}],"model":"claude-3-haiku-20240307","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}}}"#,
+                "\n",
+                r#"{"custom_id":"request-2","result":{"type":"errored","error":{"type":"error","error":{"type":"invalid_request_error","message":"Invalid"}}}}"#,
+                "\n",
+            )
+            .to_string(),
+        }])
+        .await;
+
+        let results = super::retrieve_message_batch_results(
+            &client,
+            "msgbatch_01",
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            &results[0].result,
+            MessageBatchResultType::Succeeded { message } if message.id == "msg_01"
+        ));
+        assert!(matches!(
+            results[1].result,
+            MessageBatchResultType::Errored { .. }
+        ));
+        assert_eq!(
+            server.requests()[0].request_line,
+            "GET /v1/messages/batches/msgbatch_01/results HTTP/1.1"
+        );
+    }
+}
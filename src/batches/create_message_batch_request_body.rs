@@ -0,0 +1,57 @@
+use crate::macros::impl_display_for_serialize;
+use crate::messages::MessagesRequestBody;
+
+/// The request body to create a Message Batch.
+///
+/// See also [Create a Message Batch](https://docs.anthropic.com/en/api/creating-message-batches).
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CreateMessageBatchRequestBody {
+    /// List of requests for prompt completion.
+    pub requests: Vec<MessageBatchRequest>,
+}
+
+impl_display_for_serialize!(CreateMessageBatchRequestBody);
+
+impl FromIterator<MessageBatchRequest> for CreateMessageBatchRequestBody {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = MessageBatchRequest>,
+    {
+        Self {
+            requests: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// A request in a Message Batch.
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct MessageBatchRequest {
+    /// Developer-provided ID created for each request in a Message Batch to match results to requests.
+    ///
+    /// Must be unique for each request within the Message Batch.
+    pub custom_id: String,
+    /// Messages API creation parameters for the individual request.
+    ///
+    /// The `stream` option is not supported in batches.
+    pub params: MessagesRequestBody,
+}
+
+impl MessageBatchRequest {
+    /// Creates a new request in a Message Batch.
+    pub fn new<S>(
+        custom_id: S,
+        params: MessagesRequestBody,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            custom_id: custom_id.into(),
+            params,
+        }
+    }
+}
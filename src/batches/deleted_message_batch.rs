@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+
+/// The response body of deleting a Message Batch.
+///
+/// See also [Delete a Message Batch](https://docs.anthropic.com/en/api/deleting-message-batches).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct DeletedMessageBatch {
+    /// ID of the Message Batch.
+    pub id: String,
+    /// Deleted object type.
+    ///
+    /// For Message Batches, this is always "message_batch_deleted".
+    #[serde(rename = "type")]
+    pub _type: DeletedMessageBatchObjectType,
+}
+
+impl_display_for_serialize!(DeletedMessageBatch);
+
+/// The object type for deleted message batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletedMessageBatchObjectType {
+    /// message_batch_deleted
+    MessageBatchDeleted,
+}
+
+impl Default for DeletedMessageBatchObjectType {
+    fn default() -> Self {
+        Self::MessageBatchDeleted
+    }
+}
+
+impl Display for DeletedMessageBatchObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | DeletedMessageBatchObjectType::MessageBatchDeleted => {
+                write!(f, "message_batch_deleted")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    DeletedMessageBatchObjectType,
+    MessageBatchDeleted => "message_batch_deleted"
+);
@@ -0,0 +1,43 @@
+use crate::batches::{MessageBatch, BatchesError};
+use crate::transport::BoxFuture;
+use crate::{Client, CursorParameters, Page, Paginate, RequestOptions};
+
+/// The query parameters to list Message Batches.
+///
+/// See also [List Message Batches](https://docs.anthropic.com/en/api/listing-message-batches).
+///
+/// ## Example
+/// ```
+/// use clust::batches::ListMessageBatchesParameters;
+///
+/// let parameters = ListMessageBatchesParameters::new()
+///     .after_id("msgbatch_013Zva2CMHLNnXjNJJKqJ2EF")
+///     .limit(10);
+/// ```
+pub type ListMessageBatchesParameters = CursorParameters<ListMessageBatches>;
+
+/// The marker of the list Message Batches endpoint for [`ListMessageBatchesParameters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ListMessageBatches;
+
+impl Paginate for ListMessageBatchesParameters {
+    type Item = MessageBatch;
+    type Error = BatchesError;
+
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>> {
+        Box::pin(async move {
+            crate::batches::api::list_message_batches(client, &self, &options).await
+        })
+    }
+
+    fn next_page(
+        self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self> {
+        CursorParameters::next_page(self, page)
+    }
+}
@@ -0,0 +1,7 @@
+use crate::batches::MessageBatch;
+use crate::Page;
+
+/// The response body to list Message Batches, the most recently created first.
+///
+/// See also [List Message Batches](https://docs.anthropic.com/en/api/listing-message-batches).
+pub type ListMessageBatchesResponseBody = Page<MessageBatch>;
@@ -0,0 +1,169 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+
+/// A Message Batch.
+///
+/// See also [Retrieve a Message Batch](https://docs.anthropic.com/en/api/retrieving-message-batches).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct MessageBatch {
+    /// Unique object identifier.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For Message Batches, this is always "message_batch".
+    #[serde(rename = "type")]
+    pub _type: MessageBatchObjectType,
+    /// Processing status of the Message Batch.
+    pub processing_status: ProcessingStatus,
+    /// Tallies requests within the Message Batch, categorized by their status.
+    pub request_counts: RequestCounts,
+    /// RFC 3339 datetime string representing the time at which processing for the Message Batch ended.
+    pub ended_at: Option<String>,
+    /// RFC 3339 datetime string representing the time at which the Message Batch was created.
+    pub created_at: String,
+    /// RFC 3339 datetime string representing the time at which the Message Batch will expire and end processing, which is 24 hours after creation.
+    pub expires_at: String,
+    /// RFC 3339 datetime string representing the time at which the Message Batch was archived and its results became unavailable.
+    pub archived_at: Option<String>,
+    /// RFC 3339 datetime string representing the time at which cancellation was initiated for the Message Batch.
+    pub cancel_initiated_at: Option<String>,
+    /// URL to a `.jsonl` file containing the results of the Message Batch requests.
+    ///
+    /// Specified only once processing ends.
+    pub results_url: Option<String>,
+}
+
+impl_display_for_serialize!(MessageBatch);
+
+/// Tallies of requests within a Message Batch.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct RequestCounts {
+    /// Number of requests in the Message Batch that are processing.
+    pub processing: u32,
+    /// Number of requests in the Message Batch that have completed successfully.
+    pub succeeded: u32,
+    /// Number of requests in the Message Batch that encountered an error.
+    pub errored: u32,
+    /// Number of requests in the Message Batch that have been canceled.
+    pub canceled: u32,
+    /// Number of requests in the Message Batch that have expired.
+    pub expired: u32,
+}
+
+/// The object type for message batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBatchObjectType {
+    /// message_batch
+    MessageBatch,
+}
+
+impl Default for MessageBatchObjectType {
+    fn default() -> Self {
+        Self::MessageBatch
+    }
+}
+
+impl Display for MessageBatchObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | MessageBatchObjectType::MessageBatch => {
+                write!(f, "message_batch")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    MessageBatchObjectType,
+    MessageBatch => "message_batch"
+);
+
+/// Processing status of a Message Batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessingStatus {
+    /// in_progress
+    InProgress,
+    /// canceling
+    Canceling,
+    /// ended
+    Ended,
+}
+
+impl Default for ProcessingStatus {
+    fn default() -> Self {
+        Self::InProgress
+    }
+}
+
+impl Display for ProcessingStatus {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ProcessingStatus::InProgress => write!(f, "in_progress"),
+            | ProcessingStatus::Canceling => write!(f, "canceling"),
+            | ProcessingStatus::Ended => write!(f, "ended"),
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ProcessingStatus,
+    InProgress => "in_progress",
+    Canceling => "canceling",
+    Ended => "ended"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let message_batch: MessageBatch = serde_json::from_str(
+            r#"{
+  "id": "msgbatch_01",
+  "type": "message_batch",
+  "processing_status": "ended",
+  "request_counts": {"processing": 0, "succeeded": 2, "errored": 1, "canceled": 0, "expired": 0},
+  "ended_at": "2024-08-20T18:37:24.100435Z",
+  "created_at": "2024-08-20T18:37:24.100435Z",
+  "expires_at": "2024-08-21T18:37:24.100435Z",
+  "archived_at": null,
+  "cancel_initiated_at": null,
+  "results_url": "https://api.anthropic.com/v1/messages/batches/msgbatch_01/results"
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            message_batch.processing_status,
+            ProcessingStatus::Ended
+        );
+        assert_eq!(message_batch.request_counts.succeeded, 2);
+        assert_eq!(message_batch.archived_at, None);
+        assert_eq!(
+            message_batch.results_url.as_deref(),
+            Some("https://api.anthropic.com/v1/messages/batches/msgbatch_01/results")
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::macros::impl_display_for_serialize;
+use crate::messages::MessagesResponseBody;
+use crate::ApiErrorResponse;
+
+/// A result of a request in a Message Batch.
+///
+/// See also [Retrieve Message Batch Results](https://docs.anthropic.com/en/api/retrieving-message-batch-results).
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MessageBatchResult {
+    /// Developer-provided ID of the request.
+    pub custom_id: String,
+    /// Processing result of the request.
+    pub result: MessageBatchResultType,
+}
+
+impl_display_for_serialize!(MessageBatchResult);
+
+/// Processing result of a request in a Message Batch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageBatchResultType {
+    /// The request succeeded with the message.
+    Succeeded {
+        /// The response body of the Messages API.
+        message: MessagesResponseBody,
+    },
+    /// The request encountered an error and a message was not created.
+    Errored {
+        /// The error response.
+        error: ApiErrorResponse,
+    },
+    /// The request was canceled before it could be sent to the model.
+    Canceled,
+    /// The request expired before it could be sent to the model.
+    Expired,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let result: MessageBatchResult = serde_json::from_str(
+            r#"{"custom_id":"request-1","result":{"type":"errored","error":{"type":"error","error":{"type":"invalid_request_error","message":"Invalid"}}}}"#,
+        )
+        .unwrap();
+        assert_eq!(result.custom_id, "request-1");
+        assert!(matches!(
+            result.result,
+            MessageBatchResultType::Errored { error }
+                if error.error._type == "invalid_request_error"
+        ));
+
+        let result: MessageBatchResult = serde_json::from_str(
+            r#"{"custom_id":"request-2","result":{"type":"expired"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            result.result,
+            MessageBatchResultType::Expired
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BytesMut};
+use futures_core::Stream;
+
+use crate::batches::MessageBatchResult;
+use crate::transport::ByteStream;
+use crate::ClientError;
+
+/// The stream of results of a Message Batch decoded from the `.jsonl` response body line by line.
+///
+/// Results are not guaranteed to be in the same order as requests, so use `custom_id` to match them.
+pub struct MessageBatchResultStream {
+    body: ByteStream,
+    buffer: BytesMut,
+    finished: bool,
+}
+
+impl MessageBatchResultStream {
+    /// Creates a new result stream from the response body.
+    pub(crate) fn new(body: ByteStream) -> Self {
+        Self {
+            body,
+            buffer: BytesMut::new(),
+            finished: false,
+        }
+    }
+
+    /// Takes the next non-empty line from the buffer, or the rest at the end of the body.
+    fn next_line(&mut self) -> Option<BytesMut> {
+        while let Some(position) = self
+            .buffer
+            .iter()
+            .position(|byte| *byte == b'\n')
+        {
+            let line = self.buffer.split_to(position);
+            self.buffer.advance(1);
+            if !is_blank(&line) {
+                return Some(line);
+            }
+        }
+
+        if self.finished && !is_blank(&self.buffer) {
+            return Some(self.buffer.split());
+        }
+
+        None
+    }
+}
+
+fn is_blank(line: &[u8]) -> bool {
+    line.iter()
+        .all(u8::is_ascii_whitespace)
+}
+
+impl Stream for MessageBatchResultStream {
+    type Item = Result<MessageBatchResult, ClientError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(line) = self.next_line() {
+                let result = serde_json::from_slice(&line).map_err(|error| {
+                    ClientError::ResponseDeserializationFailed {
+                        error,
+                        text: String::from_utf8_lossy(&line).to_string(),
+                    }
+                });
+                return Poll::Ready(Some(result));
+            }
+
+            if self.finished {
+                return Poll::Ready(None);
+            }
+
+            match self.body.as_mut().poll_next(cx) {
+                | Poll::Ready(Some(Ok(bytes))) => {
+                    self.buffer.extend_from_slice(&bytes);
+                },
+                | Poll::Ready(Some(Err(error))) => {
+                    self.finished = true;
+                    self.buffer.clear();
+                    return Poll::Ready(Some(Err(
+                        ClientError::ReadResponseTextFailed(error),
+                    )));
+                },
+                | Poll::Ready(None) => {
+                    self.finished = true;
+                },
+                | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batches::MessageBatchResultType;
+    use crate::transport::TransportError;
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn split_lines_across_chunks() {
+        let chunks: Vec<Result<Bytes, TransportError>> = vec![
+            Ok(Bytes::from(r#"{"custom_id":"a","result":{"type":"canc"#)),
+            Ok(Bytes::from("eled\"}}\n\n{\"custom_id\":\"b\",")),
+            Ok(Bytes::from(r#""result":{"type":"expired"}}"#)),
+        ];
+        let stream = MessageBatchResultStream::new(Box::pin(
+            tokio_stream::iter(chunks),
+        ));
+
+        let results = stream
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].custom_id, "a");
+        assert_eq!(
+            results[0].result,
+            MessageBatchResultType::Canceled
+        );
+        assert_eq!(results[1].custom_id, "b");
+        assert_eq!(
+            results[1].result,
+            MessageBatchResultType::Expired
+        );
+    }
+}
@@ -0,0 +1,676 @@
+//! The [Amazon Bedrock](https://docs.aws.amazon.com/bedrock/latest/userguide/model-parameters-anthropic-claude-messages.html) provider,
+//! enabled by the `bedrock` feature flag.
+//!
+//! The provider rewrites requests of the Messages API for Bedrock:
+//! - The model is moved from the request body to the URL.
+//! - `anthropic_version` and `anthropic_beta` are set in the request body.
+//! - Requests are signed by AWS Signature Version 4 instead of the API key.
+//! - The event stream of a streaming response is decoded into the message chunks.
+//!
+//! ## Example
+//! ```no_run
+//! use clust::bedrock::{AwsCredentials, Bedrock};
+//! use clust::ClientBuilder;
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let bedrock = Bedrock::new("us-east-1", AwsCredentials::from_env()?);
+//!
+//!     // The requests are signed by the AWS credentials instead of an API key.
+//!     let client = ClientBuilder::default()
+//!         .bedrock(bedrock)
+//!         .build();
+//!
+//!     Ok(())
+//! }
+//! ```
+
+mod event_stream;
+mod sigv4;
+
+use std::collections::HashMap;
+use std::env::VarError;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use http::header::{HeaderValue, ACCEPT, CONTENT_TYPE};
+use http::StatusCode;
+
+use crate::messages::ClaudeModel;
+use crate::transport::{
+    BoxFuture, Transport, TransportError, TransportErrorKind,
+    TransportRequest, TransportResponse,
+};
+
+/// The `anthropic_version` of the Bedrock request body.
+const BEDROCK_VERSION: &str = "bedrock-2023-05-31";
+
+/// The AWS credentials to sign requests.
+///
+/// The secret access key and the session token are redacted in the `Debug` format.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AwsCredentials {
+    /// The access key ID.
+    access_key_id: String,
+    /// The secret access key.
+    secret_access_key: String,
+    /// The session token of temporary credentials.
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Creates new static credentials.
+    pub fn new<S, T>(
+        access_key_id: S,
+        secret_access_key: T,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    pub fn session_token<S>(
+        mut self,
+        session_token: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Loads the credentials from the environment variables:
+    /// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optional `AWS_SESSION_TOKEN`.
+    pub fn from_env() -> Result<Self, VarError> {
+        let credentials = Self::new(
+            std::env::var("AWS_ACCESS_KEY_ID")?,
+            std::env::var("AWS_SECRET_ACCESS_KEY")?,
+        );
+
+        match std::env::var("AWS_SESSION_TOKEN") {
+            | Ok(session_token) => Ok(credentials.session_token(session_token)),
+            | Err(VarError::NotPresent) => Ok(credentials),
+            | Err(error) => Err(error),
+        }
+    }
+}
+
+impl std::fmt::Debug for AwsCredentials {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"[REDACTED]")
+            .field(
+                "session_token",
+                &self
+                    .session_token
+                    .as_ref()
+                    .map(|_| "[REDACTED]"),
+            )
+            .finish()
+    }
+}
+
+/// The configuration of the Amazon Bedrock provider set by [`crate::ClientBuilder::bedrock`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bedrock {
+    /// The AWS region.
+    region: String,
+    /// The credentials to sign requests.
+    credentials: AwsCredentials,
+    /// The endpoint overriding the default one of the region.
+    endpoint: Option<String>,
+    /// The Bedrock model IDs overriding the default ones.
+    model_ids: HashMap<String, String>,
+}
+
+impl Bedrock {
+    /// Creates a new Bedrock configuration.
+    ///
+    /// ## Arguments
+    /// - `region` - The AWS region, e.g. `us-east-1`.
+    /// - `credentials` - The credentials to sign requests.
+    pub fn new<S>(
+        region: S,
+        credentials: AwsCredentials,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            region: region.into(),
+            credentials,
+            endpoint: None,
+            model_ids: HashMap::new(),
+        }
+    }
+
+    /// Loads the region from the environment variable: `AWS_REGION` or `AWS_DEFAULT_REGION`,
+    /// and the credentials by [`AwsCredentials::from_env`].
+    pub fn from_env() -> Result<Self, VarError> {
+        let region = std::env::var("AWS_REGION")
+            .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))?;
+
+        Ok(Self::new(region, AwsCredentials::from_env()?))
+    }
+
+    /// Sets the endpoint, e.g. a VPC endpoint.
+    ///
+    /// The default is `https://bedrock-runtime.{region}.amazonaws.com`.
+    pub fn endpoint<S>(
+        mut self,
+        endpoint: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the Bedrock model ID or the inference profile of the model.
+    ///
+    /// The default model ID is `anthropic.{model}-v1:0`,
+    /// and `ClaudeModel::Other` containing `.` or `:` is used as a Bedrock model ID as it is.
+    pub fn model_id<S>(
+        mut self,
+        model: ClaudeModel,
+        model_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.model_ids
+            .insert(model.to_string(), model_id.into());
+        self
+    }
+
+    /// Resolves the Bedrock model ID of the model.
+    fn resolve_model_id(
+        &self,
+        model: &str,
+    ) -> String {
+        if let Some(model_id) = self.model_ids.get(model) {
+            return model_id.clone();
+        }
+
+        if model.contains('.') || model.contains(':') {
+            model.to_string()
+        } else {
+            format!("anthropic.{model}-v1:0")
+        }
+    }
+
+    /// Rewrites the request of the Messages API into the signed request of Bedrock.
+    ///
+    /// Returns whether the request is streaming.
+    fn prepare(
+        &self,
+        request: &mut TransportRequest,
+    ) -> Result<bool, TransportError> {
+        if !request.url.ends_with("/v1/messages") {
+            return Err(TransportError::new(
+                TransportErrorKind::Other,
+                format!(
+                    "Bedrock supports only the Messages API: {}",
+                    request.url
+                ),
+            ));
+        }
+
+        let mut body: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&request.body).map_err(|error| {
+                TransportError::new(TransportErrorKind::Other, error)
+            })?;
+
+        let model = match body.remove("model") {
+            | Some(serde_json::Value::String(model)) => model,
+            | _ => {
+                return Err(TransportError::new(
+                    TransportErrorKind::Other,
+                    "Bedrock supports only requests with a model",
+                ));
+            },
+        };
+        let stream = body
+            .remove("stream")
+            .and_then(|stream| stream.as_bool())
+            .unwrap_or(false);
+
+        body.insert(
+            "anthropic_version".to_string(),
+            BEDROCK_VERSION.into(),
+        );
+        if let Some(betas) = request
+            .headers
+            .remove("anthropic-beta")
+        {
+            let betas = String::from_utf8_lossy(betas.as_bytes())
+                .split(',')
+                .map(|beta| beta.trim().into())
+                .collect::<Vec<serde_json::Value>>();
+            body.insert(
+                "anthropic_beta".to_string(),
+                betas.into(),
+            );
+        }
+
+        let endpoint = self
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| {
+                format!(
+                    "https://bedrock-runtime.{}.amazonaws.com",
+                    self.region
+                )
+            });
+        request.url = format!(
+            "{}/model/{}/{}",
+            endpoint.trim_end_matches('/'),
+            sigv4::uri_encode(&self.resolve_model_id(&model)),
+            if stream {
+                "invoke-with-response-stream"
+            } else {
+                "invoke"
+            }
+        );
+        request.body = serde_json::to_vec(&body)
+            .map_err(|error| {
+                TransportError::new(TransportErrorKind::Other, error)
+            })?
+            .into();
+
+        request.headers.remove("x-api-key");
+        request
+            .headers
+            .remove(http::header::AUTHORIZATION);
+        request
+            .headers
+            .remove("anthropic-version");
+        request.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        request.headers.insert(
+            ACCEPT,
+            HeaderValue::from_static(if stream {
+                "application/vnd.amazon.eventstream"
+            } else {
+                "application/json"
+            }),
+        );
+
+        sigv4::sign(
+            request,
+            &self.credentials,
+            &sigv4::SigningScope {
+                region: &self.region,
+                service: "bedrock",
+                time: SystemTime::now(),
+            },
+        )?;
+
+        Ok(stream)
+    }
+}
+
+/// The transport sending requests to Bedrock through the inner transport.
+pub(crate) struct BedrockTransport {
+    bedrock: Bedrock,
+    inner: Arc<dyn Transport>,
+}
+
+impl BedrockTransport {
+    /// Creates a new Bedrock transport.
+    pub(crate) fn new(
+        bedrock: Bedrock,
+        inner: Arc<dyn Transport>,
+    ) -> Self {
+        Self {
+            bedrock,
+            inner,
+        }
+    }
+}
+
+impl Transport for BedrockTransport {
+    fn send(
+        &self,
+        mut request: TransportRequest,
+    ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+        Box::pin(async move {
+            let stream = self.bedrock.prepare(&mut request)?;
+            let mut response = self.inner.send(request).await?;
+
+            // Bedrock returns the request ID by `x-amzn-requestid`.
+            if !response
+                .headers
+                .contains_key("request-id")
+            {
+                if let Some(request_id) = response
+                    .headers
+                    .get("x-amzn-requestid")
+                    .cloned()
+                {
+                    response
+                        .headers
+                        .insert("request-id", request_id);
+                }
+            }
+
+            if !response.status.is_success() {
+                return into_api_error_response(response).await;
+            }
+
+            if stream {
+                response.headers.insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                response.body = Box::pin(
+                    event_stream::ServerSentEventStream::new(response.body),
+                );
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Converts the error response of Bedrock: `{"message": "..."}` into the one of the Anthropic API.
+async fn into_api_error_response(
+    response: TransportResponse
+) -> Result<TransportResponse, TransportError> {
+    let status = response.status;
+    let headers = response.headers.clone();
+    let exception = headers
+        .get("x-amzn-errortype")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(':').next())
+        .map(str::to_string);
+    let body = response.bytes().await?;
+
+    let message = serde_json::from_slice::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|body| {
+            body.get("message")
+                .or(body.get("Message"))
+                .and_then(|message| message.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| String::from_utf8_lossy(&body).to_string());
+    let message = match exception {
+        | Some(exception) => format!("{exception}: {message}"),
+        | None => message,
+    };
+
+    let error_type = match status {
+        | StatusCode::BAD_REQUEST => "invalid_request_error",
+        | StatusCode::UNAUTHORIZED => "authentication_error",
+        | StatusCode::FORBIDDEN => "permission_error",
+        | StatusCode::NOT_FOUND => "not_found_error",
+        | StatusCode::TOO_MANY_REQUESTS => "rate_limit_error",
+        | StatusCode::SERVICE_UNAVAILABLE => "overloaded_error",
+        | _ => "api_error",
+    };
+
+    Ok(TransportResponse::from_bytes(
+        status,
+        headers,
+        serde_json::json!({
+            "type": "error",
+            "error": {
+                "type": error_type,
+                "message": message,
+            },
+        })
+        .to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        MessageChunk, MessagesError, MessagesRequestBody, StreamOption,
+    };
+    use crate::{ApiErrorType, ApiKey, Beta, ClientBuilder};
+    use http::HeaderMap;
+    use std::sync::Mutex;
+
+    /// The transport recording requests and responding the canned response.
+    struct CannedTransport {
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Vec<u8>,
+        requests: Arc<Mutex<Vec<TransportRequest>>>,
+    }
+
+    impl Transport for CannedTransport {
+        fn send(
+            &self,
+            request: TransportRequest,
+        ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request);
+
+            Box::pin(async {
+                Ok(TransportResponse::from_bytes(
+                    self.status,
+                    self.headers.clone(),
+                    self.body.clone(),
+                ))
+            })
+        }
+    }
+
+    fn client(
+        status: StatusCode,
+        body: Vec<u8>,
+    ) -> (crate::Client, Arc<Mutex<Vec<TransportRequest>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-amzn-requestid",
+            HeaderValue::from_static("req_bedrock"),
+        );
+        if !status.is_success() {
+            headers.insert(
+                "x-amzn-errortype",
+                HeaderValue::from_static(
+                    "ThrottlingException:http://internal.amazon.com/coral/com.amazon.bedrock/",
+                ),
+            );
+        }
+
+        // Built as documented in the module, without an API key.
+        let client = ClientBuilder::default()
+            .transport(CannedTransport {
+                status,
+                headers,
+                body,
+                requests: requests.clone(),
+            })
+            .beta(Beta::Tools2024_04_04)
+            .bedrock(
+                Bedrock::new(
+                    "us-west-2",
+                    AwsCredentials::new("AKIDEXAMPLE", "secret")
+                        .session_token("token"),
+                )
+                .model_id(
+                    ClaudeModel::Claude35Sonnet20240620,
+                    "us.anthropic.claude-3-5-sonnet-20240620-v1:0",
+                ),
+            )
+            .build();
+
+        (client, requests)
+    }
+
+    const MESSAGE_RESPONSE: &str = r#"{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"text","text":"Hello!"}],"model":"claude-3-opus-20240229","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}"#;
+
+    #[tokio::test]
+    async fn create_a_message() {
+        let (client, requests) =
+            client(StatusCode::OK, MESSAGE_RESPONSE.into());
+
+        let (response, meta) = client
+            .create_a_message_with_meta(
+                MessagesRequestBody {
+                    model: ClaudeModel::Claude3Opus20240229,
+                    ..Default::default()
+                },
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.id, "msg_01");
+        assert_eq!(meta.request_id, Some("req_bedrock".to_string()));
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(
+            request.url,
+            "https://bedrock-runtime.us-west-2.amazonaws.com/model/anthropic.claude-3-opus-20240229-v1%3A0/invoke"
+        );
+        assert!(request.headers.get("x-api-key").is_none());
+        assert!(request
+            .headers
+            .get("anthropic-version")
+            .is_none());
+        assert!(request
+            .headers
+            .get("anthropic-beta")
+            .is_none());
+        assert_eq!(request.headers["x-amz-security-token"], "token");
+        assert!(request.headers["authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&request.body).unwrap();
+        assert!(body.get("model").is_none());
+        assert!(body.get("stream").is_none());
+        assert_eq!(body["anthropic_version"], "bedrock-2023-05-31");
+        assert_eq!(
+            body["anthropic_beta"],
+            serde_json::json!(["tools-2024-04-04"])
+        );
+    }
+
+    #[tokio::test]
+    async fn create_a_message_stream() {
+        use futures_util::StreamExt;
+
+        let mut body = Vec::new();
+        for chunk in [
+            r#"{"type":"message_start","message":{"id":"msg_01","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20240620","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello!"}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":5}}"#,
+            r#"{"type":"message_stop"}"#,
+        ] {
+            body.extend(event_stream::encode_chunk(chunk));
+        }
+        let (client, requests) = client(StatusCode::OK, body);
+
+        let stream = client
+            .create_a_message_stream(MessagesRequestBody {
+                model: ClaudeModel::Claude35Sonnet20240620,
+                stream: Some(StreamOption::ReturnStream),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let chunks = stream
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(chunks.len(), 6);
+        assert!(matches!(
+            chunks[0],
+            MessageChunk::MessageStart(_)
+        ));
+        assert!(matches!(
+            chunks[5],
+            MessageChunk::MessageStop(_)
+        ));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(
+            requests[0].url,
+            "https://bedrock-runtime.us-west-2.amazonaws.com/model/us.anthropic.claude-3-5-sonnet-20240620-v1%3A0/invoke-with-response-stream"
+        );
+        assert_eq!(
+            requests[0].headers["accept"],
+            "application/vnd.amazon.eventstream"
+        );
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let (client, _) = client(
+            StatusCode::TOO_MANY_REQUESTS,
+            br#"{"message":"Too many requests, please wait before trying again."}"#.to_vec(),
+        );
+
+        let error = client
+            .create_a_message(MessagesRequestBody::default())
+            .await
+            .unwrap_err();
+
+        match error {
+            | MessagesError::ApiError(error) => {
+                assert_eq!(error._type, ApiErrorType::RateLimitError);
+                assert_eq!(
+                    error.response.error.message,
+                    "ThrottlingException: Too many requests, please wait before trying again."
+                );
+                assert_eq!(error.request_id, Some("req_bedrock".to_string()));
+            },
+            | _ => panic!("unexpected error: {error:?}"),
+        }
+    }
+
+    #[test]
+    fn unsupported_request_error_is_not_retryable() {
+        let bedrock = Bedrock::new(
+            "us-west-2",
+            AwsCredentials::new("AKIDEXAMPLE", "secret"),
+        );
+        let mut request = TransportRequest::new(
+            http::Method::POST,
+            "https://api.anthropic.com/v1/messages/count_tokens",
+        );
+
+        let error = bedrock.prepare(&mut request).unwrap_err();
+        assert_eq!(error.kind(), TransportErrorKind::Other);
+    }
+
+    #[test]
+    fn credentials_debug_redacted() {
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI")
+            .session_token("AQoDYXdzEPT");
+        let debug = format!("{credentials:?}");
+
+        assert!(debug.contains("AKIDEXAMPLE"));
+        assert!(!debug.contains("wJalrXUtnFEMI"));
+        assert!(!debug.contains("AQoDYXdzEPT"));
+    }
+}
@@ -0,0 +1,425 @@
+//! Decoding [the AWS event stream encoding](https://docs.aws.amazon.com/transcribe/latest/dg/streaming-setting-up.html#streaming-event-stream)
+//! of Bedrock responses into server-sent events of the Messages API.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use base64::Engine;
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::Stream;
+
+use crate::transport::{
+    ByteStream, TransportError, TransportErrorKind,
+};
+
+/// The length of the prelude: the total length, the headers length and the prelude CRC.
+const PRELUDE_LENGTH: usize = 12;
+/// The length of the message CRC.
+const CRC_LENGTH: usize = 4;
+
+/// A message of the event stream.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EventMessage {
+    /// The headers with string values.
+    headers: Vec<(String, String)>,
+    /// The payload.
+    payload: Bytes,
+}
+
+impl EventMessage {
+    /// Gets the string value of the header.
+    fn header(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Converts the message into a server-sent event of the Messages API.
+    fn into_server_sent_event(self) -> Result<Option<Bytes>, TransportError> {
+        match self.header(":message-type") {
+            | Some("event") => {},
+            | Some("exception") | Some("error") => {
+                let error_type = self
+                    .header(":exception-type")
+                    .or(self.header(":error-code"))
+                    .unwrap_or("unknown")
+                    .to_string();
+                let message = serde_json::from_slice::<serde_json::Value>(
+                    &self.payload,
+                )
+                .ok()
+                .and_then(|payload| {
+                    payload
+                        .get("message")
+                        .and_then(|message| message.as_str())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| {
+                    String::from_utf8_lossy(&self.payload).to_string()
+                });
+
+                return Err(body_error(format!(
+                    "Bedrock stream {error_type}: {message}"
+                )));
+            },
+            | _ => return Ok(None),
+        }
+
+        if self.header(":event-type") != Some("chunk") {
+            return Ok(None);
+        }
+
+        // The payload is `{"bytes": "<base64 encoded chunk>"}`.
+        let payload: serde_json::Value = serde_json::from_slice(&self.payload)
+            .map_err(|error| TransportError::new(TransportErrorKind::Body, error))?;
+        let encoded = payload
+            .get("bytes")
+            .and_then(|bytes| bytes.as_str())
+            .ok_or_else(|| body_error("Bedrock chunk has no bytes"))?;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|error| TransportError::new(TransportErrorKind::Body, error))?;
+
+        // Re-serialize the chunk in a single line.
+        let chunk: serde_json::Value = serde_json::from_slice(&decoded)
+            .map_err(|error| TransportError::new(TransportErrorKind::Body, error))?;
+        let chunk_type = chunk
+            .get("type")
+            .and_then(|chunk_type| chunk_type.as_str())
+            .ok_or_else(|| body_error("Bedrock chunk has no type"))?;
+
+        Ok(Some(Bytes::from(format!(
+            "event: {chunk_type}\ndata: {chunk}\n\n"
+        ))))
+    }
+}
+
+/// Decodes messages from the buffer of the event stream.
+///
+/// Returns `None` if the buffer does not have a whole message yet.
+pub(crate) fn decode(
+    buffer: &mut BytesMut
+) -> Result<Option<EventMessage>, TransportError> {
+    if buffer.len() < PRELUDE_LENGTH {
+        return Ok(None);
+    }
+
+    let total_length = u32::from_be_bytes([
+        buffer[0], buffer[1], buffer[2], buffer[3],
+    ]) as usize;
+    let headers_length = u32::from_be_bytes([
+        buffer[4], buffer[5], buffer[6], buffer[7],
+    ]) as usize;
+    let prelude_crc = u32::from_be_bytes([
+        buffer[8], buffer[9], buffer[10], buffer[11],
+    ]);
+
+    if crc32fast::hash(&buffer[..8]) != prelude_crc {
+        return Err(body_error("Event stream prelude CRC mismatch"));
+    }
+    if total_length < PRELUDE_LENGTH + headers_length + CRC_LENGTH {
+        return Err(body_error(format!(
+            "Event stream message is too short: {total_length}"
+        )));
+    }
+    if buffer.len() < total_length {
+        return Ok(None);
+    }
+
+    let mut message = buffer.split_to(total_length).freeze();
+    let message_crc = u32::from_be_bytes([
+        message[total_length - 4],
+        message[total_length - 3],
+        message[total_length - 2],
+        message[total_length - 1],
+    ]);
+    if crc32fast::hash(&message[..total_length - CRC_LENGTH]) != message_crc {
+        return Err(body_error("Event stream message CRC mismatch"));
+    }
+
+    message.advance(PRELUDE_LENGTH);
+    let headers = decode_headers(message.split_to(headers_length))?;
+    let payload = message.split_to(
+        total_length - PRELUDE_LENGTH - headers_length - CRC_LENGTH,
+    );
+
+    Ok(Some(EventMessage {
+        headers,
+        payload,
+    }))
+}
+
+/// Decodes the headers, keeping only the ones with string values.
+fn decode_headers(
+    mut headers: Bytes
+) -> Result<Vec<(String, String)>, TransportError> {
+    let truncated = || body_error("Event stream headers are truncated");
+    let mut decoded = Vec::new();
+
+    while headers.has_remaining() {
+        let name_length = headers.get_u8() as usize;
+        if headers.remaining() < name_length + 1 {
+            return Err(truncated());
+        }
+        let name = String::from_utf8_lossy(&headers.split_to(name_length))
+            .to_string();
+
+        let value_length = match headers.get_u8() {
+            // bool true and false
+            | 0 | 1 => 0,
+            // byte
+            | 2 => 1,
+            // short
+            | 3 => 2,
+            // integer
+            | 4 => 4,
+            // long and timestamp
+            | 5 | 8 => 8,
+            // byte array and string
+            | 6 | 7 => {
+                if headers.remaining() < 2 {
+                    return Err(truncated());
+                }
+                let length = headers.get_u16() as usize;
+                if headers.remaining() < length {
+                    return Err(truncated());
+                }
+                let value = headers.split_to(length);
+                decoded.push((
+                    name,
+                    String::from_utf8_lossy(&value).to_string(),
+                ));
+                continue;
+            },
+            // uuid
+            | 9 => 16,
+            | value_type => {
+                return Err(body_error(format!(
+                    "Unknown event stream header type: {value_type}"
+                )));
+            },
+        };
+
+        if headers.remaining() < value_length {
+            return Err(truncated());
+        }
+        headers.advance(value_length);
+    }
+
+    Ok(decoded)
+}
+
+/// The stream of server-sent events decoded from the event stream of Bedrock.
+pub(crate) struct ServerSentEventStream {
+    stream: ByteStream,
+    buffer: BytesMut,
+    finished: bool,
+}
+
+impl ServerSentEventStream {
+    /// Creates a new stream from the event stream body.
+    pub(crate) fn new(stream: ByteStream) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+            finished: false,
+        }
+    }
+}
+
+impl Stream for ServerSentEventStream {
+    type Item = Result<Bytes, TransportError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            if this.finished {
+                return Poll::Ready(None);
+            }
+
+            // Emit the decoded messages in the buffer.
+            match decode(&mut this.buffer) {
+                | Ok(Some(message)) => match message.into_server_sent_event() {
+                    | Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                    | Ok(None) => continue,
+                    | Err(error) => {
+                        this.finished = true;
+                        return Poll::Ready(Some(Err(error)));
+                    },
+                },
+                | Ok(None) => {},
+                | Err(error) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(error)));
+                },
+            }
+
+            match this
+                .stream
+                .as_mut()
+                .poll_next(cx)
+            {
+                | Poll::Ready(Some(Ok(bytes))) => this.buffer.extend(&bytes),
+                | Poll::Ready(Some(Err(error))) => {
+                    return Poll::Ready(Some(Err(error)));
+                },
+                | Poll::Ready(None) => {
+                    this.finished = true;
+                    if !this.buffer.is_empty() {
+                        return Poll::Ready(Some(Err(body_error(
+                            "Event stream ended in the middle of a message",
+                        ))));
+                    }
+                },
+                | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+fn body_error<S>(message: S) -> TransportError
+where
+    S: Into<String>,
+{
+    TransportError::new(TransportErrorKind::Body, message.into())
+}
+
+/// Encodes a message with string headers for tests.
+#[cfg(test)]
+pub(crate) fn encode(
+    headers: &[(&str, &str)],
+    payload: &[u8],
+) -> Vec<u8> {
+    use bytes::BufMut;
+
+    let mut encoded_headers = Vec::new();
+    for (name, value) in headers {
+        encoded_headers.put_u8(name.len() as u8);
+        encoded_headers.put_slice(name.as_bytes());
+        encoded_headers.put_u8(7);
+        encoded_headers.put_u16(value.len() as u16);
+        encoded_headers.put_slice(value.as_bytes());
+    }
+
+    let total_length =
+        PRELUDE_LENGTH + encoded_headers.len() + payload.len() + CRC_LENGTH;
+    let mut message = Vec::with_capacity(total_length);
+    message.put_u32(total_length as u32);
+    message.put_u32(encoded_headers.len() as u32);
+    message.put_u32(crc32fast::hash(&message));
+    message.put_slice(&encoded_headers);
+    message.put_slice(payload);
+    message.put_u32(crc32fast::hash(&message));
+
+    message
+}
+
+/// Encodes a chunk of the Messages API into an event stream message for tests.
+#[cfg(test)]
+pub(crate) fn encode_chunk(chunk: &str) -> Vec<u8> {
+    let payload = serde_json::json!({
+        "bytes": base64::engine::general_purpose::STANDARD.encode(chunk),
+    });
+
+    encode(
+        &[
+            (":message-type", "event"),
+            (":event-type", "chunk"),
+            (":content-type", "application/json"),
+        ],
+        payload.to_string().as_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn decode_message() {
+        let mut buffer = BytesMut::from(
+            &encode(&[(":message-type", "event")], b"payload")[..],
+        );
+        let whole = buffer.clone();
+
+        // Partial message.
+        let mut partial = BytesMut::from(&whole[..whole.len() - 1]);
+        assert_eq!(decode(&mut partial).unwrap(), None);
+
+        let message = decode(&mut buffer)
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.header(":message-type"), Some("event"));
+        assert_eq!(message.payload, Bytes::from_static(b"payload"));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn decode_crc_mismatch() {
+        let mut encoded = encode(&[(":message-type", "event")], b"payload");
+        let last = encoded.len() - 1;
+        encoded[last] ^= 0xff;
+
+        assert!(decode(&mut BytesMut::from(&encoded[..])).is_err());
+    }
+
+    #[tokio::test]
+    async fn server_sent_events() {
+        let mut body = encode_chunk(r#"{"type":"message_stop"}"#);
+        body.extend(encode_chunk(r#"{"type":"ping"}"#));
+
+        // Split the body at an arbitrary position.
+        let (first, second) = body.split_at(10);
+        let stream: ByteStream = Box::pin(futures_util::stream::iter(vec![
+            Ok(Bytes::copy_from_slice(first)),
+            Ok(Bytes::copy_from_slice(second)),
+        ]));
+
+        let events = ServerSentEventStream::new(stream)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                Bytes::from("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"),
+                Bytes::from("event: ping\ndata: {\"type\":\"ping\"}\n\n"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn exception() {
+        let body = encode(
+            &[
+                (":message-type", "exception"),
+                (":exception-type", "throttlingException"),
+            ],
+            br#"{"message":"Too many requests"}"#,
+        );
+        let stream: ByteStream = Box::pin(futures_util::stream::iter(vec![
+            Ok(Bytes::from(body)),
+        ]));
+
+        let mut events = ServerSentEventStream::new(stream);
+        let error = events
+            .next()
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("throttlingException: Too many requests"));
+        assert!(events.next().await.is_none());
+    }
+}
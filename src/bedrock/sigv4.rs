@@ -0,0 +1,341 @@
+//! Signing requests by [AWS Signature Version 4](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_sigv.html).
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use http::header::{HeaderValue, AUTHORIZATION};
+use sha2::{Digest, Sha256};
+
+use crate::bedrock::AwsCredentials;
+use crate::transport::{TransportError, TransportErrorKind, TransportRequest};
+
+/// The signing algorithm.
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// The scope of a signature.
+pub(crate) struct SigningScope<'a> {
+    /// The AWS region, e.g. `us-east-1`.
+    pub(crate) region: &'a str,
+    /// The AWS service, e.g. `bedrock`.
+    pub(crate) service: &'a str,
+    /// The time of signing.
+    pub(crate) time: SystemTime,
+}
+
+/// Signs the request by adding the `x-amz-date`, `x-amz-security-token` and `authorization` headers.
+///
+/// All headers of the request and the host of the URL are signed.
+pub(crate) fn sign(
+    request: &mut TransportRequest,
+    credentials: &AwsCredentials,
+    scope: &SigningScope<'_>,
+) -> Result<(), TransportError> {
+    let url = reqwest::Url::parse(&request.url).map_err(|error| {
+        TransportError::new(TransportErrorKind::Other, error)
+    })?;
+    let host = match (url.host_str(), url.port()) {
+        | (Some(host), Some(port)) => format!("{host}:{port}"),
+        | (Some(host), None) => host.to_string(),
+        | (None, _) => {
+            return Err(TransportError::new(
+                TransportErrorKind::Other,
+                format!("The URL has no host: {}", request.url),
+            ));
+        },
+    };
+
+    let (amz_date, date) = format_time(scope.time);
+    request
+        .headers
+        .insert("x-amz-date", header_value(&amz_date)?);
+    if let Some(session_token) = &credentials.session_token {
+        let mut session_token = header_value(session_token)?;
+        session_token.set_sensitive(true);
+        request
+            .headers
+            .insert("x-amz-security-token", session_token);
+    }
+
+    // Canonical headers sorted by the lowercase names.
+    let mut headers = vec![("host".to_string(), host)];
+    for name in request.headers.keys() {
+        let values = request
+            .headers
+            .get_all(name)
+            .iter()
+            .map(|value| {
+                String::from_utf8_lossy(value.as_bytes())
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        headers.push((name.as_str().to_string(), values));
+    }
+    headers.sort();
+
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_headers = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{value}\n"))
+        .collect::<String>();
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        request.method,
+        canonical_uri(url.path()),
+        canonical_query(url.query().unwrap_or_default()),
+        canonical_headers,
+        signed_headers,
+        hex(&Sha256::digest(&request.body)),
+    );
+
+    let credential_scope = format!(
+        "{date}/{}/{}/aws4_request",
+        scope.region, scope.service
+    );
+    let string_to_sign = format!(
+        "{ALGORITHM}\n{amz_date}\n{credential_scope}\n{}",
+        hex(&Sha256::digest(canonical_request.as_bytes())),
+    );
+
+    let signing_key = [
+        date.as_str(),
+        scope.region,
+        scope.service,
+        "aws4_request",
+    ]
+    .iter()
+    .fold(
+        format!("AWS4{}", credentials.secret_access_key).into_bytes(),
+        |key, data| hmac_sha256(&key, data.as_bytes()),
+    );
+    let signature = hex(&hmac_sha256(
+        &signing_key,
+        string_to_sign.as_bytes(),
+    ));
+
+    let mut authorization = header_value(&format!(
+        "{ALGORITHM} Credential={}/{credential_scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key_id,
+    ))?;
+    authorization.set_sensitive(true);
+    request
+        .headers
+        .insert(AUTHORIZATION, authorization);
+
+    Ok(())
+}
+
+/// Encodes each segment of the percent-encoded path again, as required by services other than S3.
+fn canonical_uri(path: &str) -> String {
+    if path.is_empty() {
+        return "/".to_string();
+    }
+
+    path.split('/')
+        .map(uri_encode)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Sorts the percent-encoded query parameters by names and values.
+fn canonical_query(query: &str) -> String {
+    let mut parameters = query
+        .split('&')
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| match parameter.split_once('=') {
+            | Some((name, value)) => (name, value),
+            | None => (parameter, ""),
+        })
+        .collect::<Vec<_>>();
+    parameters.sort();
+
+    parameters
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encodes all characters except the unreserved ones.
+pub(crate) fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            | b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.'
+            | b'~' => (byte as char).to_string(),
+            | _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Formats the time into `YYYYMMDD'T'HHMMSS'Z'` and `YYYYMMDD` in UTC.
+fn format_time(time: SystemTime) -> (String, String) {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+    // Converts days since the epoch into the civil date.
+    // See also http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{year:04}{month:02}{day:02}");
+    let amz_date = format!(
+        "{date}T{:02}{:02}{:02}Z",
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+    );
+
+    (amz_date, date)
+}
+
+fn hmac_sha256(
+    key: &[u8],
+    data: &[u8],
+) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .expect("HMAC can take a key of any size");
+    mac.update(data);
+    mac.finalize()
+        .into_bytes()
+        .to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, TransportError> {
+    HeaderValue::from_str(value).map_err(|error| {
+        TransportError::new(TransportErrorKind::Other, error)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use std::time::Duration;
+
+    /// Signs the request of [the test suite](https://docs.aws.amazon.com/general/latest/gr/signature-v4-test-suite.html)
+    /// and returns the `authorization` header.
+    fn sign_test_vector(mut request: TransportRequest) -> String {
+        let credentials = AwsCredentials::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        );
+        let scope = SigningScope {
+            region: "us-east-1",
+            service: "service",
+            time: UNIX_EPOCH + Duration::from_secs(1_440_938_160),
+        };
+
+        sign(&mut request, &credentials, &scope).unwrap();
+
+        assert_eq!(
+            request.headers["x-amz-date"],
+            "20150830T123600Z"
+        );
+        request.headers[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn get_vanilla() {
+        let request =
+            TransportRequest::new(Method::GET, "https://example.amazonaws.com/");
+
+        assert_eq!(
+            sign_test_vector(request),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn get_vanilla_query_order_key_case() {
+        let request = TransportRequest::new(
+            Method::GET,
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+        );
+
+        assert_eq!(
+            sign_test_vector(request),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        );
+    }
+
+    #[test]
+    fn post_vanilla() {
+        let request =
+            TransportRequest::new(Method::POST, "https://example.amazonaws.com/");
+
+        assert_eq!(
+            sign_test_vector(request),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b"
+        );
+    }
+
+    #[test]
+    fn post_x_www_form_urlencoded() {
+        let mut request =
+            TransportRequest::new(Method::POST, "https://example.amazonaws.com/");
+        request.headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        request.body = "Param1=value1".into();
+
+        assert_eq!(
+            sign_test_vector(request),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=content-type;host;x-amz-date, Signature=ff11897932ad3f4e8b18135d722051e5ac45fc38421b1da7b9d196a0fe09473a"
+        );
+    }
+
+    #[test]
+    fn canonical_uri_double_encoded() {
+        assert_eq!(
+            canonical_uri("/model/anthropic.claude-v2%3A1/invoke"),
+            "/model/anthropic.claude-v2%253A1/invoke"
+        );
+    }
+
+    #[test]
+    fn format_leap_day() {
+        assert_eq!(
+            format_time(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            (
+                "20240229T123456Z".to_string(),
+                "20240229".to_string()
+            )
+        );
+    }
+}
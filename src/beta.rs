@@ -3,12 +3,20 @@ use std::fmt::Display;
 /// The beta feature.
 ///
 /// See also [the API reference](https://docs.anthropic.com/claude/reference/versions).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Use `Beta::Other` for a beta feature that is not defined in this crate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Beta {
     /// tools-2024-04-04
     Tools2024_04_04,
     /// extended-cache-ttl-2025-04-11
     ExtendedCacheTtl2025_04_11,
+    /// interleaved-thinking-2025-05-14
+    InterleavedThinking2025_05_14,
+    /// files-api-2025-04-14
+    FilesApi2025_04_14,
+    /// Other beta feature by the header value.
+    Other(String),
 }
 
 impl Default for Beta {
@@ -29,10 +37,61 @@ impl Display for Beta {
             | Beta::ExtendedCacheTtl2025_04_11 => {
                 write!(f, "extended-cache-ttl-2025-04-11")
             },
+            | Beta::InterleavedThinking2025_05_14 => {
+                write!(f, "interleaved-thinking-2025-05-14")
+            },
+            | Beta::FilesApi2025_04_14 => {
+                write!(f, "files-api-2025-04-14")
+            },
+            | Beta::Other(value) => {
+                write!(f, "{value}")
+            },
+        }
+    }
+}
+
+impl From<&str> for Beta {
+    fn from(value: &str) -> Self {
+        match value {
+            | "tools-2024-04-04" => Beta::Tools2024_04_04,
+            | "extended-cache-ttl-2025-04-11" => {
+                Beta::ExtendedCacheTtl2025_04_11
+            },
+            | "interleaved-thinking-2025-05-14" => {
+                Beta::InterleavedThinking2025_05_14
+            },
+            | "files-api-2025-04-14" => Beta::FilesApi2025_04_14,
+            | _ => Beta::Other(value.to_string()),
         }
     }
 }
 
+impl From<String> for Beta {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+/// Joins the beta features into the value of the `anthropic-beta` header without duplicates.
+pub(crate) fn join_betas<'a, I>(betas: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a Beta>,
+{
+    let mut values: Vec<String> = Vec::new();
+    for beta in betas {
+        let value = beta.to_string();
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(","))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,5 +111,42 @@ mod tests {
             Beta::ExtendedCacheTtl2025_04_11.to_string(),
             "extended-cache-ttl-2025-04-11",
         );
+        assert_eq!(
+            Beta::InterleavedThinking2025_05_14.to_string(),
+            "interleaved-thinking-2025-05-14",
+        );
+        assert_eq!(
+            Beta::FilesApi2025_04_14.to_string(),
+            "files-api-2025-04-14",
+        );
+        assert_eq!(
+            Beta::Other("new-beta-2025-01-01".to_string()).to_string(),
+            "new-beta-2025-01-01",
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            Beta::from("tools-2024-04-04"),
+            Beta::Tools2024_04_04,
+        );
+        assert_eq!(
+            Beta::from("new-beta-2025-01-01"),
+            Beta::Other("new-beta-2025-01-01".to_string()),
+        );
+    }
+
+    #[test]
+    fn join() {
+        assert_eq!(join_betas(&[]), None);
+        assert_eq!(
+            join_betas(&[
+                Beta::Tools2024_04_04,
+                Beta::ExtendedCacheTtl2025_04_11,
+                Beta::Other("tools-2024-04-04".to_string()),
+            ]),
+            Some("tools-2024-04-04,extended-cache-ttl-2025-04-11".to_string()),
+        );
     }
 }
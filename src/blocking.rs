@@ -0,0 +1,592 @@
+//! The blocking API client for synchronous code, enabled by the `blocking` feature flag.
+//!
+//! The blocking client drives the asynchronous [`crate::Client`] on an internal runtime,
+//! so it shares the same options, e.g. retries, middlewares and timeouts.
+//!
+//! ## NOTE
+//! The blocking client must not be used in an asynchronous context, e.g. inside `#[tokio::main]`,
+//! because it panics when blocking on the internal runtime.
+//!
+//! ## Example
+//! ```no_run
+//! use clust::blocking::Client;
+//! use clust::messages::{MessagesRequestBody, Message, StreamOption};
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     let client = Client::from_env()?;
+//!
+//!     let request_body = MessagesRequestBody {
+//!         messages: vec![Message::user("Hello, Claude!")],
+//!         ..Default::default()
+//!     };
+//!     let response = client.create_a_message(request_body)?;
+//!     println!("Result:\n{}", response);
+//!
+//!     let request_body = MessagesRequestBody {
+//!         messages: vec![Message::user("Hello, Claude!")],
+//!         stream: Some(StreamOption::ReturnStream),
+//!         ..Default::default()
+//!     };
+//!     for chunk in client.create_a_message_stream(request_body)? {
+//!         println!("Chunk:\n{}", chunk?);
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_core::Stream;
+use tokio::runtime::Runtime;
+
+use crate::messages::{
+    Content, ContentBlock, Message, MessageChunk, MessagesError,
+    MessagesRequestBody, MessagesResponseBody, StopReason, StreamError,
+    ToolList, ToolResult,
+};
+use crate::{ApiKey, RequestOptions, ResponseMeta};
+
+/// The blocking API client.
+///
+/// Create a client from an asynchronous [`crate::Client`] built by [`crate::ClientBuilder`] to configure options.
+///
+/// ## Example
+/// ```
+/// use clust::{ApiKey, ClientBuilder, RetryPolicy};
+///
+/// let client = clust::blocking::Client::from_async(
+///     ClientBuilder::new(ApiKey::new("api-key"))
+///         .retry_policy(RetryPolicy::new().max_attempts(3))
+///         .build(),
+/// )
+/// .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Client {
+    /// The asynchronous client.
+    inner: crate::Client,
+    /// The runtime to drive the asynchronous client.
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a new blocking API client from the asynchronous client.
+    ///
+    /// ## Arguments
+    /// - `inner` - The asynchronous client.
+    ///
+    /// ## Errors
+    /// Returns an error when the internal runtime can not be built.
+    pub fn from_async(inner: crate::Client) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Create a new blocking API client with the API key loaded from the environment variable: `ANTHROPIC_API_KEY` and default options.
+    pub fn from_env() -> Result<Self, BuildError> {
+        let inner = crate::Client::from_env()?;
+        Ok(Self::from_async(inner)?)
+    }
+
+    /// Create a new blocking API client with the API key and default options.
+    ///
+    /// ## Arguments
+    /// - `api_key` - The API key.
+    ///
+    /// ## Errors
+    /// Returns an error when the internal runtime can not be built.
+    pub fn from_api_key(api_key: ApiKey) -> std::io::Result<Self> {
+        Self::from_async(crate::Client::from_api_key(api_key))
+    }
+
+    /// Create a Message.
+    ///
+    /// See also [`crate::Client::create_a_message`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    pub fn create_a_message(
+        &self,
+        request_body: MessagesRequestBody,
+    ) -> Result<MessagesResponseBody, MessagesError> {
+        self.block_on(
+            self.inner
+                .create_a_message(request_body),
+        )
+    }
+
+    /// Create a Message with the per-request options.
+    ///
+    /// See also [`crate::Client::create_a_message_with_options`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub fn create_a_message_with_options(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<MessagesResponseBody, MessagesError> {
+        self.block_on(
+            self.inner
+                .create_a_message_with_options(request_body, options),
+        )
+    }
+
+    /// Create a Message with the metadata of the response.
+    ///
+    /// See also [`crate::Client::create_a_message_with_meta`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub fn create_a_message_with_meta(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<(MessagesResponseBody, ResponseMeta), MessagesError> {
+        self.block_on(
+            self.inner
+                .create_a_message_with_meta(request_body, options),
+        )
+    }
+
+    /// Create a Message with incrementally streaming the response as an iterator of chunks.
+    ///
+    /// See also [`crate::Client::create_a_message_stream`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    ///
+    /// ## NOTE
+    /// The `stream` option must be `StreamOption::ReturnStream`.
+    pub fn create_a_message_stream(
+        &self,
+        request_body: MessagesRequestBody,
+    ) -> Result<MessageChunkIter, MessagesError> {
+        self.create_a_message_stream_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+    }
+
+    /// Create a Message stream with the per-request options.
+    ///
+    /// See also [`crate::Client::create_a_message_stream_with_options`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub fn create_a_message_stream_with_options(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<MessageChunkIter, MessagesError> {
+        self.create_a_message_stream_with_meta(request_body, options)
+            .map(|(iter, _)| iter)
+    }
+
+    /// Create a Message stream with the metadata of the response.
+    ///
+    /// See also [`crate::Client::create_a_message_stream_with_meta`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub fn create_a_message_stream_with_meta(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<(MessageChunkIter, ResponseMeta), MessagesError> {
+        let (stream, meta) = self.block_on(
+            self.inner
+                .create_a_message_stream_with_meta(request_body, options),
+        )?;
+
+        Ok((
+            MessageChunkIter {
+                stream: Box::pin(stream),
+                runtime: self.runtime.clone(),
+            },
+            meta,
+        ))
+    }
+
+    /// Create a Message and dispatch tool uses of the response to the tools until the assistant stops using tools.
+    ///
+    /// The tool definitions are set to the request body if it has no tools.
+    /// The assistant messages with tool uses and the user messages with tool results are appended to the conversation,
+    /// and a failed tool call is sent to the assistant as an error result.
+    ///
+    /// `ToolLoopError::MaxToolTurnsExceeded` is returned when the assistant still uses tools
+    /// after `max_turns` requests.
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `tools` - The tools that can be called by the assistant.
+    /// - `max_turns` - The maximum number of requests.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::blocking::Client;
+    /// use clust::messages::{MessagesRequestBody, Message, ToolList};
+    ///
+    /// fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let tools = ToolList::new(vec![/* Box::new(ClustTool_get_weather {}) */]);
+    ///     let request_body = MessagesRequestBody {
+    ///         messages: vec![Message::user("What is the weather like in San Francisco?")],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let response = client.create_a_message_with_tools(request_body, &tools, 10)?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn create_a_message_with_tools(
+        &self,
+        mut request_body: MessagesRequestBody,
+        tools: &ToolList,
+        max_turns: u32,
+    ) -> Result<MessagesResponseBody, ToolLoopError> {
+        if request_body.tools.is_none() {
+            request_body.tools = Some(tools.definitions());
+        }
+
+        for _ in 0..max_turns {
+            let response = self.create_a_message(request_body.clone())?;
+            if response.stop_reason != Some(StopReason::ToolUse) {
+                return Ok(response);
+            }
+
+            let tool_results = dispatch_tool_uses(&response.content, tools);
+            if tool_results.is_empty() {
+                return Ok(response);
+            }
+
+            request_body
+                .messages
+                .push(Message::assistant(response.content));
+            request_body
+                .messages
+                .push(Message::user(Content::MultipleBlocks(
+                    tool_results,
+                )));
+        }
+
+        Err(ToolLoopError::MaxToolTurnsExceeded(max_turns))
+    }
+
+    fn block_on<F>(
+        &self,
+        future: F,
+    ) -> F::Output
+    where
+        F: Future,
+    {
+        self.runtime.block_on(future)
+    }
+}
+
+/// The error type for building the blocking client.
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// The API key is not found in the environment variable.
+    #[error(transparent)]
+    Env(#[from] std::env::VarError),
+    /// The internal runtime can not be built.
+    #[error("Failed to build the runtime of the blocking client: {0}")]
+    Runtime(#[from] std::io::Error),
+}
+
+/// The error type for [`Client::create_a_message_with_tools`].
+#[derive(Debug, thiserror::Error)]
+pub enum ToolLoopError {
+    /// The messages API error.
+    #[error(transparent)]
+    Messages(#[from] MessagesError),
+    /// The assistant is still using tools after the maximum number of turns.
+    #[error("Tool use exceeded the maximum number of turns: {0}")]
+    MaxToolTurnsExceeded(u32),
+}
+
+/// Calls the tools for all tool uses in the content, and returns the tool results.
+fn dispatch_tool_uses(
+    content: &Content,
+    tools: &ToolList,
+) -> Vec<ContentBlock> {
+    let Content::MultipleBlocks(blocks) = content
+    else {
+        return Vec::new();
+    };
+
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            | ContentBlock::ToolUse(tool_use) => Some(&tool_use.tool_use),
+            | _ => None,
+        })
+        .map(|tool_use| {
+            tools
+                .call(tool_use.clone())
+                .unwrap_or_else(|error| {
+                    ToolResult::error(&tool_use.id, Some(error.to_string()))
+                })
+                .into()
+        })
+        .collect()
+}
+
+/// The blocking iterator of message chunks.
+///
+/// Each call of `next` blocks until the next chunk is received.
+pub struct MessageChunkIter {
+    stream: Pin<
+        Box<dyn Stream<Item = Result<MessageChunk, StreamError>> + Send>,
+    >,
+    runtime: Arc<Runtime>,
+}
+
+impl Iterator for MessageChunkIter {
+    type Item = Result<MessageChunk, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = &mut self.stream;
+        self.runtime
+            .block_on(poll_fn(|cx| stream.as_mut().poll_next(cx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        Tool, ToolCallError, ToolDefinition, ToolUse,
+    };
+    use crate::transport::{
+        BoxFuture, Transport, TransportError, TransportRequest,
+        TransportResponse,
+    };
+    use http::{HeaderMap, StatusCode};
+    use std::sync::Mutex;
+
+    /// The transport responding the canned bodies in order.
+    struct CannedTransport {
+        responses: Mutex<Vec<&'static str>>,
+        requests: Arc<Mutex<Vec<TransportRequest>>>,
+    }
+
+    impl Transport for CannedTransport {
+        fn send(
+            &self,
+            request: TransportRequest,
+        ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request);
+            let body = self
+                .responses
+                .lock()
+                .unwrap()
+                .remove(0);
+
+            Box::pin(async move {
+                Ok(TransportResponse::from_bytes(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    body,
+                ))
+            })
+        }
+    }
+
+    fn client(
+        responses: Vec<&'static str>
+    ) -> (Client, Arc<Mutex<Vec<TransportRequest>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::from_async(
+            crate::ClientBuilder::new(ApiKey::new("api-key"))
+                .transport(CannedTransport {
+                    responses: Mutex::new(responses),
+                    requests: requests.clone(),
+                })
+                .build(),
+        )
+        .unwrap();
+
+        (client, requests)
+    }
+
+    const MESSAGE_RESPONSE: &str = r#"{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"text","text":"Hello!"}],"model":"claude-3-opus-20240229","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}"#;
+
+    const TOOL_USE_RESPONSE: &str = r#"{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"tool_use","id":"toolu_01","name":"get_weather","input":{"location":"San Francisco, CA"}}],"model":"claude-3-opus-20240229","stop_reason":"tool_use","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}"#;
+
+    const STREAM_RESPONSE: &str = r#"event: message_start
+data: {"type": "message_start", "message": {"id": "msg_01", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 1}}}
+
+event: content_block_delta
+data: {"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hello!"}}
+
+event: message_stop
+data: {"type": "message_stop"}
+
+"#;
+
+    struct GetWeather;
+
+    impl Tool for GetWeather {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition::new(
+                "get_weather",
+                Some("Get the current weather"),
+                serde_json::json!({"type": "object"}),
+            )
+        }
+
+        fn call(
+            &self,
+            tool_use: ToolUse,
+        ) -> Result<ToolResult, ToolCallError> {
+            Ok(ToolResult::success(tool_use.id, Some("15 degrees")))
+        }
+    }
+
+    #[test]
+    fn create_a_message() {
+        let (client, _) = client(vec![MESSAGE_RESPONSE]);
+
+        let response = client
+            .create_a_message(MessagesRequestBody::default())
+            .unwrap();
+
+        assert_eq!(response.id, "msg_01");
+        assert_eq!(
+            response
+                .content
+                .flatten_into_text()
+                .unwrap(),
+            "Hello!"
+        );
+    }
+
+    #[test]
+    fn create_a_message_stream() {
+        let (client, _) = client(vec![STREAM_RESPONSE]);
+
+        let chunks = client
+            .create_a_message_stream(MessagesRequestBody {
+                stream: Some(crate::messages::StreamOption::ReturnStream),
+                ..Default::default()
+            })
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(matches!(
+            chunks[0],
+            MessageChunk::MessageStart(_)
+        ));
+        assert!(matches!(
+            chunks[2],
+            MessageChunk::MessageStop(_)
+        ));
+    }
+
+    #[test]
+    fn create_a_message_with_tools() {
+        let (client, requests) =
+            client(vec![TOOL_USE_RESPONSE, MESSAGE_RESPONSE]);
+        let tools = ToolList::new(vec![Box::new(GetWeather)]);
+
+        let response = client
+            .create_a_message_with_tools(
+                MessagesRequestBody {
+                    messages: vec![Message::user("What is the weather?")],
+                    ..Default::default()
+                },
+                &tools,
+                10,
+            )
+            .unwrap();
+        assert_eq!(response.stop_reason, Some(StopReason::EndTurn));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+
+        let request_body: MessagesRequestBody =
+            serde_json::from_slice(&requests[1].body).unwrap();
+        assert_eq!(
+            request_body.tools,
+            Some(tools.definitions())
+        );
+        assert_eq!(request_body.messages.len(), 3);
+        assert_eq!(
+            request_body.messages[1],
+            Message::assistant(ToolUse::new(
+                "toolu_01",
+                "get_weather",
+                serde_json::json!({"location": "San Francisco, CA"}),
+            ))
+        );
+        assert_eq!(
+            request_body.messages[2],
+            Message::user(Content::MultipleBlocks(vec![
+                ToolResult::success("toolu_01", Some("15 degrees")).into()
+            ]))
+        );
+    }
+
+    #[test]
+    fn create_a_message_with_tools_max_turns() {
+        let (client, requests) =
+            client(vec![TOOL_USE_RESPONSE, TOOL_USE_RESPONSE]);
+        let tools = ToolList::new(vec![Box::new(GetWeather)]);
+
+        let error = client
+            .create_a_message_with_tools(
+                MessagesRequestBody {
+                    messages: vec![Message::user("What is the weather?")],
+                    ..Default::default()
+                },
+                &tools,
+                2,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ToolLoopError::MaxToolTurnsExceeded(2)
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn dispatch_unknown_tool() {
+        let tools = ToolList::new(vec![Box::new(GetWeather)]);
+        let content = Content::from(ToolUse::new(
+            "toolu_01",
+            "unknown",
+            serde_json::Value::Null,
+        ));
+
+        assert_eq!(
+            dispatch_tool_uses(&content, &tools),
+            vec![ToolResult::error(
+                "toolu_01",
+                Some("Tool not found: unknown")
+            )
+            .into()]
+        );
+    }
+}
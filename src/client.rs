@@ -1,203 +1,1404 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use futures_core::Stream;
-use reqwest::RequestBuilder;
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use http::Method;
+use tokio::io::AsyncWrite;
+
+use crate::messages::{
+    CountTokensRequestBody, CountTokensResponseBody, MessageChunk,
+    MessagesError, MessagesRequestBody, MessagesResponseBody, StreamError,
+};
+use crate::batches::{
+    BatchesError, CreateMessageBatchRequestBody, DeletedMessageBatch,
+    ListMessageBatchesParameters, ListMessageBatchesResponseBody,
+    MessageBatch, MessageBatchResultStream,
+};
+use crate::beta::join_betas;
+use crate::completions::{
+    CompletionChunk, CompletionRequestBody, CompletionResponseBody,
+    CompletionsError,
+};
+use crate::credentials::{
+    CredentialChain, CredentialError, CredentialProvider,
+};
+use crate::files::{
+    DeletedFile, FileMetadata, FileUpload, FilesError, ListFilesParameters,
+    ListFilesResponseBody,
+};
+use crate::models::{
+    ListModelsParameters, ListModelsResponseBody, ModelInfo, ModelsError,
+};
+use crate::transport::{
+    BoxFuture, ReqwestTransport, Transport, TransportRequest,
+};
+use crate::{
+    ApiError, ApiKey, Beta, ClientError, Middleware, PageStream, Paginate,
+    RateLimiter, RequestOptions, ResponseMeta, RetryPolicy, Version,
+};
+
+/// The default base URL of the API.
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+
+/// The credential provider of the clients whose requests are authorized by the provider transport,
+/// e.g. Amazon Bedrock and Google Vertex AI, instead of the API key.
+struct TransportCredentials;
+
+impl CredentialProvider for TransportCredentials {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async { Ok(HeaderMap::new()) })
+    }
+}
+
+/// The API client.
+#[derive(Clone)]
+pub struct Client {
+    /// The credential provider.
+    credentials: Arc<dyn CredentialProvider>,
+    /// The API version.
+    version: Version,
+    /// The base URL of the API.
+    base_url: String,
+    /// The HTTP transport.
+    transport: Arc<dyn Transport>,
+    /// Beta features.
+    betas: Vec<Beta>,
+    /// The retry policy.
+    retry_policy: RetryPolicy,
+    /// The middlewares.
+    middlewares: Vec<Arc<dyn Middleware>>,
+    /// The client-side rate limiter.
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl Client {
+    /// Create a new API client with the API key loaded from the environment variable: `ANTHROPIC_API_KEY` and default options.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    ///
+    /// let client = Client::from_env().unwrap();
+    /// ```
+    pub fn from_env() -> Result<Self, std::env::VarError> {
+        let api_key = ApiKey::from_env()?;
+        let version = Version::default();
+        let transport = Arc::new(ReqwestTransport::default());
+
+        Ok(Self {
+            credentials: Arc::new(api_key),
+            version,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            transport,
+            betas: Vec::new(),
+            retry_policy: RetryPolicy::none(),
+            middlewares: Vec::new(),
+            rate_limiter: None,
+        })
+    }
+
+    /// Create a new API client with the API key and default options.
+    ///
+    /// ## Arguments
+    /// - `api_key` - The API key.
+    ///
+    /// ## Example
+    /// ```
+    /// use clust::Client;
+    ///
+    /// let api_key = clust::ApiKey::new("api-key");
+    ///
+    /// let client = Client::from_api_key(api_key);
+    /// ```
+    pub fn from_api_key(api_key: ApiKey) -> Self {
+        let version = Version::default();
+        let transport = Arc::new(ReqwestTransport::default());
+
+        Self {
+            credentials: Arc::new(api_key),
+            version,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            transport,
+            betas: Vec::new(),
+            retry_policy: RetryPolicy::none(),
+            middlewares: Vec::new(),
+            rate_limiter: None,
+        }
+    }
+
+    /// Composes the URL of the API path, e.g. `/v1/messages`, with the base URL.
+    pub(crate) fn url(
+        &self,
+        path: &str,
+    ) -> String {
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Gets the retry policy.
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Gets the middlewares.
+    pub(crate) fn middlewares(&self) -> &[Arc<dyn Middleware>] {
+        &self.middlewares
+    }
+
+    /// Gets the client-side rate limiter.
+    pub(crate) fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Gets the HTTP transport.
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
+    }
+
+    /// Create a request of the `POST` method with the JSON body.
+    ///
+    /// The `betas` are merged with the beta features of the client.
+    pub(crate) fn post<T>(
+        &self,
+        endpoint: &str,
+        body: &T,
+        betas: &[Beta],
+    ) -> Result<TransportRequest, ClientError>
+    where
+        T: serde::Serialize,
+    {
+        let mut request = self.request(Method::POST, endpoint, betas)?;
+        request.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        request.body = serde_json::to_vec(body)
+            .map_err(ClientError::RequestSerializationFailed)?
+            .into();
+
+        Ok(request)
+    }
+
+    /// Create a request with the common headers except the authorization headers added by [`Client::authorize`].
+    ///
+    /// The `betas` are merged with the beta features of the client into a single `anthropic-beta` header.
+    pub(crate) fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        betas: &[Beta],
+    ) -> Result<TransportRequest, ClientError> {
+        let mut request = TransportRequest::new(method, endpoint);
+        request.headers.insert(
+            "anthropic-version",
+            HeaderValue::from_str(&self.version.to_string())?,
+        );
+
+        if let Some(beta) = join_betas(self.betas.iter().chain(betas)) {
+            request.headers.insert(
+                "anthropic-beta",
+                HeaderValue::from_str(&beta)?,
+            );
+        }
+
+        Ok(request)
+    }
+
+    /// Adds the authorization headers of the credential provider to the request, and returns the headers.
+    ///
+    /// The credential provider is asked on every request to rotate credentials.
+    pub(crate) async fn authorize(
+        &self,
+        request: &mut TransportRequest,
+    ) -> Result<HeaderMap, ClientError> {
+        let headers = self
+            .credentials
+            .headers()
+            .await?;
+        request
+            .headers
+            .extend(headers.clone());
+
+        Ok(headers)
+    }
+
+    /// Reports the API error to the credential provider, and returns whether to fail over to other credentials.
+    pub(crate) fn report_credential_error(
+        &self,
+        headers: &HeaderMap,
+        error: &ApiError,
+        retry_after: Option<Duration>,
+    ) -> bool {
+        self.credentials
+            .report_error(headers, &error._type, retry_after)
+    }
+
+    /// Gets the maximum number of failovers to other credentials in a request.
+    pub(crate) fn max_failovers(&self) -> u32 {
+        self.credentials.max_failovers()
+    }
+}
+
+impl Client {
+    /// Create a Message.
+    ///
+    /// Send a structured list of input messages with text and/or image content, and the model will generate the next message in the conversation.
+    ///
+    /// The Messages API can be used for either single queries or stateless multi-turn conversations.
+    ///
+    /// See also [Create a Message](https://docs.anthropic.com/claude/reference/messages_post).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    ///
+    /// ## NOTE
+    /// The `stream` option must be `None` or `StreamOption::ReturnOnce`.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message, Role, MaxTokens};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let model = ClaudeModel::Claude3Sonnet20240229;
+    ///     let max_tokens = MaxTokens::new(1024, model)?;
+    ///     let request_body = MessagesRequestBody {
+    ///         model,
+    ///         max_tokens,
+    ///         messages: vec![
+    ///             Message::user("Hello, Claude!"),
+    ///         ],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let response = client
+    ///         .create_a_message(request_body)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message(
+        &self,
+        request_body: MessagesRequestBody,
+    ) -> Result<MessagesResponseBody, MessagesError> {
+        self.create_a_message_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Create a Message with the per-request options.
+    ///
+    /// See also [`Client::create_a_message`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::{Client, RequestOptions};
+    /// use clust::messages::{MessagesRequestBody, Message};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let request_body = MessagesRequestBody {
+    ///         messages: vec![Message::user("Hello, Claude!")],
+    ///         ..Default::default()
+    ///     };
+    ///     let options = RequestOptions::new()
+    ///         .endpoint("https://gateway.example.com/anthropic/v1/messages");
+    ///
+    ///     let response = client
+    ///         .create_a_message_with_options(request_body, options)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message_with_options(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<MessagesResponseBody, MessagesError> {
+        self.create_a_message_with_meta(request_body, options)
+            .await
+            .map(|(response_body, _)| response_body)
+    }
+
+    /// Create a Message with the metadata of the response.
+    ///
+    /// The metadata contains the request ID and the rate limit information read from the response headers.
+    ///
+    /// See also [`Client::create_a_message`] and [rate limits](https://docs.anthropic.com/en/api/rate-limits#response-headers).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::{Client, RequestOptions};
+    /// use clust::messages::{MessagesRequestBody, Message};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let request_body = MessagesRequestBody {
+    ///         messages: vec![Message::user("Hello, Claude!")],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let (response, meta) = client
+    ///         .create_a_message_with_meta(request_body, RequestOptions::default())
+    ///         .await?;
+    ///
+    ///     println!("Request ID: {:?}", meta.request_id);
+    ///     println!("Remaining requests: {:?}", meta.rate_limit.requests.remaining);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message_with_meta(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<(MessagesResponseBody, ResponseMeta), MessagesError> {
+        crate::messages::api::create_a_message(self, request_body, &options)
+            .await
+    }
+
+    /// Create a Message with incrementally streaming the response using server-sent events (SSE).
+    ///
+    /// See also [Streaming Messages](https://docs.anthropic.com/claude/reference/messages-streaming).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    ///
+    /// ## NOTE
+    /// The `stream` option must be `StreamOption::ReturnStream`.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message, Role, MaxTokens, StreamOption};
+    /// use tokio_stream::StreamExt; // or futures_util::StreamExt to `stream.next().await`.
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let model = ClaudeModel::Claude3Sonnet20240229;
+    ///     let max_tokens = MaxTokens::new(1024, model)?;
+    ///     let request_body = MessagesRequestBody {
+    ///         model,
+    ///         max_tokens,
+    ///         messages: vec![
+    ///             Message::user("Hello, Claude!"),
+    ///         ],
+    ///         stream: Some(StreamOption::ReturnStream),
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let mut stream = client
+    ///         .create_a_message_stream(request_body)
+    ///         .await?;
+    ///
+    ///     while let Some(chunk) = stream.next().await {
+    ///         match chunk {
+    ///             Ok(chunk) => {
+    ///                 // Process the chunk.
+    ///             }
+    ///             Err(error) => {
+    ///                 // Handle the error.
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message_stream(
+        &self,
+        request_body: MessagesRequestBody,
+    ) -> Result<
+        impl Stream<Item = Result<MessageChunk, StreamError>>,
+        MessagesError,
+    > {
+        self.create_a_message_stream_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Create a Message stream with the per-request options.
+    ///
+    /// See also [`Client::create_a_message_stream`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub async fn create_a_message_stream_with_options(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<
+        impl Stream<Item = Result<MessageChunk, StreamError>>,
+        MessagesError,
+    > {
+        self.create_a_message_stream_with_meta(request_body, options)
+            .await
+            .map(|(stream, _)| stream)
+    }
+
+    /// Create a Message stream with the metadata of the response.
+    ///
+    /// The metadata contains the request ID and the rate limit information read from the response headers.
+    ///
+    /// See also [`Client::create_a_message_stream`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub async fn create_a_message_stream_with_meta(
+        &self,
+        request_body: MessagesRequestBody,
+        options: RequestOptions,
+    ) -> Result<
+        (
+            impl Stream<Item = Result<MessageChunk, StreamError>>,
+            ResponseMeta,
+        ),
+        MessagesError,
+    > {
+        crate::messages::api::create_a_message_stream(
+            self,
+            request_body,
+            &options,
+        )
+        .await
+    }
+
+    /// Count the number of tokens in a Message.
+    ///
+    /// The request body can be a [`MessagesRequestBody`] whose model, messages, system prompt, tools and thinking are counted.
+    ///
+    /// See also [Count Message tokens](https://docs.anthropic.com/en/api/messages-count-tokens).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///     let request_body = MessagesRequestBody {
+    ///         model: ClaudeModel::Claude3Sonnet20240229,
+    ///         messages: vec![
+    ///             Message::user("Hello, Claude!"),
+    ///         ],
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     let response = client
+    ///         .count_tokens(request_body)
+    ///         .await?;
+    ///
+    ///     println!("Input tokens: {}", response.input_tokens);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_tokens<B>(
+        &self,
+        request_body: B,
+    ) -> Result<CountTokensResponseBody, MessagesError>
+    where
+        B: Into<CountTokensRequestBody>,
+    {
+        self.count_tokens_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Count the number of tokens in a Message with the per-request options.
+    ///
+    /// See also [`Client::count_tokens`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub async fn count_tokens_with_options<B>(
+        &self,
+        request_body: B,
+        options: RequestOptions,
+    ) -> Result<CountTokensResponseBody, MessagesError>
+    where
+        B: Into<CountTokensRequestBody>,
+    {
+        crate::messages::api::count_tokens(
+            self,
+            request_body.into(),
+            &options,
+        )
+        .await
+        .map(|(response_body, _)| response_body)
+    }
+
+    /// List available models, the most recently released first.
+    ///
+    /// See also [List Models](https://docs.anthropic.com/en/api/models-list).
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::models::ListModelsParameters;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let response = client
+    ///         .list_models(ListModelsParameters::new().limit(10))
+    ///         .await?;
+    ///
+    ///     for model in response.data {
+    ///         println!("{}: {}", model.id, model.display_name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_models(
+        &self,
+        parameters: ListModelsParameters,
+    ) -> Result<ListModelsResponseBody, ModelsError> {
+        self.list_models_with_options(parameters, RequestOptions::default())
+            .await
+    }
+
+    /// List available models with the per-request options.
+    ///
+    /// See also [`Client::list_models`].
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    /// - `options` - The per-request options.
+    pub async fn list_models_with_options(
+        &self,
+        parameters: ListModelsParameters,
+        options: RequestOptions,
+    ) -> Result<ListModelsResponseBody, ModelsError> {
+        crate::models::api::list_models(self, &parameters, &options).await
+    }
+
+    /// Get a model by the model ID or alias.
+    ///
+    /// See also [Get a Model](https://docs.anthropic.com/en/api/models).
+    ///
+    /// ## Arguments
+    /// - `model_id` - The model ID or alias, e.g. `claude-3-opus-20240229`.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::messages::ClaudeModel;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let model_info = client
+    ///         .get_model("claude-3-opus-20240229")
+    ///         .await?;
+    ///     let model: ClaudeModel = model_info.into();
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_model<S>(
+        &self,
+        model_id: S,
+    ) -> Result<ModelInfo, ModelsError>
+    where
+        S: AsRef<str>,
+    {
+        self.get_model_with_options(model_id, RequestOptions::default())
+            .await
+    }
+
+    /// Get a model by the model ID or alias with the per-request options.
+    ///
+    /// See also [`Client::get_model`].
+    ///
+    /// ## Arguments
+    /// - `model_id` - The model ID or alias.
+    /// - `options` - The per-request options.
+    pub async fn get_model_with_options<S>(
+        &self,
+        model_id: S,
+        options: RequestOptions,
+    ) -> Result<ModelInfo, ModelsError>
+    where
+        S: AsRef<str>,
+    {
+        crate::models::api::get_model(self, model_id.as_ref(), &options).await
+    }
+
+    /// Create a Message Batch to process many Messages API requests asynchronously.
+    ///
+    /// See also [Create a Message Batch](https://docs.anthropic.com/en/api/creating-message-batches).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The requests with the custom IDs.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::batches::MessageBatchRequest;
+    /// use clust::messages::{Message, MessagesRequestBody};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let request_body = ["Hello!", "Hi!"]
+    ///         .into_iter()
+    ///         .enumerate()
+    ///         .map(|(index, text)| {
+    ///             MessageBatchRequest::new(
+    ///                 format!("request-{}", index),
+    ///                 MessagesRequestBody {
+    ///                     messages: vec![Message::user(text)],
+    ///                     ..Default::default()
+    ///                 },
+    ///             )
+    ///         })
+    ///         .collect();
+    ///
+    ///     let message_batch = client
+    ///         .create_a_message_batch(request_body)
+    ///         .await?;
+    ///     println!("{}: {}", message_batch.id, message_batch.processing_status);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn create_a_message_batch(
+        &self,
+        request_body: CreateMessageBatchRequestBody,
+    ) -> Result<MessageBatch, BatchesError> {
+        self.create_a_message_batch_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Create a Message Batch with the per-request options.
+    ///
+    /// See also [`Client::create_a_message_batch`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The requests with the custom IDs.
+    /// - `options` - The per-request options.
+    pub async fn create_a_message_batch_with_options(
+        &self,
+        request_body: CreateMessageBatchRequestBody,
+        options: RequestOptions,
+    ) -> Result<MessageBatch, BatchesError> {
+        crate::batches::api::create_a_message_batch(
+            self,
+            &request_body,
+            &options,
+        )
+        .await
+    }
+
+    /// Retrieve a Message Batch to poll its processing status.
+    ///
+    /// See also [Retrieve a Message Batch](https://docs.anthropic.com/en/api/retrieving-message-batches).
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    pub async fn retrieve_a_message_batch<S>(
+        &self,
+        batch_id: S,
+    ) -> Result<MessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        self.retrieve_a_message_batch_with_options(
+            batch_id,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Retrieve a Message Batch with the per-request options.
+    ///
+    /// See also [`Client::retrieve_a_message_batch`].
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    /// - `options` - The per-request options.
+    pub async fn retrieve_a_message_batch_with_options<S>(
+        &self,
+        batch_id: S,
+        options: RequestOptions,
+    ) -> Result<MessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::batches::api::retrieve_a_message_batch(
+            self,
+            batch_id.as_ref(),
+            &options,
+        )
+        .await
+    }
+
+    /// List Message Batches in the workspace, the most recently created first.
+    ///
+    /// See also [List Message Batches](https://docs.anthropic.com/en/api/listing-message-batches).
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    pub async fn list_message_batches(
+        &self,
+        parameters: ListMessageBatchesParameters,
+    ) -> Result<ListMessageBatchesResponseBody, BatchesError> {
+        self.list_message_batches_with_options(
+            parameters,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// List Message Batches with the per-request options.
+    ///
+    /// See also [`Client::list_message_batches`].
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    /// - `options` - The per-request options.
+    pub async fn list_message_batches_with_options(
+        &self,
+        parameters: ListMessageBatchesParameters,
+        options: RequestOptions,
+    ) -> Result<ListMessageBatchesResponseBody, BatchesError> {
+        crate::batches::api::list_message_batches(self, &parameters, &options)
+            .await
+    }
+
+    /// Cancel a Message Batch in processing.
+    ///
+    /// The batch is `canceling` until the requests in processing are done.
+    ///
+    /// See also [Cancel a Message Batch](https://docs.anthropic.com/en/api/canceling-message-batches).
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    pub async fn cancel_a_message_batch<S>(
+        &self,
+        batch_id: S,
+    ) -> Result<MessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        self.cancel_a_message_batch_with_options(
+            batch_id,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Cancel a Message Batch with the per-request options.
+    ///
+    /// See also [`Client::cancel_a_message_batch`].
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    /// - `options` - The per-request options.
+    pub async fn cancel_a_message_batch_with_options<S>(
+        &self,
+        batch_id: S,
+        options: RequestOptions,
+    ) -> Result<MessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::batches::api::cancel_a_message_batch(
+            self,
+            batch_id.as_ref(),
+            &options,
+        )
+        .await
+    }
+
+    /// Delete an ended Message Batch.
+    ///
+    /// See also [Delete a Message Batch](https://docs.anthropic.com/en/api/deleting-message-batches).
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    pub async fn delete_a_message_batch<S>(
+        &self,
+        batch_id: S,
+    ) -> Result<DeletedMessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        self.delete_a_message_batch_with_options(
+            batch_id,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Delete a Message Batch with the per-request options.
+    ///
+    /// See also [`Client::delete_a_message_batch`].
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    /// - `options` - The per-request options.
+    pub async fn delete_a_message_batch_with_options<S>(
+        &self,
+        batch_id: S,
+        options: RequestOptions,
+    ) -> Result<DeletedMessageBatch, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::batches::api::delete_a_message_batch(
+            self,
+            batch_id.as_ref(),
+            &options,
+        )
+        .await
+    }
+
+    /// Retrieve the results of an ended Message Batch as a stream of the results in any order.
+    ///
+    /// See also [Retrieve Message Batch Results](https://docs.anthropic.com/en/api/retrieving-message-batch-results).
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::batches::MessageBatchResultType;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let mut results = client
+    ///         .retrieve_message_batch_results("msgbatch_01")
+    ///         .await?;
+    ///
+    ///     while let Some(result) = results.next().await {
+    ///         let result = result?;
+    ///         match result.result {
+    ///             | MessageBatchResultType::Succeeded { message } => {
+    ///                 println!("{}: {}", result.custom_id, message);
+    ///             },
+    ///             | other => println!("{}: {:?}", result.custom_id, other),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn retrieve_message_batch_results<S>(
+        &self,
+        batch_id: S,
+    ) -> Result<MessageBatchResultStream, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        self.retrieve_message_batch_results_with_options(
+            batch_id,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Retrieve the results of a Message Batch with the per-request options.
+    ///
+    /// The timeout of the options applies to receiving the response headers.
+    ///
+    /// See also [`Client::retrieve_message_batch_results`].
+    ///
+    /// ## Arguments
+    /// - `batch_id` - The ID of the Message Batch.
+    /// - `options` - The per-request options.
+    pub async fn retrieve_message_batch_results_with_options<S>(
+        &self,
+        batch_id: S,
+        options: RequestOptions,
+    ) -> Result<MessageBatchResultStream, BatchesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::batches::api::retrieve_message_batch_results(
+            self,
+            batch_id.as_ref(),
+            &options,
+        )
+        .await
+    }
+
+    /// Upload a file to reference it by the file ID in the Messages API.
+    ///
+    /// See also [Upload File](https://docs.anthropic.com/en/api/files-create).
+    ///
+    /// ## Arguments
+    /// - `upload` - The file to upload.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use clust::Client;
+    /// use clust::files::FileUpload;
+    /// use clust::messages::{Content, ContentBlock, Message};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let file = client
+    ///         .upload_a_file(FileUpload::from_path("report.pdf").await?)
+    ///         .await?;
+    ///
+    ///     let message = Message::user(Content::MultipleBlocks(vec![
+    ///         ContentBlock::from(file.document_source()),
+    ///         ContentBlock::from("Summarize the report."),
+    ///     ]));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn upload_a_file(
+        &self,
+        upload: FileUpload,
+    ) -> Result<FileMetadata, FilesError> {
+        self.upload_a_file_with_options(upload, RequestOptions::default())
+            .await
+    }
+
+    /// Upload a file with the per-request options.
+    ///
+    /// See also [`Client::upload_a_file`].
+    ///
+    /// ## Arguments
+    /// - `upload` - The file to upload.
+    /// - `options` - The per-request options.
+    pub async fn upload_a_file_with_options(
+        &self,
+        upload: FileUpload,
+        options: RequestOptions,
+    ) -> Result<FileMetadata, FilesError> {
+        crate::files::api::upload_a_file(self, &upload, &options).await
+    }
 
-use crate::messages::{
-    MessageChunk, MessagesError, MessagesRequestBody, MessagesResponseBody,
-    StreamError,
-};
-use crate::{ApiKey, Beta, Version};
+    /// List uploaded files, the most recently created first.
+    ///
+    /// See also [List Files](https://docs.anthropic.com/en/api/files-list).
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    pub async fn list_files(
+        &self,
+        parameters: ListFilesParameters,
+    ) -> Result<ListFilesResponseBody, FilesError> {
+        self.list_files_with_options(parameters, RequestOptions::default())
+            .await
+    }
 
-/// The API client.
-#[derive(Clone)]
-pub struct Client {
-    /// The API key.
-    api_key: ApiKey,
-    /// The API version.
-    version: Version,
-    /// Internal HTTP client.
-    client: reqwest::Client,
-    /// Beta feature.
-    beta: Option<Beta>,
-}
+    /// List uploaded files with the per-request options.
+    ///
+    /// See also [`Client::list_files`].
+    ///
+    /// ## Arguments
+    /// - `parameters` - The pagination parameters.
+    /// - `options` - The per-request options.
+    pub async fn list_files_with_options(
+        &self,
+        parameters: ListFilesParameters,
+        options: RequestOptions,
+    ) -> Result<ListFilesResponseBody, FilesError> {
+        crate::files::api::list_files(self, &parameters, &options).await
+    }
 
-impl Client {
-    /// Create a new API client with the API key loaded from the environment variable: `ANTHROPIC_API_KEY` and default options.
+    /// Paginate a list endpoint as a stream of the objects, fetching the pages lazily.
+    ///
+    /// The parameters of the first page determine the page size by the `limit`,
+    /// and the direction by the `before_id` or the `after_id`.
+    ///
+    /// ## Arguments
+    /// - `parameters` - The parameters of the first page, e.g. [`ListModelsParameters`].
     ///
     /// ## Example
     /// ```no_run
     /// use clust::Client;
+    /// use clust::models::ListModelsParameters;
+    /// use tokio_stream::StreamExt;
     ///
-    /// let client = Client::from_env().unwrap();
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
+    ///
+    ///     let mut models = client
+    ///         .paginate(ListModelsParameters::new().limit(100))
+    ///         .limit(250);
+    ///
+    ///     while let Some(model) = models.next().await {
+    ///         let model = model?;
+    ///         println!("{}: {}", model.id, model.display_name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn from_env() -> Result<Self, std::env::VarError> {
-        let api_key = ApiKey::from_env()?;
-        let version = Version::default();
-        let client = reqwest::Client::new();
+    pub fn paginate<P>(
+        &self,
+        parameters: P,
+    ) -> PageStream<'_, P>
+    where
+        P: Paginate,
+    {
+        self.paginate_with_options(parameters, RequestOptions::default())
+    }
 
-        Ok(Self {
-            api_key,
-            version,
-            client,
-            beta: None,
-        })
+    /// Paginate a list endpoint as a stream of the objects with the per-request options applied to each page.
+    ///
+    /// See also [`Client::paginate`].
+    ///
+    /// ## Arguments
+    /// - `parameters` - The parameters of the first page.
+    /// - `options` - The per-request options.
+    pub fn paginate_with_options<P>(
+        &self,
+        parameters: P,
+        options: RequestOptions,
+    ) -> PageStream<'_, P>
+    where
+        P: Paginate,
+    {
+        PageStream::new(self, parameters, options)
     }
 
-    /// Create a new API client with the API key and default options.
+    /// Retrieve the metadata of an uploaded file.
+    ///
+    /// See also [Get File Metadata](https://docs.anthropic.com/en/api/files-metadata).
     ///
     /// ## Arguments
-    /// - `api_key` - The API key.
+    /// - `file_id` - The ID of the file.
+    pub async fn retrieve_file_metadata<S>(
+        &self,
+        file_id: S,
+    ) -> Result<FileMetadata, FilesError>
+    where
+        S: AsRef<str>,
+    {
+        self.retrieve_file_metadata_with_options(
+            file_id,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Retrieve the metadata of an uploaded file with the per-request options.
+    ///
+    /// See also [`Client::retrieve_file_metadata`].
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the file.
+    /// - `options` - The per-request options.
+    pub async fn retrieve_file_metadata_with_options<S>(
+        &self,
+        file_id: S,
+        options: RequestOptions,
+    ) -> Result<FileMetadata, FilesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::files::api::retrieve_file_metadata(
+            self,
+            file_id.as_ref(),
+            &options,
+        )
+        .await
+    }
+
+    /// Download the content of a file into the writer as it arrives, and returns the number of bytes written.
+    ///
+    /// Only files created by tools can be downloaded.
+    ///
+    /// See also [Download a File](https://docs.anthropic.com/en/api/files-content).
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the file.
+    /// - `writer` - The writer of the file content.
     ///
     /// ## Example
-    /// ```
+    /// ```no_run
     /// use clust::Client;
     ///
-    /// let api_key = clust::ApiKey::new("api-key");
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let client = Client::from_env()?;
     ///
-    /// let client = Client::from_api_key(api_key);
+    ///     let mut file = tokio::fs::File::create("output.csv").await?;
+    ///     let written = client
+    ///         .download_a_file("file_011CNha8iCJcU1wXNR6q4V8w", &mut file)
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn from_api_key(api_key: ApiKey) -> Self {
-        let version = Version::default();
-        let client = reqwest::Client::new();
-
-        Self {
-            api_key,
-            version,
-            client,
-            beta: None,
-        }
+    pub async fn download_a_file<S, W>(
+        &self,
+        file_id: S,
+        writer: &mut W,
+    ) -> Result<u64, FilesError>
+    where
+        S: AsRef<str>,
+        W: AsyncWrite + Unpin,
+    {
+        self.download_a_file_with_options(
+            file_id,
+            writer,
+            RequestOptions::default(),
+        )
+        .await
     }
 
-    /// Create a request builder for the `POST` method.
-    pub(crate) fn post(
+    /// Download the content of a file with the per-request options.
+    ///
+    /// The timeout of the options applies to the whole download.
+    ///
+    /// See also [`Client::download_a_file`].
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the file.
+    /// - `writer` - The writer of the file content.
+    /// - `options` - The per-request options.
+    pub async fn download_a_file_with_options<S, W>(
         &self,
-        endpoint: &str,
-    ) -> RequestBuilder {
-        let mut builder = self
-            .client
-            .post(endpoint)
-            .header("x-api-key", self.api_key.value())
-            .header(
-                "anthropic-version",
-                self.version.to_string(),
-            );
-
-        if let Some(beta) = self.beta {
-            builder = builder.header("anthropic-beta", beta.to_string());
-        }
+        file_id: S,
+        writer: &mut W,
+        options: RequestOptions,
+    ) -> Result<u64, FilesError>
+    where
+        S: AsRef<str>,
+        W: AsyncWrite + Unpin,
+    {
+        crate::files::api::download_a_file(
+            self,
+            file_id.as_ref(),
+            writer,
+            &options,
+        )
+        .await
+    }
 
-        builder
+    /// Delete an uploaded file.
+    ///
+    /// See also [Delete a File](https://docs.anthropic.com/en/api/files-delete).
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the file.
+    pub async fn delete_a_file<S>(
+        &self,
+        file_id: S,
+    ) -> Result<DeletedFile, FilesError>
+    where
+        S: AsRef<str>,
+    {
+        self.delete_a_file_with_options(file_id, RequestOptions::default())
+            .await
     }
-}
 
-impl Client {
-    /// Create a Message.
+    /// Delete an uploaded file with the per-request options.
     ///
-    /// Send a structured list of input messages with text and/or image content, and the model will generate the next message in the conversation.
+    /// See also [`Client::delete_a_file`].
     ///
-    /// The Messages API can be used for either single queries or stateless multi-turn conversations.
+    /// ## Arguments
+    /// - `file_id` - The ID of the file.
+    /// - `options` - The per-request options.
+    pub async fn delete_a_file_with_options<S>(
+        &self,
+        file_id: S,
+        options: RequestOptions,
+    ) -> Result<DeletedFile, FilesError>
+    where
+        S: AsRef<str>,
+    {
+        crate::files::api::delete_a_file(self, file_id.as_ref(), &options)
+            .await
+    }
+
+    /// Create a Text Completion by the legacy Text Completions API.
     ///
-    /// See also [Create a Message](https://docs.anthropic.com/claude/reference/messages_post).
+    /// See also [Create a Text Completion](https://docs.anthropic.com/en/api/complete).
     ///
     /// ## Arguments
     /// - `request_body` - The request body.
     ///
-    /// ## NOTE
-    /// The `stream` option must be `None` or `StreamOption::ReturnOnce`.
-    ///
     /// ## Example
     /// ```no_run
     /// use clust::Client;
-    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message, Role, MaxTokens};
+    /// use clust::completions::{prompt_from_messages, CompletionRequestBody};
+    /// use clust::messages::{ClaudeModel, Message};
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
     ///     let client = Client::from_env()?;
-    ///     let model = ClaudeModel::Claude3Sonnet20240229;
-    ///     let max_tokens = MaxTokens::new(1024, model)?;
-    ///     let request_body = MessagesRequestBody {
-    ///         model,
-    ///         max_tokens,
-    ///         messages: vec![
-    ///             Message::user("Hello, Claude!"),
-    ///         ],
+    ///
+    ///     let request_body = CompletionRequestBody {
+    ///         model: ClaudeModel::Other("claude-2.1".to_string()),
+    ///         prompt: prompt_from_messages(&[Message::user("Hello, Claude")])?,
+    ///         max_tokens_to_sample: 256,
     ///         ..Default::default()
     ///     };
     ///
-    ///     let response = client
-    ///         .create_a_message(request_body, None)
+    ///     let response_body = client
+    ///         .create_a_completion(request_body)
     ///         .await?;
+    ///     println!("{}", response_body.completion);
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn create_a_message(
+    pub async fn create_a_completion(
         &self,
-        request_body: MessagesRequestBody,
-        endpoint: Option<String>,
-    ) -> Result<MessagesResponseBody, MessagesError> {
-        let endpoint = endpoint.unwrap_or("https://api.anthropic.com/v1/messages".to_string());
-        crate::messages::api::create_a_message(self, request_body, &endpoint).await
+        request_body: CompletionRequestBody,
+    ) -> Result<CompletionResponseBody, CompletionsError> {
+        self.create_a_completion_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
     }
 
-    /// Create a Message with incrementally streaming the response using server-sent events (SSE).
+    /// Create a Text Completion with the per-request options.
     ///
-    /// See also [Streaming Messages](https://docs.anthropic.com/claude/reference/messages-streaming).
+    /// See also [`Client::create_a_completion`].
     ///
     /// ## Arguments
     /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub async fn create_a_completion_with_options(
+        &self,
+        request_body: CompletionRequestBody,
+        options: RequestOptions,
+    ) -> Result<CompletionResponseBody, CompletionsError> {
+        crate::completions::api::create_a_completion(
+            self,
+            &request_body,
+            &options,
+        )
+        .await
+    }
+
+    /// Create a Text Completion stream by the legacy Text Completions API.
     ///
-    /// ## NOTE
-    /// The `stream` option must be `StreamOption::ReturnStream`.
+    /// The `stream` of the request body must be [`StreamOption::ReturnStream`](crate::messages::StreamOption::ReturnStream).
+    ///
+    /// See also [Streaming Text Completions](https://docs.anthropic.com/en/api/streaming).
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
     ///
     /// ## Example
     /// ```no_run
     /// use clust::Client;
-    /// use clust::messages::{MessagesRequestBody, ClaudeModel, Message, Role, MaxTokens, StreamOption};
-    /// use tokio_stream::StreamExt; // or futures_util::StreamExt to `stream.next().await`.
+    /// use clust::completions::{CompletionChunk, CompletionRequestBody, AI_PROMPT, HUMAN_PROMPT};
+    /// use clust::messages::{ClaudeModel, StreamOption};
+    /// use tokio_stream::StreamExt;
     ///
     /// #[tokio::main]
     /// async fn main() -> anyhow::Result<()> {
     ///     let client = Client::from_env()?;
-    ///     let model = ClaudeModel::Claude3Sonnet20240229;
-    ///     let max_tokens = MaxTokens::new(1024, model)?;
-    ///     let request_body = MessagesRequestBody {
-    ///         model,
-    ///         max_tokens,
-    ///         messages: vec![
-    ///             Message::user("Hello, Claude!"),
-    ///         ],
+    ///
+    ///     let request_body = CompletionRequestBody {
+    ///         model: ClaudeModel::Other("claude-2.1".to_string()),
+    ///         prompt: format!("{HUMAN_PROMPT} Hello, Claude{AI_PROMPT}"),
+    ///         max_tokens_to_sample: 256,
     ///         stream: Some(StreamOption::ReturnStream),
     ///         ..Default::default()
     ///     };
     ///
     ///     let mut stream = client
-    ///         .create_a_message_stream(request_body)
+    ///         .create_a_completion_stream(request_body)
     ///         .await?;
     ///
     ///     while let Some(chunk) = stream.next().await {
-    ///         match chunk {
-    ///             Ok(chunk) => {
-    ///                 // Process the chunk.
-    ///             }
-    ///             Err(error) => {
-    ///                 // Handle the error.
-    ///             }
+    ///         if let CompletionChunk::Completion(completion) = chunk? {
+    ///             print!("{}", completion.completion);
     ///         }
     ///     }
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub async fn create_a_message_stream(
+    pub async fn create_a_completion_stream(
         &self,
-        request_body: MessagesRequestBody,
-        endpoint: Option<String>,
+        request_body: CompletionRequestBody,
     ) -> Result<
-        impl Stream<Item = Result<MessageChunk, StreamError>>,
-        MessagesError,
+        impl Stream<Item = Result<CompletionChunk, StreamError>>,
+        CompletionsError,
+    > {
+        self.create_a_completion_stream_with_options(
+            request_body,
+            RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Create a Text Completion stream with the per-request options.
+    ///
+    /// See also [`Client::create_a_completion_stream`].
+    ///
+    /// ## Arguments
+    /// - `request_body` - The request body.
+    /// - `options` - The per-request options.
+    pub async fn create_a_completion_stream_with_options(
+        &self,
+        request_body: CompletionRequestBody,
+        options: RequestOptions,
+    ) -> Result<
+        impl Stream<Item = Result<CompletionChunk, StreamError>>,
+        CompletionsError,
     > {
-        let endpoint = endpoint.unwrap_or("https://api.anthropic.com/v1/messages".to_string());
-        crate::messages::api::create_a_message_stream(self, request_body, &endpoint).await
+        crate::completions::api::create_a_completion_stream(
+            self,
+            &request_body,
+            &options,
+        )
+        .await
     }
 }
 
@@ -209,33 +1410,76 @@ impl Client {
 /// use clust::ApiKey;
 /// use clust::Version;
 /// use clust::Beta;
+/// use clust::RetryPolicy;
 ///
 /// let client = ClientBuilder::new(ApiKey::new("api-key"))
 ///     .version(Version::V2023_06_01)
+///     .base_url("https://api.anthropic.com")
 ///     .client(reqwest::Client::new())
 ///     .beta(Beta::Tools2024_04_04)
+///     .retry_policy(RetryPolicy::new().max_attempts(3))
 ///     .build();
 /// ```
 #[derive(Clone)]
 pub struct ClientBuilder {
-    /// The API key.
-    api_key: ApiKey,
+    /// The credential provider.
+    credentials: Arc<dyn CredentialProvider>,
     /// The API version.
     version: Option<Version>,
-    /// Internal HTTP client.
-    client: Option<reqwest::Client>,
-    /// Beta feature.
-    beta: Option<Beta>,
+    /// The base URL of the API.
+    base_url: Option<String>,
+    /// The HTTP transport.
+    transport: Option<Arc<dyn Transport>>,
+    /// Beta features.
+    betas: Vec<Beta>,
+    /// The retry policy.
+    retry_policy: Option<RetryPolicy>,
+    /// The middlewares.
+    middlewares: Vec<Arc<dyn Middleware>>,
+    /// The client-side rate limiter.
+    rate_limiter: Option<RateLimiter>,
+    /// The Amazon Bedrock provider.
+    #[cfg(feature = "bedrock")]
+    bedrock: Option<crate::bedrock::Bedrock>,
+    /// The Google Vertex AI provider.
+    vertex: Option<crate::vertex::Vertex>,
+}
+
+impl Default for ClientBuilder {
+    /// Creates a new API client builder without credentials.
+    ///
+    /// Set the credentials by [`ClientBuilder::credentials`],
+    /// or the provider by [`ClientBuilder::vertex`] or `ClientBuilder::bedrock`.
+    fn default() -> Self {
+        Self::with_credentials(CredentialChain::new())
+    }
 }
 
 impl ClientBuilder {
     /// Creates a new API client builder with the API key.
     pub fn new(api_key: ApiKey) -> Self {
+        Self::with_credentials(api_key)
+    }
+
+    /// Creates a new API client builder with the credential provider instead of the API key.
+    ///
+    /// The provider is asked for the authorization headers on every request.
+    pub fn with_credentials<C>(credentials: C) -> Self
+    where
+        C: CredentialProvider + 'static,
+    {
         Self {
-            api_key,
+            credentials: Arc::new(credentials),
             version: None,
-            client: None,
-            beta: None,
+            base_url: None,
+            transport: None,
+            betas: Vec::new(),
+            retry_policy: None,
+            middlewares: Vec::new(),
+            rate_limiter: None,
+            #[cfg(feature = "bedrock")]
+            bedrock: None,
+            vertex: None,
         }
     }
 
@@ -246,6 +1490,20 @@ impl ClientBuilder {
         Ok(Self::new(api_key))
     }
 
+    /// Sets the credential provider instead of the API key.
+    ///
+    /// The provider is asked for the authorization headers on every request.
+    pub fn credentials<C>(
+        mut self,
+        credentials: C,
+    ) -> Self
+    where
+        C: CredentialProvider + 'static,
+    {
+        self.credentials = Arc::new(credentials);
+        self
+    }
+
     /// Sets the API version.
     pub fn version(
         mut self,
@@ -255,21 +1513,149 @@ impl ClientBuilder {
         self
     }
 
-    /// Sets the HTTP client.
+    /// Sets the base URL of the API, e.g. an Anthropic-compatible gateway.
+    ///
+    /// The default is `https://api.anthropic.com`.
+    pub fn base_url<S>(
+        mut self,
+        base_url: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Sets the HTTP client of the default transport.
     pub fn client(
         mut self,
         client: reqwest::Client,
     ) -> Self {
-        self.client = Some(client);
+        self.transport = Some(Arc::new(ReqwestTransport::new(client)));
+        self
+    }
+
+    /// Sets the default transport with the timeout of connecting to the server,
+    /// which is retried by the retry policy and surfaced as `ConnectTimeout` of the API errors.
+    ///
+    /// This replaces the transport set by [`ClientBuilder::client`] or [`ClientBuilder::transport`],
+    /// so configure the timeout of your own HTTP client instead, e.g. by `reqwest::ClientBuilder::connect_timeout`.
+    pub fn connect_timeout(
+        mut self,
+        connect_timeout: Duration,
+    ) -> Self {
+        self.transport = Some(Arc::new(
+            ReqwestTransport::with_connect_timeout(connect_timeout),
+        ));
+        self
+    }
+
+    /// Sets the HTTP transport instead of the default one implemented by `reqwest`.
+    pub fn transport<T>(
+        mut self,
+        transport: T,
+    ) -> Self
+    where
+        T: Transport + 'static,
+    {
+        self.transport = Some(Arc::new(transport));
         self
     }
 
-    /// Sets the beta feature.
+    /// Adds a beta feature.
+    ///
+    /// Beta features required by a request body, e.g. the 1-hour cache TTL, are added automatically per request.
     pub fn beta(
         mut self,
         beta: Beta,
     ) -> Self {
-        self.beta = Some(beta);
+        self.betas.push(beta);
+        self
+    }
+
+    /// Adds the beta features.
+    pub fn betas<I>(
+        mut self,
+        betas: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Beta>,
+    {
+        self.betas.extend(betas);
+        self
+    }
+
+    /// Sets the retry policy.
+    ///
+    /// The client does not retry by default.
+    pub fn retry_policy(
+        mut self,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Adds a middleware to intercept the Messages API calling.
+    ///
+    /// Middlewares are called in the order of addition for requests,
+    /// and in the reverse order for responses and chunks.
+    pub fn middleware<M>(
+        mut self,
+        middleware: M,
+    ) -> Self
+    where
+        M: Middleware + 'static,
+    {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sets the client-side rate limiter.
+    ///
+    /// The client does not limit requests by default.
+    pub fn rate_limiter(
+        mut self,
+        rate_limiter: RateLimiter,
+    ) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sends requests to Amazon Bedrock instead of the Anthropic API.
+    ///
+    /// The requests are rewritten for Bedrock and signed by the AWS credentials instead of the API key,
+    /// then sent by the transport of this builder.
+    /// The API key and the credential provider of this builder are not used, so the API key can be empty.
+    ///
+    /// This replaces the Google Vertex AI provider set by [`ClientBuilder::vertex`].
+    #[cfg(feature = "bedrock")]
+    pub fn bedrock(
+        mut self,
+        bedrock: crate::bedrock::Bedrock,
+    ) -> Self {
+        self.bedrock = Some(bedrock);
+        self.vertex = None;
+        self
+    }
+
+    /// Sends requests to Google Vertex AI instead of the Anthropic API.
+    ///
+    /// The requests are rewritten for Vertex AI and authorized by the access token instead of the API key,
+    /// then sent by the transport of this builder.
+    /// The API key and the credential provider of this builder are not used, so the API key can be empty.
+    ///
+    /// This replaces the Amazon Bedrock provider set by `ClientBuilder::bedrock`.
+    pub fn vertex(
+        mut self,
+        vertex: crate::vertex::Vertex,
+    ) -> Self {
+        #[cfg(feature = "bedrock")]
+        {
+            self.bedrock = None;
+        }
+        self.vertex = Some(vertex);
         self
     }
 
@@ -278,15 +1664,41 @@ impl ClientBuilder {
         let version = self
             .version
             .unwrap_or_default();
-        let client = self
-            .client
-            .unwrap_or_default();
+        let transport: Arc<dyn Transport> = self
+            .transport
+            .unwrap_or_else(|| Arc::new(ReqwestTransport::default()));
+        let mut credentials = self.credentials;
+        #[cfg(feature = "bedrock")]
+        let transport: Arc<dyn Transport> = match self.bedrock {
+            | Some(bedrock) => {
+                credentials = Arc::new(TransportCredentials);
+                Arc::new(crate::bedrock::BedrockTransport::new(
+                    bedrock, transport,
+                ))
+            },
+            | None => transport,
+        };
+        let transport: Arc<dyn Transport> = match self.vertex {
+            | Some(vertex) => {
+                credentials = Arc::new(TransportCredentials);
+                Arc::new(crate::vertex::VertexTransport::new(vertex, transport))
+            },
+            | None => transport,
+        };
 
         Client {
-            api_key: self.api_key,
+            credentials,
             version,
-            client,
-            beta: self.beta,
+            base_url: self
+                .base_url
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            transport,
+            betas: self.betas,
+            retry_policy: self
+                .retry_policy
+                .unwrap_or_else(RetryPolicy::none),
+            middlewares: self.middlewares,
+            rate_limiter: self.rate_limiter,
         }
     }
 }
@@ -295,16 +1707,29 @@ impl ClientBuilder {
 mod tests {
     use super::*;
 
+    fn api_key(client: &Client) -> String {
+        let headers = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(client.credentials.headers())
+            .unwrap();
+
+        headers["x-api-key"]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
     #[test]
     fn builder() {
         let client = ClientBuilder::new(ApiKey::new("api-key")).build();
-        assert_eq!(client.api_key.value(), "api-key");
+        assert_eq!(api_key(&client), "api-key");
         assert_eq!(client.version, Version::default());
 
         let client = ClientBuilder::new(ApiKey::new("api-key"))
             .version(Version::V2023_01_01)
             .build();
-        assert_eq!(client.api_key.value(), "api-key");
+        assert_eq!(api_key(&client), "api-key");
         assert_eq!(client.version, Version::V2023_01_01);
 
         let client = ClientBuilder::new(ApiKey::new("api-key"))
@@ -315,13 +1740,98 @@ mod tests {
                     .unwrap(),
             )
             .build();
-        assert_eq!(client.api_key.value(), "api-key");
+        assert_eq!(api_key(&client), "api-key");
         assert_eq!(client.version, Version::default());
 
         let client = ClientBuilder::new(ApiKey::new("api-key"))
             .beta(Beta::Tools2024_04_04)
             .build();
-        assert_eq!(client.api_key.value(), "api-key");
-        assert_eq!(client.beta, Some(Beta::Tools2024_04_04));
+        assert_eq!(api_key(&client), "api-key");
+        assert_eq!(client.betas, vec![Beta::Tools2024_04_04]);
+
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .beta(Beta::Tools2024_04_04)
+            .betas(vec![
+                Beta::ExtendedCacheTtl2025_04_11,
+                Beta::Other("new-beta-2025-01-01".to_string()),
+            ])
+            .build();
+        assert_eq!(
+            client.betas,
+            vec![
+                Beta::Tools2024_04_04,
+                Beta::ExtendedCacheTtl2025_04_11,
+                Beta::Other("new-beta-2025-01-01".to_string()),
+            ]
+        );
+
+        let client = ClientBuilder::new(ApiKey::new("api-key")).build();
+        assert_eq!(client.base_url, "https://api.anthropic.com");
+
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url("https://gateway.example.com/anthropic")
+            .build();
+        assert_eq!(
+            client.base_url,
+            "https://gateway.example.com/anthropic"
+        );
+
+        let client = ClientBuilder::new(ApiKey::new("api-key")).build();
+        assert_eq!(client.retry_policy, RetryPolicy::none());
+
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .retry_policy(RetryPolicy::new().max_attempts(5))
+            .build();
+        assert_eq!(
+            client.retry_policy,
+            RetryPolicy::new().max_attempts(5)
+        );
+    }
+
+    #[test]
+    fn url() {
+        let client = Client::from_api_key(ApiKey::new("api-key"));
+        assert_eq!(
+            client.url("/v1/messages"),
+            "https://api.anthropic.com/v1/messages"
+        );
+
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url("https://gateway.example.com/anthropic/")
+            .build();
+        assert_eq!(
+            client.url("/v1/messages/count_tokens"),
+            "https://gateway.example.com/anthropic/v1/messages/count_tokens"
+        );
+        assert_eq!(
+            client.url("v1/models"),
+            "https://gateway.example.com/anthropic/v1/models"
+        );
+    }
+
+    #[cfg(feature = "bedrock")]
+    #[test]
+    fn providers_are_exclusive() {
+        let bedrock = crate::bedrock::Bedrock::new(
+            "us-west-2",
+            crate::bedrock::AwsCredentials::new("AKIDEXAMPLE", "secret"),
+        );
+        let vertex = crate::vertex::Vertex::new(
+            "my-project",
+            "us-east5",
+            crate::vertex::StaticAccessToken::new("access-token"),
+        );
+
+        let builder = ClientBuilder::default()
+            .bedrock(bedrock.clone())
+            .vertex(vertex.clone());
+        assert!(builder.bedrock.is_none());
+        assert!(builder.vertex.is_some());
+
+        let builder = ClientBuilder::default()
+            .vertex(vertex)
+            .bedrock(bedrock);
+        assert!(builder.bedrock.is_some());
+        assert!(builder.vertex.is_none());
     }
 }
@@ -0,0 +1,22 @@
+//! The legacy [Text Completions API](https://docs.anthropic.com/en/api/complete) implementations.
+//!
+//! The Text Completions API is superseded by the [Messages API](`crate::messages`),
+//! but prompts of `\n\nHuman:` and `\n\nAssistant:` turns are still supported for existing pipelines.
+
+mod completion_chunk;
+mod completion_request_body;
+mod completion_response_body;
+mod error;
+mod prompt;
+
+pub(crate) mod api;
+
+pub use completion_chunk::CompletionChunk;
+pub use completion_request_body::CompletionRequestBody;
+pub use completion_response_body::CompletionObjectType;
+pub use completion_response_body::CompletionResponseBody;
+pub use error::CompletionsError;
+pub use error::PromptConversionError;
+pub use prompt::prompt_from_messages;
+pub use prompt::AI_PROMPT;
+pub use prompt::HUMAN_PROMPT;
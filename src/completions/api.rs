@@ -0,0 +1,202 @@
+use crate::completions::{
+    CompletionChunk, CompletionRequestBody, CompletionResponseBody,
+    CompletionsError,
+};
+use crate::instrument::CallSpan;
+use crate::messages::chunk_stream::ChunkStream;
+use crate::messages::StreamOption;
+use crate::request::{call_json, send_request, with_timeout, CallError};
+use crate::transport::ByteStream;
+use crate::{Client, RequestOptions};
+
+/// The path of the Text Completions API.
+const COMPLETIONS_PATH: &str = "/v1/complete";
+
+pub(crate) async fn create_a_completion(
+    client: &Client,
+    request_body: &CompletionRequestBody,
+    options: &RequestOptions,
+) -> Result<CompletionResponseBody, CompletionsError> {
+    // Validate stream option.
+    if let Some(stream) = &request_body.stream {
+        if *stream != StreamOption::ReturnOnce {
+            return Err(CompletionsError::StreamOptionMismatch);
+        }
+    }
+
+    call_json(
+        client,
+        "completions.create",
+        COMPLETIONS_PATH,
+        |endpoint| client.post(endpoint, request_body, &[]),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn create_a_completion_stream(
+    client: &Client,
+    request_body: &CompletionRequestBody,
+    options: &RequestOptions,
+) -> Result<ChunkStream<ByteStream, CompletionChunk>, CompletionsError> {
+    // Validate stream option.
+    if request_body.stream != Some(StreamOption::ReturnStream) {
+        return Err(CompletionsError::StreamOptionMismatch);
+    }
+
+    // Send the request, retries only happen before the first chunk.
+    let span = CallSpan::api("completions.stream");
+    let call = with_timeout(options.timeout, async {
+        send_request(
+            client,
+            COMPLETIONS_PATH,
+            |endpoint| client.post(endpoint, request_body, &[]),
+            options,
+            &span,
+        )
+        .await
+    });
+    let response = match span.instrument(call).await {
+        | Ok(result) => result.map_err(CompletionsError::from),
+        | Err(timeout) => Err(CompletionsError::timeout(timeout)),
+    }
+    .inspect_err(|error| {
+        span.record_error(error);
+    })?;
+
+    // Create a chunk stream from response bytes stream.
+    Ok(ChunkStream::new(response.body).with_timeouts(
+        options.first_chunk_timeout,
+        options.idle_timeout,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{StubResponse, StubServer};
+    use crate::{ApiKey, ClientBuilder};
+    use futures_util::StreamExt;
+
+    async fn client(responses: Vec<StubResponse>) -> (Client, StubServer) {
+        let server = StubServer::start(responses).await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        (client, server)
+    }
+
+    fn request_body(stream: Option<StreamOption>) -> CompletionRequestBody {
+        CompletionRequestBody {
+            prompt: "\n\nHuman: Hello\n\nAssistant:".to_string(),
+            max_tokens_to_sample: 256,
+            stream,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn create_a_completion() {
+        let (client, server) = client(vec![StubResponse::json(
+            200,
+            r#"{"type":"completion","id":"compl_01","completion":" Hello!","stop_reason":"stop_sequence","model":"claude-2.1"}"#,
+        )])
+        .await;
+
+        let response_body = super::create_a_completion(
+            &client,
+            &request_body(None),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response_body.completion, " Hello!");
+        assert_eq!(
+            response_body.stop_reason,
+            Some(crate::messages::StopReason::StopSequence)
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests[0].request_line, "POST /v1/complete HTTP/1.1");
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["max_tokens_to_sample"], 256);
+        assert_eq!(
+            body["prompt"],
+            "\n\nHuman: Hello\n\nAssistant:"
+        );
+    }
+
+    #[tokio::test]
+    async fn create_a_completion_stream() {
+        let (client, _server) = client(vec![StubResponse::Http {
+            status: 200,
+            headers: vec![(
+                "content-type".to_string(),
+                "text/event-stream".to_string(),
+            )],
+            body: concat!(
+                "event: completion\n",
+                r#"data: {"type": "completion", "completion": " Hello", "stop_reason": null, "model": "claude-2.1", "id": "compl_01"}"#,
+                "\n\n",
+                "event: ping\n",
+                r#"data: {"type": "ping"}"#,
+                "\n\n",
+                "event: completion\n",
+                r#"data: {"type": "completion", "completion": "!", "stop_reason": "stop_sequence", "model": "claude-2.1", "id": "compl_01"}"#,
+                "\n\n",
+            )
+            .to_string(),
+        }])
+        .await;
+
+        let chunks = super::create_a_completion_stream(
+            &client,
+            &request_body(Some(StreamOption::ReturnStream)),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1], CompletionChunk::Ping);
+        let completion = chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                | CompletionChunk::Completion(completion) => {
+                    Some(completion.completion.as_str())
+                },
+                | _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(completion, " Hello!");
+    }
+
+    #[tokio::test]
+    async fn stream_option_mismatch() {
+        let (client, _server) = client(vec![]).await;
+
+        assert!(matches!(
+            super::create_a_completion(
+                &client,
+                &request_body(Some(StreamOption::ReturnStream)),
+                &RequestOptions::default(),
+            )
+            .await,
+            Err(CompletionsError::StreamOptionMismatch)
+        ));
+        assert!(matches!(
+            super::create_a_completion_stream(
+                &client,
+                &request_body(None),
+                &RequestOptions::default(),
+            )
+            .await,
+            Err(CompletionsError::StreamOptionMismatch)
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::completions::CompletionResponseBody;
+use crate::messages::chunk_stream::ParseChunk;
+use crate::messages::StreamError;
+use crate::ApiErrorResponse;
+
+/// The stream chunk of the legacy Text Completions API.
+///
+/// See also [Streaming Text Completions](https://docs.anthropic.com/en/api/streaming).
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompletionChunk {
+    /// The `completion` event with the next part of the completion.
+    Completion(CompletionResponseBody),
+    /// The `ping` event.
+    Ping,
+    /// The `error` event, e.g. `overloaded_error` in high usage periods.
+    Error(ApiErrorResponse),
+}
+
+impl ParseChunk for CompletionChunk {
+    fn parse(source: &str) -> Result<Self, StreamError> {
+        let mut event = None;
+        let mut data = None;
+        for line in source.lines() {
+            if let Some(value) = line.strip_prefix("event: ") {
+                event = Some(value);
+            } else if let Some(value) = line.strip_prefix("data: ") {
+                data = Some(value);
+            }
+        }
+
+        let (Some(event), Some(data)) = (event, data)
+        else {
+            return Err(StreamError::ParseChunkStringError(format!(
+                "Chunk must have 'event: ' and 'data: ' lines, but not: {source}"
+            )));
+        };
+
+        match event {
+            | "completion" => Ok(CompletionChunk::Completion(
+                serde_json::from_str(data)?,
+            )),
+            | "ping" => Ok(CompletionChunk::Ping),
+            | "error" => Ok(CompletionChunk::Error(serde_json::from_str(
+                data,
+            )?)),
+            | _ => Err(StreamError::ParseChunkStringError(format!(
+                "Not supported completion event: {event}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let chunk = CompletionChunk::parse(
+            "event: completion\ndata: {\"type\": \"completion\", \"completion\": \" Hello\", \"stop_reason\": null, \"model\": \"claude-2.0\", \"id\": \"compl_01\"}",
+        )
+        .unwrap();
+        assert!(matches!(
+            chunk,
+            CompletionChunk::Completion(completion)
+                if completion.completion == " Hello"
+        ));
+
+        assert_eq!(
+            CompletionChunk::parse("event: ping\ndata: {\"type\": \"ping\"}")
+                .unwrap(),
+            CompletionChunk::Ping
+        );
+
+        let chunk = CompletionChunk::parse(
+            "event: error\ndata: {\"type\": \"error\", \"error\": {\"type\": \"overloaded_error\", \"message\": \"Overloaded\"}}",
+        )
+        .unwrap();
+        assert!(matches!(
+            chunk,
+            CompletionChunk::Error(error)
+                if error.error._type == "overloaded_error"
+        ));
+
+        assert!(matches!(
+            CompletionChunk::parse("event: unknown\ndata: {}"),
+            Err(StreamError::ParseChunkStringError(_))
+        ));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::macros::impl_display_for_serialize;
+use crate::messages::{
+    ClaudeModel, Metadata, StopSequence, StreamOption, Temperature, TopK, TopP,
+};
+
+/// The request body for the legacy Text Completions API.
+///
+/// See also [Create a Text Completion](https://docs.anthropic.com/en/api/complete).
+///
+/// ## Example
+/// ```
+/// use clust::completions::{CompletionRequestBody, AI_PROMPT, HUMAN_PROMPT};
+/// use clust::messages::ClaudeModel;
+///
+/// let request_body = CompletionRequestBody {
+///     model: ClaudeModel::Other("claude-2.1".to_string()),
+///     prompt: format!("{HUMAN_PROMPT} Hello, Claude{AI_PROMPT}"),
+///     max_tokens_to_sample: 256,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CompletionRequestBody {
+    /// The model that will complete your prompt.
+    pub model: ClaudeModel,
+    /// The prompt that you want Claude to complete.
+    ///
+    /// The prompt must alternate `\n\nHuman:` and `\n\nAssistant:` turns and end with `\n\nAssistant:`.
+    /// See [`crate::completions::prompt_from_messages`] to convert messages into a prompt.
+    pub prompt: String,
+    /// The maximum number of tokens to generate before stopping.
+    pub max_tokens_to_sample: u32,
+    /// Sequences that will cause the model to stop generating.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<StopSequence>>,
+    /// Amount of randomness injected into the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<Temperature>,
+    /// Use nucleus sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<TopP>,
+    /// Only sample from the top K options for each subsequent token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<TopK>,
+    /// An object describing metadata about the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+    /// Whether to incrementally stream the response using server-sent events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<StreamOption>,
+}
+
+impl_display_for_serialize!(CompletionRequestBody);
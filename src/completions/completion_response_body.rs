@@ -0,0 +1,64 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::{ClaudeModel, StopReason};
+
+/// The response body for the legacy Text Completions API,
+/// which is also the data of a `completion` event in streaming.
+///
+/// See also [Create a Text Completion](https://docs.anthropic.com/en/api/complete).
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CompletionResponseBody {
+    /// Unique object identifier.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For Text Completions, this is always "completion".
+    #[serde(rename = "type")]
+    pub _type: CompletionObjectType,
+    /// The resulting completion up to and excluding the stop sequences.
+    pub completion: String,
+    /// The reason that we stopped, `stop_sequence` or `max_tokens`.
+    ///
+    /// In streaming, it is null until the last `completion` event.
+    pub stop_reason: Option<StopReason>,
+    /// The model that handled the request.
+    pub model: ClaudeModel,
+}
+
+impl_display_for_serialize!(CompletionResponseBody);
+
+/// The object type for text completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionObjectType {
+    /// completion
+    Completion,
+}
+
+impl Default for CompletionObjectType {
+    fn default() -> Self {
+        Self::Completion
+    }
+}
+
+impl Display for CompletionObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | CompletionObjectType::Completion => {
+                write!(f, "completion")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    CompletionObjectType,
+    Completion => "completion"
+);
@@ -0,0 +1,40 @@
+use crate::request::impl_call_error;
+use crate::transport::TransportError;
+use crate::{ApiError, ClientError};
+use std::time::Duration;
+
+/// The error type for the Text Completions API.
+#[derive(Debug, thiserror::Error)]
+pub enum CompletionsError {
+    /// The client error.
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    /// The API error.
+    #[error(transparent)]
+    ApiError(#[from] ApiError),
+    /// Stream option mismatch.
+    #[error("Stream option mismatch")]
+    StreamOptionMismatch,
+    /// Timed out connecting to the server.
+    #[error("Connect timed out: {0}")]
+    ConnectTimeout(TransportError),
+    /// Timed out until the response headers are received.
+    #[error("Response headers timed out after {0:?}")]
+    HeadersTimeout(Duration),
+    /// Timed out the whole call.
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+impl_call_error!(CompletionsError);
+
+/// The error type for converting messages into a prompt of the Text Completions API.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PromptConversionError {
+    /// No messages to convert.
+    #[error("No messages to convert")]
+    Empty,
+    /// The message at the index has a content block other than text.
+    #[error("Message at index {0} has a content block other than text")]
+    NotTextContent(usize),
+}
@@ -0,0 +1,110 @@
+use crate::completions::PromptConversionError;
+use crate::messages::{Content, ContentBlock, Message, Role};
+
+/// The prefix of a human turn in a prompt of the Text Completions API.
+pub const HUMAN_PROMPT: &str = "\n\nHuman:";
+
+/// The prefix of an assistant turn in a prompt of the Text Completions API.
+pub const AI_PROMPT: &str = "\n\nAssistant:";
+
+/// Converts the messages into a prompt of the legacy Text Completions API.
+///
+/// Each user message becomes a `\n\nHuman:` turn and each assistant message becomes a `\n\nAssistant:` turn.
+/// An empty `\n\nAssistant:` turn is appended unless the last message is from the assistant to prefill the completion.
+///
+/// Only text content can be converted.
+///
+/// ## Example
+/// ```
+/// use clust::completions::prompt_from_messages;
+/// use clust::messages::Message;
+///
+/// let prompt = prompt_from_messages(&[
+///     Message::user("Hello"),
+///     Message::assistant("Hi!"),
+///     Message::user("How are you?"),
+/// ])
+/// .unwrap();
+///
+/// assert_eq!(
+///     prompt,
+///     "\n\nHuman: Hello\n\nAssistant: Hi!\n\nHuman: How are you?\n\nAssistant:"
+/// );
+/// ```
+pub fn prompt_from_messages(
+    messages: &[Message]
+) -> Result<String, PromptConversionError> {
+    let Some(last) = messages.last() else {
+        return Err(PromptConversionError::Empty);
+    };
+
+    let mut prompt = String::new();
+    for (index, message) in messages.iter().enumerate() {
+        let prefix = match message.role {
+            | Role::User => HUMAN_PROMPT,
+            | Role::Assistant => AI_PROMPT,
+        };
+        let text = text_of(&message.content)
+            .ok_or(PromptConversionError::NotTextContent(index))?;
+
+        prompt.push_str(prefix);
+        prompt.push(' ');
+        prompt.push_str(&text);
+    }
+
+    if last.role != Role::Assistant {
+        prompt.push_str(AI_PROMPT);
+    }
+
+    Ok(prompt)
+}
+
+/// Concatenates the text of the content, or `None` if it has other blocks.
+fn text_of(content: &Content) -> Option<String> {
+    match content {
+        | Content::SingleText(text) => Some(text.clone()),
+        | Content::MultipleBlocks(blocks) => blocks
+            .iter()
+            .map(|block| match block {
+                | ContentBlock::Text(text) => Some(text.text.as_str()),
+                | _ => None,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::ImageContentSource;
+
+    #[test]
+    fn prefill() {
+        assert_eq!(
+            prompt_from_messages(&[
+                Message::user(Content::MultipleBlocks(vec![
+                    ContentBlock::from("Hello, "),
+                    ContentBlock::from("Claude"),
+                ])),
+                Message::assistant("Sure,"),
+            ])
+            .unwrap(),
+            "\n\nHuman: Hello, Claude\n\nAssistant: Sure,"
+        );
+    }
+
+    #[test]
+    fn error() {
+        assert_eq!(
+            prompt_from_messages(&[]),
+            Err(PromptConversionError::Empty)
+        );
+        assert_eq!(
+            prompt_from_messages(&[
+                Message::user("Hello"),
+                Message::user(ImageContentSource::default()),
+            ]),
+            Err(PromptConversionError::NotTextContent(1))
+        );
+    }
+}
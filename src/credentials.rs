@@ -0,0 +1,959 @@
+//! The credential providers to authorize requests of the API.
+//!
+//! The client asks the [`CredentialProvider`] for the authorization headers on every request,
+//! so that credentials can be rotated without rebuilding the [`crate::Client`].
+//!
+//! Built-in providers:
+//! - [`crate::ApiKey`]: The fixed API key.
+//! - [`EnvApiKey`]: The API key loaded from an environment variable.
+//! - [`FileApiKey`]: The API key loaded from a file.
+//! - [`CommandApiKey`]: The API key printed by a command.
+//! - [`BearerToken`]: The fixed bearer token.
+//! - [`OAuthToken`]: The bearer token refreshed by a [`TokenRefresher`], e.g. [`RefreshTokenGrant`].
+//! - [`KeyPool`]: The API keys in rotation with failover.
+//! - [`CredentialChain`]: The first available credentials of the providers.
+//!
+//! ## Example
+//! ```no_run
+//! use clust::credentials::{CredentialChain, EnvApiKey, FileApiKey};
+//! use clust::ClientBuilder;
+//!
+//! let credentials = CredentialChain::new()
+//!     .provider(EnvApiKey::new())
+//!     .provider(FileApiKey::new("/run/secrets/anthropic-api-key"));
+//!
+//! let client = ClientBuilder::with_credentials(credentials).build();
+//! ```
+
+use std::env::VarError;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::header::{
+    HeaderMap, HeaderValue, InvalidHeaderValue, ACCEPT, AUTHORIZATION,
+    CONTENT_TYPE,
+};
+use http::Method;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::transport::{
+    BoxFuture, ReqwestTransport, Transport, TransportRequest,
+};
+use crate::{ApiErrorType, ApiKey};
+
+mod key_pool;
+
+pub use key_pool::{KeyHealth, KeyPool, KeyStatus};
+
+/// The environment variable of the API key.
+const API_KEY_VAR: &str = "ANTHROPIC_API_KEY";
+
+/// The provider of the authorization headers of requests.
+///
+/// ## Example
+/// ```
+/// use clust::credentials::{CredentialError, CredentialProvider};
+/// use clust::http::{HeaderMap, HeaderValue};
+/// use clust::transport::BoxFuture;
+///
+/// struct MyCredentials;
+///
+/// impl CredentialProvider for MyCredentials {
+///     fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+///         Box::pin(async {
+///             let mut headers = HeaderMap::new();
+///             // Fetch the API key from your secret store.
+///             headers.insert("x-api-key", HeaderValue::from_static("api-key"));
+///             Ok(headers)
+///         })
+///     }
+/// }
+/// ```
+pub trait CredentialProvider: Send + Sync {
+    /// Gets the authorization headers of a request.
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>>;
+
+    /// Reports the API error of the request authorized by the headers.
+    ///
+    /// Returns `true` if the provider has other credentials to fail over to,
+    /// then the request is sent again with the next headers up to [`CredentialProvider::max_failovers`] times.
+    ///
+    /// The default implementation ignores the error and returns `false`.
+    fn report_error(
+        &self,
+        _headers: &HeaderMap,
+        _error: &ApiErrorType,
+        _retry_after: Option<Duration>,
+    ) -> bool {
+        false
+    }
+
+    /// Gets the maximum number of failovers in a request,
+    /// after which the error is handled by the retry policy of the client.
+    ///
+    /// The default implementation returns `0`.
+    fn max_failovers(&self) -> u32 {
+        0
+    }
+}
+
+impl<T> CredentialProvider for Arc<T>
+where
+    T: CredentialProvider + ?Sized,
+{
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        self.as_ref().headers()
+    }
+
+    fn report_error(
+        &self,
+        headers: &HeaderMap,
+        error: &ApiErrorType,
+        retry_after: Option<Duration>,
+    ) -> bool {
+        self.as_ref()
+            .report_error(headers, error, retry_after)
+    }
+
+    fn max_failovers(&self) -> u32 {
+        self.as_ref().max_failovers()
+    }
+}
+
+/// The error of the credential providers.
+#[derive(Debug, thiserror::Error)]
+pub enum CredentialError {
+    /// The environment variable is not available.
+    #[error("Environment variable {name} is not available: {source:?}")]
+    EnvironmentVariable {
+        name: String,
+        source: VarError,
+    },
+    /// Failed to read the credentials file.
+    #[error("Failed to read credentials file {path:?}: {source:?}")]
+    ReadFileFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Failed to run the credentials command.
+    #[error("Failed to run credentials command {program:?}: {source:?}")]
+    RunCommandFailed {
+        program: String,
+        source: std::io::Error,
+    },
+    /// The credentials command exited with a failure.
+    #[error("Credentials command {program:?} exited with {status}: {stderr}")]
+    CommandExited {
+        program: String,
+        status: ExitStatus,
+        stderr: String,
+    },
+    /// The credential is empty.
+    #[error("The credential is empty")]
+    Empty,
+    /// Failed to refresh the access token.
+    #[error("Failed to refresh the access token: {0}")]
+    RefreshFailed(Box<dyn Error + Send + Sync>),
+    /// Invalid header value of the credential.
+    #[error("Invalid header value: {0:?}")]
+    InvalidHeaderValue(#[from] InvalidHeaderValue),
+    /// All keys of the [`KeyPool`] are removed from the rotation.
+    #[error("No available key in the pool")]
+    NoAvailableKey,
+    /// None of the providers in the chain has credentials.
+    #[error("No credentials in the chain: {0:?}")]
+    NoCredentials(Vec<CredentialError>),
+}
+
+impl CredentialProvider for ApiKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async { api_key_headers(self.value()) })
+    }
+}
+
+/// The API key loaded from the environment variable on every request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvApiKey {
+    name: String,
+}
+
+impl Default for EnvApiKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvApiKey {
+    /// Creates a new provider of the environment variable: `ANTHROPIC_API_KEY`.
+    pub fn new() -> Self {
+        Self::var(API_KEY_VAR)
+    }
+
+    /// Creates a new provider of the environment variable.
+    pub fn var<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+        }
+    }
+}
+
+impl CredentialProvider for EnvApiKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async {
+            let value = std::env::var(&self.name).map_err(|source| {
+                CredentialError::EnvironmentVariable {
+                    name: self.name.clone(),
+                    source,
+                }
+            })?;
+
+            api_key_headers(&value)
+        })
+    }
+}
+
+/// The API key loaded from the file on every request, e.g. a mounted secret.
+///
+/// Leading and trailing whitespaces of the file are trimmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileApiKey {
+    path: PathBuf,
+}
+
+impl FileApiKey {
+    /// Creates a new provider of the file.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            path: path.into(),
+        }
+    }
+}
+
+impl CredentialProvider for FileApiKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async {
+            let value = tokio::fs::read_to_string(&self.path)
+                .await
+                .map_err(|source| CredentialError::ReadFileFailed {
+                    path: self.path.clone(),
+                    source,
+                })?;
+
+            api_key_headers(&value)
+        })
+    }
+}
+
+/// The API key printed to the standard output by the command, e.g. a password manager.
+///
+/// The command is run on every request unless the cache TTL is set.
+/// Leading and trailing whitespaces of the output are trimmed.
+#[derive(Debug)]
+pub struct CommandApiKey {
+    program: String,
+    args: Vec<String>,
+    ttl: Option<Duration>,
+    cache: Mutex<Option<(String, Instant)>>,
+}
+
+impl CommandApiKey {
+    /// Creates a new provider of the command.
+    pub fn new<S>(program: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            ttl: None,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Adds the arguments of the command.
+    pub fn args<I, S>(
+        mut self,
+        args: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args
+            .extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the TTL to cache the output of the command.
+    pub fn ttl(
+        mut self,
+        ttl: Duration,
+    ) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    async fn run(&self) -> Result<String, CredentialError> {
+        let output = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .output()
+            .await
+            .map_err(|source| CredentialError::RunCommandFailed {
+                program: self.program.clone(),
+                source,
+            })?;
+
+        if !output.status.success() {
+            return Err(CredentialError::CommandExited {
+                program: self.program.clone(),
+                status: output.status,
+                stderr: String::from_utf8_lossy(&output.stderr)
+                    .trim()
+                    .to_string(),
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl CredentialProvider for CommandApiKey {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async {
+            let Some(ttl) = self.ttl
+            else {
+                return api_key_headers(&self.run().await?);
+            };
+
+            let mut cache = self.cache.lock().await;
+            if let Some((value, expires_at)) = cache.as_ref() {
+                if Instant::now() < *expires_at {
+                    return api_key_headers(value);
+                }
+            }
+
+            let value = self.run().await?;
+            let headers = api_key_headers(&value)?;
+            *cache = Some((value, Instant::now() + ttl));
+
+            Ok(headers)
+        })
+    }
+}
+
+/// The fixed bearer token sent by the `authorization` header.
+///
+/// The value is redacted in the `Debug` format.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BearerToken {
+    value: String,
+}
+
+impl BearerToken {
+    /// Creates a new bearer token.
+    pub fn new<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for BearerToken {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_tuple("BearerToken")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+impl CredentialProvider for BearerToken {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async { bearer_headers(&self.value) })
+    }
+}
+
+/// An access token issued by a [`TokenRefresher`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    /// The access token.
+    pub value: String,
+    /// The lifetime of the access token, or `None` if it does not expire.
+    pub expires_in: Option<Duration>,
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("value", &"[REDACTED]")
+            .field("expires_in", &self.expires_in)
+            .finish()
+    }
+}
+
+/// The refresher of access tokens used by [`OAuthToken`].
+pub trait TokenRefresher: Send + Sync {
+    /// Issues a new access token.
+    fn refresh(&self) -> BoxFuture<'_, Result<AccessToken, CredentialError>>;
+}
+
+/// The bearer token refreshed by the [`TokenRefresher`] before it expires.
+///
+/// Concurrent requests share a single refresh.
+pub struct OAuthToken {
+    refresher: Box<dyn TokenRefresher>,
+    margin: Duration,
+    token: Mutex<Option<(String, Option<Instant>)>>,
+}
+
+impl std::fmt::Debug for OAuthToken {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("OAuthToken")
+            .field("margin", &self.margin)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OAuthToken {
+    /// Creates a new OAuth token with the refresher.
+    pub fn new<R>(refresher: R) -> Self
+    where
+        R: TokenRefresher + 'static,
+    {
+        Self {
+            refresher: Box::new(refresher),
+            margin: Duration::from_secs(60),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Sets the margin to refresh the access token before it expires.
+    ///
+    /// The default is 60 seconds.
+    pub fn margin(
+        mut self,
+        margin: Duration,
+    ) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+impl CredentialProvider for OAuthToken {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async {
+            let mut token = self.token.lock().await;
+            if let Some((value, expires_at)) = token.as_ref() {
+                if expires_at.map_or(true, |expires_at| {
+                    Instant::now() + self.margin < expires_at
+                }) {
+                    return bearer_headers(value);
+                }
+            }
+
+            let refreshed = self.refresher.refresh().await?;
+            let headers = bearer_headers(&refreshed.value)?;
+            *token = Some((
+                refreshed.value,
+                refreshed
+                    .expires_in
+                    .map(|expires_in| Instant::now() + expires_in),
+            ));
+
+            Ok(headers)
+        })
+    }
+}
+
+/// The [refresh token grant](https://datatracker.ietf.org/doc/html/rfc6749#section-6) of OAuth 2.0.
+///
+/// A rotated refresh token in the token response is used for the next refresh.
+pub struct RefreshTokenGrant {
+    token_url: String,
+    client_id: String,
+    client_secret: Option<String>,
+    scope: Option<String>,
+    refresh_token: Mutex<String>,
+    transport: Arc<dyn Transport>,
+}
+
+impl std::fmt::Debug for RefreshTokenGrant {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("RefreshTokenGrant")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RefreshTokenGrant {
+    /// Creates a new refresh token grant.
+    ///
+    /// ## Arguments
+    /// - `token_url` - The URL of the token endpoint.
+    /// - `client_id` - The client ID.
+    /// - `refresh_token` - The refresh token.
+    pub fn new<S, T, U>(
+        token_url: S,
+        client_id: T,
+        refresh_token: U,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+        U: Into<String>,
+    {
+        Self {
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            client_secret: None,
+            scope: None,
+            refresh_token: Mutex::new(refresh_token.into()),
+            transport: Arc::new(ReqwestTransport::default()),
+        }
+    }
+
+    /// Sets the client secret of a confidential client.
+    pub fn client_secret<S>(
+        mut self,
+        client_secret: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Sets the scope of the access token.
+    pub fn scope<S>(
+        mut self,
+        scope: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Sets the HTTP transport to call the token endpoint.
+    pub fn transport<T>(
+        mut self,
+        transport: T,
+    ) -> Self
+    where
+        T: Transport + 'static,
+    {
+        self.transport = Arc::new(transport);
+        self
+    }
+}
+
+/// The successful token response of OAuth 2.0.
+#[derive(serde::Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+impl TokenRefresher for RefreshTokenGrant {
+    fn refresh(&self) -> BoxFuture<'_, Result<AccessToken, CredentialError>> {
+        Box::pin(async {
+            let mut refresh_token = self.refresh_token.lock().await;
+
+            let mut parameters = vec![
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token.as_str()),
+                ("client_id", self.client_id.as_str()),
+            ];
+            if let Some(client_secret) = &self.client_secret {
+                parameters.push(("client_secret", client_secret));
+            }
+            if let Some(scope) = &self.scope {
+                parameters.push(("scope", scope));
+            }
+
+            let mut request =
+                TransportRequest::new(Method::POST, self.token_url.clone());
+            request.headers.insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/x-www-form-urlencoded"),
+            );
+            request.headers.insert(
+                ACCEPT,
+                HeaderValue::from_static("application/json"),
+            );
+            request.body = form_urlencode(&parameters).into();
+
+            let response = self
+                .transport
+                .send(request)
+                .await
+                .map_err(|error| CredentialError::RefreshFailed(error.into()))?;
+            let status = response.status;
+            let body = response
+                .bytes()
+                .await
+                .map_err(|error| CredentialError::RefreshFailed(error.into()))?;
+
+            if !status.is_success() {
+                return Err(CredentialError::RefreshFailed(
+                    format!(
+                        "({status}) {}",
+                        String::from_utf8_lossy(&body)
+                    )
+                    .into(),
+                ));
+            }
+
+            let token: TokenResponse = serde_json::from_slice(&body)
+                .map_err(|error| CredentialError::RefreshFailed(error.into()))?;
+            if let Some(rotated) = token.refresh_token {
+                *refresh_token = rotated;
+            }
+
+            Ok(AccessToken {
+                value: token.access_token,
+                expires_in: token
+                    .expires_in
+                    .map(Duration::from_secs),
+            })
+        })
+    }
+}
+
+/// The first available credentials of the providers in order.
+#[derive(Clone, Default)]
+pub struct CredentialChain {
+    providers: Vec<Arc<dyn CredentialProvider>>,
+}
+
+impl std::fmt::Debug for CredentialChain {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("CredentialChain")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+impl CredentialChain {
+    /// Creates a new empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a provider to the end of the chain.
+    pub fn provider<P>(
+        mut self,
+        provider: P,
+    ) -> Self
+    where
+        P: CredentialProvider + 'static,
+    {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+}
+
+impl CredentialProvider for CredentialChain {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async {
+            let mut errors = Vec::new();
+            for provider in &self.providers {
+                match provider.headers().await {
+                    | Ok(headers) => return Ok(headers),
+                    | Err(error) => errors.push(error),
+                }
+            }
+
+            Err(CredentialError::NoCredentials(errors))
+        })
+    }
+
+    fn report_error(
+        &self,
+        headers: &HeaderMap,
+        error: &ApiErrorType,
+        retry_after: Option<Duration>,
+    ) -> bool {
+        self.providers
+            .iter()
+            .fold(false, |failover, provider| {
+                provider.report_error(headers, error, retry_after) || failover
+            })
+    }
+
+    fn max_failovers(&self) -> u32 {
+        self.providers
+            .iter()
+            .map(|provider| provider.max_failovers())
+            .sum()
+    }
+}
+
+/// Creates the `x-api-key` header of the API key.
+pub(crate) fn api_key_headers(value: &str) -> Result<HeaderMap, CredentialError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CredentialError::Empty);
+    }
+
+    let mut api_key = HeaderValue::from_str(value)?;
+    api_key.set_sensitive(true);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api-key", api_key);
+    Ok(headers)
+}
+
+/// Creates the `authorization` header of the bearer token.
+fn bearer_headers(value: &str) -> Result<HeaderMap, CredentialError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CredentialError::Empty);
+    }
+
+    let mut authorization = HeaderValue::from_str(&format!("Bearer {value}"))?;
+    authorization.set_sensitive(true);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, authorization);
+    Ok(headers)
+}
+
+/// Encodes the parameters into `application/x-www-form-urlencoded`.
+fn form_urlencode(parameters: &[(&str, &str)]) -> String {
+    fn encode(value: &str) -> String {
+        value
+            .bytes()
+            .map(|byte| match byte {
+                | b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_'
+                | b'.' | b'*' => (byte as char).to_string(),
+                | b' ' => "+".to_string(),
+                | _ => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+
+    parameters
+        .iter()
+        .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{StubResponse, StubServer};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// The refresher counting refreshes with the token lifetime of 10 minutes.
+    struct CountingRefresher {
+        count: Arc<AtomicU32>,
+    }
+
+    impl TokenRefresher for CountingRefresher {
+        fn refresh(
+            &self
+        ) -> BoxFuture<'_, Result<AccessToken, CredentialError>> {
+            Box::pin(async {
+                let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok(AccessToken {
+                    value: format!("token-{count}"),
+                    expires_in: Some(Duration::from_secs(600)),
+                })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn api_key() {
+        let headers = ApiKey::new("api-key")
+            .headers()
+            .await
+            .unwrap();
+
+        assert_eq!(headers["x-api-key"], "api-key");
+        assert!(headers["x-api-key"].is_sensitive());
+    }
+
+    #[tokio::test]
+    async fn env_api_key() {
+        std::env::set_var("CLUST_TEST_ENV_API_KEY", "env-api-key");
+
+        let headers = EnvApiKey::var("CLUST_TEST_ENV_API_KEY")
+            .headers()
+            .await
+            .unwrap();
+        assert_eq!(headers["x-api-key"], "env-api-key");
+
+        let error = EnvApiKey::var("CLUST_TEST_MISSING_API_KEY")
+            .headers()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CredentialError::EnvironmentVariable { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn file_api_key() {
+        let path = std::env::temp_dir().join(format!(
+            "clust-file-api-key-{}",
+            std::process::id()
+        ));
+        std::fs::write(&path, "file-api-key\n").unwrap();
+
+        let provider = FileApiKey::new(&path);
+        let headers = provider.headers().await.unwrap();
+        assert_eq!(headers["x-api-key"], "file-api-key");
+
+        // Rotated without rebuilding the provider.
+        std::fs::write(&path, "rotated-api-key").unwrap();
+        let headers = provider.headers().await.unwrap();
+        assert_eq!(headers["x-api-key"], "rotated-api-key");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn command_api_key() {
+        let headers = CommandApiKey::new("echo")
+            .args(["command-api-key"])
+            .headers()
+            .await
+            .unwrap();
+        assert_eq!(headers["x-api-key"], "command-api-key");
+
+        let error = CommandApiKey::new("false")
+            .headers()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CredentialError::CommandExited { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn bearer_token() {
+        let headers = BearerToken::new("token")
+            .headers()
+            .await
+            .unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer token");
+        assert!(headers.get("x-api-key").is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn oauth_token_refresh() {
+        let count = Arc::new(AtomicU32::new(0));
+        let token = OAuthToken::new(CountingRefresher {
+            count: count.clone(),
+        });
+
+        let headers = token.headers().await.unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer token-1");
+
+        // Cached until the margin before the expiration.
+        tokio::time::advance(Duration::from_secs(530)).await;
+        let headers = token.headers().await.unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer token-1");
+
+        tokio::time::advance(Duration::from_secs(20)).await;
+        let headers = token.headers().await.unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer token-2");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn refresh_token_grant() {
+        let server = StubServer::start(vec![
+            StubResponse::json(
+                200,
+                r#"{"access_token":"access-1","token_type":"Bearer","expires_in":3600,"refresh_token":"refresh-2"}"#,
+            ),
+            StubResponse::json(
+                200,
+                r#"{"access_token":"access-2","token_type":"Bearer"}"#,
+            ),
+        ])
+        .await;
+        let grant = RefreshTokenGrant::new(
+            server.url("/oauth/token"),
+            "client-id",
+            "refresh 1",
+        );
+
+        let token = grant.refresh().await.unwrap();
+        assert_eq!(token.value, "access-1");
+        assert_eq!(
+            token.expires_in,
+            Some(Duration::from_secs(3600))
+        );
+        let token = grant.refresh().await.unwrap();
+        assert_eq!(token.value, "access-2");
+        assert_eq!(token.expires_in, None);
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].body_text(),
+            "grant_type=refresh_token&refresh_token=refresh+1&client_id=client-id"
+        );
+        assert_eq!(
+            requests[1].body_text(),
+            "grant_type=refresh_token&refresh_token=refresh-2&client_id=client-id"
+        );
+    }
+
+    #[tokio::test]
+    async fn chain() {
+        let chain = CredentialChain::new()
+            .provider(EnvApiKey::var("CLUST_TEST_MISSING_API_KEY"))
+            .provider(BearerToken::new("token"));
+        let headers = chain.headers().await.unwrap();
+        assert_eq!(headers[AUTHORIZATION], "Bearer token");
+
+        let error = CredentialChain::new()
+            .provider(EnvApiKey::var("CLUST_TEST_MISSING_API_KEY"))
+            .headers()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            CredentialError::NoCredentials(errors) if errors.len() == 1
+        ));
+    }
+}
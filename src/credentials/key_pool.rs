@@ -0,0 +1,386 @@
+//! The pool of API keys in rotation.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use http::HeaderMap;
+use tokio::time::Instant;
+
+use crate::credentials::{api_key_headers, CredentialError, CredentialProvider};
+use crate::transport::BoxFuture;
+use crate::{ApiErrorType, ApiKey};
+
+/// The health of a key in the [`KeyPool`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyHealth {
+    /// The key is in rotation.
+    Healthy,
+    /// The key is out of rotation until the `retry-after` window of a rate limit error passes.
+    BackedOff {
+        /// The remaining duration of the window.
+        remaining: Duration,
+    },
+    /// The key is removed from rotation by an authentication or permission error.
+    Removed(ApiErrorType),
+}
+
+/// The status of a key in the [`KeyPool`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyStatus {
+    /// The API key.
+    pub api_key: ApiKey,
+    /// The health of the key.
+    pub health: KeyHealth,
+    /// The number of requests authorized by the key.
+    pub requests: u64,
+    /// The number of rate limit errors of the key.
+    pub rate_limit_errors: u64,
+    /// The number of authentication and permission errors of the key.
+    pub auth_errors: u64,
+}
+
+/// The pool of API keys to spread requests across them in round-robin.
+///
+/// A key is removed from rotation by an authentication or permission error,
+/// and backed off for the `retry-after` window by a rate limit error,
+/// then the request fails over to the next key in rotation.
+///
+/// When all keys in rotation are backed off, the key whose window ends first is used.
+///
+/// ## Example
+/// ```
+/// use clust::credentials::KeyPool;
+/// use clust::{ApiKey, ClientBuilder};
+///
+/// let pool = std::sync::Arc::new(KeyPool::new([
+///     ApiKey::new("api-key-1"),
+///     ApiKey::new("api-key-2"),
+/// ]));
+///
+/// let client = ClientBuilder::with_credentials(pool.clone()).build();
+///
+/// for status in pool.status() {
+///     println!("{:?}: {} requests", status.health, status.requests);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct KeyPool {
+    keys: Vec<ApiKey>,
+    backoff: Duration,
+    state: Mutex<PoolState>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    next: usize,
+    keys: Vec<KeyState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct KeyState {
+    backed_off_until: Option<Instant>,
+    removed: Option<ApiErrorType>,
+    requests: u64,
+    rate_limit_errors: u64,
+    auth_errors: u64,
+}
+
+impl KeyState {
+    fn is_available(
+        &self,
+        now: Instant,
+    ) -> bool {
+        self.removed.is_none()
+            && self
+                .backed_off_until
+                .map_or(true, |until| until <= now)
+    }
+}
+
+impl KeyPool {
+    /// Creates a new pool of the API keys.
+    pub fn new<I>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = ApiKey>,
+    {
+        let keys: Vec<ApiKey> = keys.into_iter().collect();
+        let state = PoolState {
+            next: 0,
+            keys: vec![KeyState::default(); keys.len()],
+        };
+
+        Self {
+            keys,
+            backoff: Duration::from_secs(60),
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Sets the backoff of a rate limited key without the `retry-after` header.
+    ///
+    /// The default is 60 seconds.
+    pub fn backoff(
+        mut self,
+        backoff: Duration,
+    ) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Gets the status of the keys in order.
+    pub fn status(&self) -> Vec<KeyStatus> {
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+
+        self.keys
+            .iter()
+            .zip(&state.keys)
+            .map(|(api_key, key)| KeyStatus {
+                api_key: api_key.clone(),
+                health: match (&key.removed, key.backed_off_until) {
+                    | (Some(error), _) => KeyHealth::Removed(error.clone()),
+                    | (None, Some(until)) if now < until => {
+                        KeyHealth::BackedOff {
+                            remaining: until - now,
+                        }
+                    },
+                    | _ => KeyHealth::Healthy,
+                },
+                requests: key.requests,
+                rate_limit_errors: key.rate_limit_errors,
+                auth_errors: key.auth_errors,
+            })
+            .collect()
+    }
+
+    /// Picks the next key in rotation.
+    fn pick(&self) -> Result<&ApiKey, CredentialError> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let len = state.keys.len();
+
+        let available = (0..len)
+            .map(|offset| (state.next + offset) % len)
+            .find(|index| state.keys[*index].is_available(now));
+        // Falls back to the backed off key whose window ends first.
+        let index = available
+            .or_else(|| {
+                state
+                    .keys
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, key)| key.removed.is_none())
+                    .min_by_key(|(_, key)| key.backed_off_until)
+                    .map(|(index, _)| index)
+            })
+            .ok_or(CredentialError::NoAvailableKey)?;
+
+        state.next = (index + 1) % len;
+        state.keys[index].requests += 1;
+
+        Ok(&self.keys[index])
+    }
+}
+
+impl CredentialProvider for KeyPool {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, CredentialError>> {
+        Box::pin(async { api_key_headers(self.pick()?.value()) })
+    }
+
+    fn report_error(
+        &self,
+        headers: &HeaderMap,
+        error: &ApiErrorType,
+        retry_after: Option<Duration>,
+    ) -> bool {
+        let Some(api_key) = headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok())
+        else {
+            return false;
+        };
+        let Some(index) = self
+            .keys
+            .iter()
+            .position(|key| key.value() == api_key)
+        else {
+            return false;
+        };
+
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let key = &mut state.keys[index];
+        match error {
+            | ApiErrorType::AuthenticationError
+            | ApiErrorType::PermissionError => {
+                key.removed = Some(error.clone());
+                key.auth_errors += 1;
+            },
+            | ApiErrorType::RateLimitError => {
+                key.backed_off_until =
+                    Some(now + retry_after.unwrap_or(self.backoff));
+                key.rate_limit_errors += 1;
+            },
+            | _ => return false,
+        }
+
+        state
+            .keys
+            .iter()
+            .any(|key| key.is_available(now))
+    }
+
+    fn max_failovers(&self) -> u32 {
+        // Every other key is tried once.
+        u32::try_from(self.keys.len())
+            .unwrap_or(u32::MAX)
+            .saturating_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> KeyPool {
+        KeyPool::new([
+            ApiKey::new("key-1"),
+            ApiKey::new("key-2"),
+            ApiKey::new("key-3"),
+        ])
+    }
+
+    async fn next_key(pool: &KeyPool) -> String {
+        pool.headers().await.unwrap()["x-api-key"]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn headers(api_key: &str) -> HeaderMap {
+        api_key_headers(api_key).unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_robin() {
+        let pool = pool();
+
+        let mut keys = Vec::new();
+        for _ in 0..4 {
+            keys.push(next_key(&pool).await);
+        }
+        assert_eq!(keys, ["key-1", "key-2", "key-3", "key-1"]);
+
+        let requests = pool
+            .status()
+            .iter()
+            .map(|status| status.requests)
+            .collect::<Vec<_>>();
+        assert_eq!(requests, [2, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn remove_on_auth_error() {
+        let pool = pool();
+
+        assert!(pool.report_error(
+            &headers("key-2"),
+            &ApiErrorType::AuthenticationError,
+            None,
+        ));
+        assert!(pool.report_error(
+            &headers("key-3"),
+            &ApiErrorType::PermissionError,
+            None,
+        ));
+        assert_eq!(next_key(&pool).await, "key-1");
+        assert_eq!(next_key(&pool).await, "key-1");
+
+        // No key to fail over to.
+        assert!(!pool.report_error(
+            &headers("key-1"),
+            &ApiErrorType::AuthenticationError,
+            None,
+        ));
+        assert!(matches!(
+            pool.headers().await,
+            Err(CredentialError::NoAvailableKey)
+        ));
+
+        let status = pool.status();
+        assert_eq!(
+            status[2].health,
+            KeyHealth::Removed(ApiErrorType::PermissionError)
+        );
+        assert_eq!(status[2].auth_errors, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn back_off_on_rate_limit_error() {
+        let pool = pool();
+
+        assert!(pool.report_error(
+            &headers("key-1"),
+            &ApiErrorType::RateLimitError,
+            Some(Duration::from_secs(10)),
+        ));
+        assert_eq!(next_key(&pool).await, "key-2");
+        assert_eq!(next_key(&pool).await, "key-3");
+        assert_eq!(next_key(&pool).await, "key-2");
+        assert_eq!(
+            pool.status()[0].health,
+            KeyHealth::BackedOff {
+                remaining: Duration::from_secs(10)
+            }
+        );
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(next_key(&pool).await, "key-3");
+        assert_eq!(next_key(&pool).await, "key-1");
+        assert_eq!(pool.status()[0].health, KeyHealth::Healthy);
+        assert_eq!(pool.status()[0].rate_limit_errors, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn all_backed_off() {
+        let pool = pool().backoff(Duration::from_secs(30));
+
+        pool.report_error(
+            &headers("key-1"),
+            &ApiErrorType::RateLimitError,
+            Some(Duration::from_secs(20)),
+        );
+        pool.report_error(
+            &headers("key-2"),
+            &ApiErrorType::RateLimitError,
+            Some(Duration::from_secs(5)),
+        );
+        assert!(!pool.report_error(
+            &headers("key-3"),
+            &ApiErrorType::RateLimitError,
+            None,
+        ));
+
+        assert_eq!(next_key(&pool).await, "key-2");
+    }
+
+    #[test]
+    fn ignore_other_errors() {
+        let pool = pool();
+
+        assert!(!pool.report_error(
+            &headers("key-1"),
+            &ApiErrorType::OverloadedError,
+            None,
+        ));
+        assert!(!pool.report_error(
+            &headers("unknown-key"),
+            &ApiErrorType::AuthenticationError,
+            None,
+        ));
+        assert!(pool
+            .status()
+            .iter()
+            .all(|status| status.health == KeyHealth::Healthy));
+    }
+}
@@ -5,8 +5,10 @@
 //! - Error of the API server -> [`crate::ApiError`]
 //! - A unique error for the API -> Each API error.
 
+use crate::credentials::CredentialError;
 use crate::macros::impl_display_for_serialize;
-use reqwest::StatusCode;
+use crate::transport::TransportError;
+use http::StatusCode;
 use std::fmt::Display;
 
 /// The error of the validation.
@@ -42,12 +44,21 @@ where
 /// The error of the client API calling.
 #[derive(Debug, thiserror::Error)]
 pub enum ClientError {
+    /// Failed to serialize the request body of an API calling.
+    #[error("Failed to serialize request as JSON: {0:?}")]
+    RequestSerializationFailed(serde_json::Error),
+    /// Invalid header value of an API calling.
+    #[error("Invalid header value: {0:?}")]
+    InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
+    /// Failed to get the credentials of an API calling.
+    #[error("Failed to get credentials: {0:?}")]
+    CredentialError(#[from] CredentialError),
     /// HTTP request error of an API calling.
     #[error("HTTP request error: {0:?}")]
-    HttpRequestError(reqwest::Error),
+    HttpRequestError(TransportError),
     /// Reading response text failed of an API calling.
     #[error("Reading response text failed: {0:?}")]
-    ReadResponseTextFailed(reqwest::Error),
+    ReadResponseTextFailed(TransportError),
     /// Failed to deserialize response of an API calling.
     #[error("Failed to deserialize response as JSON: {error:?}, {text:?}")]
     ResponseDeserializationFailed {
@@ -73,6 +84,8 @@ pub struct ApiError {
     pub _type: ApiErrorType,
     /// The response body of the error.
     pub response: ApiErrorResponse,
+    /// The request ID from the `request-id` header to reference the request in support tickets.
+    pub request_id: Option<String>,
 }
 
 impl Display for ApiError {
@@ -84,7 +97,13 @@ impl Display for ApiError {
             f,
             "API error: ({}) {}: {}",
             self.status, self._type, self.response,
-        )
+        )?;
+
+        if let Some(request_id) = &self.request_id {
+            write!(f, ", request ID: {request_id}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -93,12 +112,14 @@ impl ApiError {
     pub(crate) fn new(
         status: StatusCode,
         response: ApiErrorResponse,
+        request_id: Option<String>,
     ) -> Self {
         let _type = ApiErrorType::from(status);
         Self {
             status,
             _type,
             response,
+            request_id,
         }
     }
 }
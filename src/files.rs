@@ -0,0 +1,22 @@
+//! The [Files API](https://docs.anthropic.com/en/api/files-create) implementations.
+//!
+//! Uploaded files can be referenced by the file ID as an image or document source
+//! in the Messages API instead of sending the content with every request.
+
+mod deleted_file;
+mod error;
+mod file_metadata;
+mod file_upload;
+mod list_files_parameters;
+mod list_files_response_body;
+
+pub(crate) mod api;
+
+pub use deleted_file::DeletedFile;
+pub use deleted_file::DeletedFileObjectType;
+pub use error::FilesError;
+pub use file_metadata::FileMetadata;
+pub use file_metadata::FileObjectType;
+pub use file_upload::FileUpload;
+pub use list_files_parameters::{ListFiles, ListFilesParameters};
+pub use list_files_response_body::ListFilesResponseBody;
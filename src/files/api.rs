@@ -0,0 +1,316 @@
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::Method;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::files::file_upload::multipart_boundary;
+use crate::files::{
+    DeletedFile, FileMetadata, FileUpload, FilesError, ListFilesParameters,
+    ListFilesResponseBody,
+};
+use crate::instrument::CallSpan;
+use crate::request::{
+    call_json, encode_path_segment, send_request, with_query, with_timeout,
+    CallError,
+};
+use crate::{Beta, Client, ClientError, RequestOptions};
+
+/// The path of the Files API.
+const FILES_PATH: &str = "/v1/files";
+
+/// The beta features required by the Files API.
+const FILES_BETAS: &[Beta] = &[Beta::FilesApi2025_04_14];
+
+pub(crate) async fn upload_a_file(
+    client: &Client,
+    upload: &FileUpload,
+    options: &RequestOptions,
+) -> Result<FileMetadata, FilesError> {
+    let boundary = multipart_boundary();
+    let body = upload.multipart(&boundary);
+    let content_type = HeaderValue::from_str(&format!(
+        "multipart/form-data; boundary={boundary}"
+    ))
+    .map_err(ClientError::from)?;
+
+    call_json(
+        client,
+        "files.upload",
+        FILES_PATH,
+        |endpoint| {
+            let mut request =
+                client.request(Method::POST, endpoint, FILES_BETAS)?;
+            request
+                .headers
+                .insert(CONTENT_TYPE, content_type.clone());
+            request.body = body.clone();
+            Ok(request)
+        },
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn list_files(
+    client: &Client,
+    parameters: &ListFilesParameters,
+    options: &RequestOptions,
+) -> Result<ListFilesResponseBody, FilesError> {
+    let query = parameters.query();
+
+    call_json(
+        client,
+        "files.list",
+        FILES_PATH,
+        |endpoint| {
+            client.request(
+                Method::GET,
+                &with_query(endpoint, &query),
+                FILES_BETAS,
+            )
+        },
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn retrieve_file_metadata(
+    client: &Client,
+    file_id: &str,
+    options: &RequestOptions,
+) -> Result<FileMetadata, FilesError> {
+    call_json(
+        client,
+        "files.retrieve",
+        &format!(
+            "{FILES_PATH}/{}",
+            encode_path_segment(file_id),
+        ),
+        |endpoint| client.request(Method::GET, endpoint, FILES_BETAS),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn delete_a_file(
+    client: &Client,
+    file_id: &str,
+    options: &RequestOptions,
+) -> Result<DeletedFile, FilesError> {
+    call_json(
+        client,
+        "files.delete",
+        &format!(
+            "{FILES_PATH}/{}",
+            encode_path_segment(file_id),
+        ),
+        |endpoint| client.request(Method::DELETE, endpoint, FILES_BETAS),
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn download_a_file<W>(
+    client: &Client,
+    file_id: &str,
+    writer: &mut W,
+    options: &RequestOptions,
+) -> Result<u64, FilesError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let span = CallSpan::api("files.download");
+    let path = format!(
+        "{FILES_PATH}/{}/content",
+        encode_path_segment(file_id),
+    );
+    let call = with_timeout(options.timeout, async {
+        let mut response = send_request(
+            client,
+            &path,
+            |endpoint| client.request(Method::GET, endpoint, FILES_BETAS),
+            options,
+            &span,
+        )
+        .await?;
+
+        // Write the chunks of the body as they arrive.
+        let mut written = 0;
+        while let Some(chunk) = std::future::poll_fn(|cx| {
+            response
+                .body
+                .as_mut()
+                .poll_next(cx)
+        })
+        .await
+        {
+            let chunk = chunk.map_err(ClientError::ReadResponseTextFailed)?;
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(FilesError::WriteFailed)?;
+            written += chunk.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(FilesError::WriteFailed)?;
+
+        Ok(written)
+    });
+
+    match span.instrument(call).await {
+        | Ok(result) => result,
+        | Err(timeout) => Err(FilesError::timeout(timeout)),
+    }
+    .inspect_err(|error| {
+        span.record_error(error);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{StubResponse, StubServer};
+    use crate::{ApiKey, ClientBuilder};
+
+    const FILE_METADATA: &str = r#"{"id":"file_01","type":"file","filename":"notes.txt","mime_type":"text/plain","size_bytes":6,"created_at":"2025-04-14T00:00:00Z","downloadable":true}"#;
+
+    async fn client(responses: Vec<StubResponse>) -> (Client, StubServer) {
+        let server = StubServer::start(responses).await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn upload_a_file() {
+        let (client, server) =
+            client(vec![StubResponse::json(200, FILE_METADATA)]).await;
+
+        let file = super::upload_a_file(
+            &client,
+            &FileUpload::new("notes.txt", "Hello!"),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(file.id, "file_01");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].request_line, "POST /v1/files HTTP/1.1");
+        assert_eq!(
+            requests[0].header("anthropic-beta"),
+            Some("files-api-2025-04-14")
+        );
+        let content_type = requests[0]
+            .header("content-type")
+            .unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        assert!(requests[0]
+            .body_text()
+            .starts_with(&format!("--{boundary}\r\n")));
+        assert!(requests[0]
+            .body_text()
+            .contains("\r\n\r\nHello!\r\n"));
+    }
+
+    #[tokio::test]
+    async fn operations() {
+        let (client, server) = client(vec![
+            StubResponse::json(
+                200,
+                format!(
+                    r#"{{"data":[{FILE_METADATA}],"has_more":true,"first_id":"file_01","last_id":"file_01"}}"#
+                ),
+            ),
+            StubResponse::json(200, FILE_METADATA),
+            StubResponse::json(200, r#"{"id":"file_01","type":"file_deleted"}"#),
+        ])
+        .await;
+        let options = RequestOptions::default();
+
+        let list = super::list_files(
+            &client,
+            &ListFilesParameters::new().after_id("file_00"),
+            &options,
+        )
+        .await
+        .unwrap();
+        assert!(list.has_more);
+        let file = super::retrieve_file_metadata(&client, "file_01", &options)
+            .await
+            .unwrap();
+        assert!(file.downloadable);
+        let deleted = super::delete_a_file(&client, "file_01", &options)
+            .await
+            .unwrap();
+        assert_eq!(deleted.id, "file_01");
+
+        let request_lines = server
+            .requests()
+            .into_iter()
+            .map(|request| request.request_line)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            request_lines,
+            [
+                "GET /v1/files?after_id=file_00 HTTP/1.1",
+                "GET /v1/files/file_01 HTTP/1.1",
+                "DELETE /v1/files/file_01 HTTP/1.1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn download_a_file() {
+        let (client, server) = client(vec![StubResponse::Http {
+            status: 200,
+            headers: vec![(
+                "content-type".to_string(),
+                "text/plain".to_string(),
+            )],
+            body: "Hello!".to_string(),
+        }])
+        .await;
+
+        let mut content = Vec::new();
+        let written = super::download_a_file(
+            &client,
+            "file_01",
+            &mut content,
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(written, 6);
+        assert_eq!(content, b"Hello!");
+        assert_eq!(
+            server.requests()[0].request_line,
+            "GET /v1/files/file_01/content HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_api_error() {
+        let (client, _server) = client(vec![StubResponse::json(
+            404,
+            r#"{"type":"error","error":{"type":"not_found_error","message":"File not found"}}"#,
+        )])
+        .await;
+
+        let mut content = Vec::new();
+        let result = super::download_a_file(
+            &client,
+            "file_02",
+            &mut content,
+            &RequestOptions::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(FilesError::ApiError(_))));
+        assert!(content.is_empty());
+    }
+}
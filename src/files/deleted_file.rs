@@ -0,0 +1,54 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+
+/// The response body of deleting a file.
+///
+/// See also [Delete a File](https://docs.anthropic.com/en/api/files-delete).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct DeletedFile {
+    /// ID of the file.
+    pub id: String,
+    /// Deleted object type.
+    ///
+    /// For files, this is always "file_deleted".
+    #[serde(rename = "type")]
+    pub _type: DeletedFileObjectType,
+}
+
+impl_display_for_serialize!(DeletedFile);
+
+/// The object type for deleted file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletedFileObjectType {
+    /// file_deleted
+    FileDeleted,
+}
+
+impl Default for DeletedFileObjectType {
+    fn default() -> Self {
+        Self::FileDeleted
+    }
+}
+
+impl Display for DeletedFileObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | DeletedFileObjectType::FileDeleted => {
+                write!(f, "file_deleted")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    DeletedFileObjectType,
+    FileDeleted => "file_deleted"
+);
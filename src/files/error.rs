@@ -0,0 +1,38 @@
+use crate::request::impl_call_error;
+use crate::transport::TransportError;
+use crate::{ApiError, ClientError};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The error type for the Files API.
+#[derive(Debug, thiserror::Error)]
+pub enum FilesError {
+    /// The client error.
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+    /// The API error.
+    #[error(transparent)]
+    ApiError(#[from] ApiError),
+    /// Timed out connecting to the server.
+    #[error("Connect timed out: {0}")]
+    ConnectTimeout(TransportError),
+    /// Timed out until the response headers are received.
+    #[error("Response headers timed out after {0:?}")]
+    HeadersTimeout(Duration),
+    /// Timed out the whole call.
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
+    /// Failed to read the file to upload.
+    #[error("Failed to read the file {path:?}: {source}")]
+    ReadFileFailed {
+        /// The path of the file.
+        path: PathBuf,
+        /// The I/O error.
+        source: std::io::Error,
+    },
+    /// Failed to write the downloaded file content.
+    #[error("Failed to write the file content: {0}")]
+    WriteFailed(std::io::Error),
+}
+
+impl_call_error!(FilesError);
@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::{DocumentContentSource, ImageContentSource};
+
+/// The metadata of an uploaded file.
+///
+/// See also [Get File Metadata](https://docs.anthropic.com/en/api/files-metadata).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct FileMetadata {
+    /// Unique object identifier.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For files, this is always "file".
+    #[serde(rename = "type")]
+    pub _type: FileObjectType,
+    /// Original filename of the uploaded file.
+    pub filename: String,
+    /// MIME type of the file.
+    pub mime_type: String,
+    /// Size of the file in bytes.
+    pub size_bytes: u64,
+    /// RFC 3339 datetime string representing when the file was created.
+    pub created_at: String,
+    /// Whether the file can be downloaded.
+    #[serde(default)]
+    pub downloadable: bool,
+}
+
+impl_display_for_serialize!(FileMetadata);
+
+impl FileMetadata {
+    /// Creates the image content source referencing this file.
+    pub fn image_source(&self) -> ImageContentSource {
+        ImageContentSource::file(&self.id)
+    }
+
+    /// Creates the document content source referencing this file.
+    pub fn document_source(&self) -> DocumentContentSource {
+        DocumentContentSource::file(&self.id)
+    }
+}
+
+/// The object type for file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileObjectType {
+    /// file
+    File,
+}
+
+impl Default for FileObjectType {
+    fn default() -> Self {
+        Self::File
+    }
+}
+
+impl Display for FileObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | FileObjectType::File => {
+                write!(f, "file")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    FileObjectType,
+    File => "file"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let file: FileMetadata = serde_json::from_str(
+            r#"{"id":"file_01","type":"file","filename":"report.pdf","mime_type":"application/pdf","size_bytes":1024,"created_at":"2025-04-14T00:00:00Z","downloadable":false}"#,
+        )
+        .unwrap();
+        assert_eq!(file.filename, "report.pdf");
+        assert_eq!(file.size_bytes, 1024);
+        assert_eq!(
+            file.document_source(),
+            DocumentContentSource::file("file_01")
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::files::FilesError;
+
+/// A file to upload with the Files API.
+///
+/// ## Example
+/// ```
+/// use clust::files::FileUpload;
+///
+/// let upload = FileUpload::new("notes.txt", "Meeting notes")
+///     .mime_type("text/plain; charset=utf-8");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileUpload {
+    /// The filename sent to the API.
+    pub filename: String,
+    /// The MIME type of the file.
+    pub mime_type: String,
+    /// The content of the file.
+    pub content: Bytes,
+}
+
+impl FileUpload {
+    /// Creates a new file to upload from the bytes,
+    /// with the MIME type detected from the extension of the filename.
+    pub fn new<S, B>(
+        filename: S,
+        content: B,
+    ) -> Self
+    where
+        S: Into<String>,
+        B: Into<Bytes>,
+    {
+        let filename = filename.into();
+        let mime_type = mime_type_from_path(Path::new(&filename)).to_string();
+
+        Self {
+            filename,
+            mime_type,
+            content: content.into(),
+        }
+    }
+
+    /// Reads the file to upload from the path,
+    /// with the MIME type detected from the extension of the path.
+    pub async fn from_path<P>(path: P) -> Result<Self, FilesError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let content = tokio::fs::read(path)
+            .await
+            .map_err(|source| FilesError::ReadFileFailed {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Self::new(filename, content))
+    }
+
+    /// Sets the MIME type of the file.
+    pub fn mime_type<S>(
+        mut self,
+        mime_type: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.mime_type = mime_type.into();
+        self
+    }
+
+    /// Encodes the file as the `multipart/form-data` body with the boundary.
+    pub(crate) fn multipart(
+        &self,
+        boundary: &str,
+    ) -> Bytes {
+        let filename = self
+            .filename
+            .replace(['"', '\r', '\n'], "_");
+        let mut body = BytesMut::with_capacity(self.content.len() + 256);
+        body.put_slice(format!("--{boundary}\r\n").as_bytes());
+        body.put_slice(
+            format!(
+                "Content-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n"
+            )
+            .as_bytes(),
+        );
+        body.put_slice(
+            format!("Content-Type: {}\r\n\r\n", self.mime_type).as_bytes(),
+        );
+        body.put_slice(&self.content);
+        body.put_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+        body.freeze()
+    }
+}
+
+/// Creates a boundary of a `multipart/form-data` body unique in the process.
+pub(crate) fn multipart_boundary() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or_default();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("clust-boundary-{nanos:08x}{count:08x}")
+}
+
+/// Detects the MIME type from the extension of the path.
+fn mime_type_from_path(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        | Some("pdf") => "application/pdf",
+        | Some("txt") => "text/plain",
+        | Some("md") => "text/markdown",
+        | Some("csv") => "text/csv",
+        | Some("json") => "application/json",
+        | Some("jpeg") | Some("jpg") => "image/jpeg",
+        | Some("png") => "image/png",
+        | Some("gif") => "image/gif",
+        | Some("webp") => "image/webp",
+        | _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_mime_type() {
+        assert_eq!(
+            FileUpload::new("report.PDF", "").mime_type,
+            "application/pdf"
+        );
+        assert_eq!(
+            FileUpload::new("image.jpg", "").mime_type,
+            "image/jpeg"
+        );
+        assert_eq!(
+            FileUpload::new("data.bin", "").mime_type,
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn multipart() {
+        let upload = FileUpload::new("notes.txt", "Hello!");
+
+        assert_eq!(
+            upload.multipart("boundary"),
+            Bytes::from_static(
+                b"--boundary\r\n\
+                Content-Disposition: form-data; name=\"file\"; filename=\"notes.txt\"\r\n\
+                Content-Type: text/plain\r\n\r\n\
+                Hello!\r\n\
+                --boundary--\r\n"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn from_path() {
+        let upload =
+            FileUpload::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))
+                .await
+                .unwrap();
+        assert_eq!(upload.filename, "README.md");
+        assert_eq!(upload.mime_type, "text/markdown");
+
+        assert!(matches!(
+            FileUpload::from_path(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/not-found.pdf"
+            ))
+            .await,
+            Err(FilesError::ReadFileFailed { .. })
+        ));
+    }
+}
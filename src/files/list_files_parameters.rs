@@ -0,0 +1,43 @@
+use crate::files::{FileMetadata, FilesError};
+use crate::transport::BoxFuture;
+use crate::{Client, CursorParameters, Page, Paginate, RequestOptions};
+
+/// The query parameters to list files.
+///
+/// See also [List Files](https://docs.anthropic.com/en/api/files-list).
+///
+/// ## Example
+/// ```
+/// use clust::files::ListFilesParameters;
+///
+/// let parameters = ListFilesParameters::new()
+///     .after_id("file_011CNha8iCJcU1wXNR6q4V8w")
+///     .limit(10);
+/// ```
+pub type ListFilesParameters = CursorParameters<ListFiles>;
+
+/// The marker of the list files endpoint for [`ListFilesParameters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ListFiles;
+
+impl Paginate for ListFilesParameters {
+    type Item = FileMetadata;
+    type Error = FilesError;
+
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>> {
+        Box::pin(async move {
+            crate::files::api::list_files(client, &self, &options).await
+        })
+    }
+
+    fn next_page(
+        self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self> {
+        CursorParameters::next_page(self, page)
+    }
+}
@@ -0,0 +1,7 @@
+use crate::files::FileMetadata;
+use crate::Page;
+
+/// The response body to list files, the most recently created first.
+///
+/// See also [List Files](https://docs.anthropic.com/en/api/files-list).
+pub type ListFilesResponseBody = Page<FileMetadata>;
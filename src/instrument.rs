@@ -0,0 +1,390 @@
+//! Internal instrumentation by `tracing`, which does nothing without the `tracing` feature.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use http::StatusCode;
+
+use crate::messages::{
+    CountTokensRequestBody, MessageChunk, MessagesRequestBody, StreamError,
+    Usage,
+};
+
+/// The span of an API call.
+#[derive(Clone)]
+pub(crate) struct CallSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started_at: std::time::Instant,
+}
+
+impl CallSpan {
+    /// Creates a span of the Messages API call.
+    pub(crate) fn messages(request_body: &MessagesRequestBody) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::info_span!(
+                "clust.messages",
+                model = %request_body.model,
+                max_tokens = %request_body.max_tokens,
+                stream = request_body.stream
+                    == Some(crate::messages::StreamOption::ReturnStream),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+                input_tokens = tracing::field::Empty,
+                output_tokens = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+            span.in_scope(|| {
+                tracing::debug!(
+                    request_body = %redact::to_string(request_body),
+                    "request body"
+                );
+            });
+
+            Self {
+                span,
+                started_at: std::time::Instant::now(),
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = request_body;
+            Self {}
+        }
+    }
+
+    /// Creates a span of the count message tokens API call.
+    pub(crate) fn count_tokens(request_body: &CountTokensRequestBody) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::info_span!(
+                "clust.count_tokens",
+                model = %request_body.model,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+            span.in_scope(|| {
+                tracing::debug!(
+                    request_body = %redact::to_string(request_body),
+                    "request body"
+                );
+            });
+
+            Self {
+                span,
+                started_at: std::time::Instant::now(),
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = request_body;
+            Self {}
+        }
+    }
+
+    /// Creates a span of an API call other than the Messages API, e.g. `models.list`.
+    pub(crate) fn api(operation: &'static str) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::info_span!(
+                "clust.api",
+                operation,
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                request_id = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+
+            Self {
+                span,
+                started_at: std::time::Instant::now(),
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = operation;
+            Self {}
+        }
+    }
+
+    /// Runs the future in this span.
+    pub(crate) fn instrument<F>(
+        &self,
+        future: F,
+    ) -> impl Future<Output = F::Output>
+    where
+        F: Future,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Instrument::instrument(future, self.span.clone())
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            future
+        }
+    }
+
+    /// Records the status, the latency and the request ID of the response.
+    pub(crate) fn record_response(
+        &self,
+        status: StatusCode,
+        request_id: Option<&str>,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span
+                .record("status", status.as_u16());
+            self.span.record(
+                "latency_ms",
+                self.started_at
+                    .elapsed()
+                    .as_millis() as u64,
+            );
+            if let Some(request_id) = request_id {
+                self.span
+                    .record("request_id", request_id);
+            }
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (status, request_id);
+        }
+    }
+
+    /// Records a retry of the request.
+    pub(crate) fn record_retry(
+        &self,
+        attempt: u32,
+        delay: Duration,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.in_scope(|| {
+                tracing::warn!(
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "retrying request"
+                );
+            });
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (attempt, delay);
+        }
+    }
+
+    /// Records the usage of the response.
+    pub(crate) fn record_usage(
+        &self,
+        usage: &Usage,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span
+                .record("input_tokens", usage.input_tokens);
+            self.span
+                .record("output_tokens", usage.output_tokens);
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = usage;
+        }
+    }
+
+    /// Records the error of the call.
+    pub(crate) fn record_error<E>(
+        &self,
+        error: &E,
+    ) where
+        E: std::fmt::Display,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record(
+                "error",
+                tracing::field::display(error),
+            );
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = error;
+        }
+    }
+
+    /// Records a chunk of the stream.
+    pub(crate) fn record_chunk(
+        &self,
+        chunk: &MessageChunk,
+    ) {
+        #[cfg(feature = "tracing")]
+        {
+            match chunk {
+                | MessageChunk::MessageStart(message_start) => {
+                    self.record_usage(&message_start.message.usage);
+                },
+                | MessageChunk::MessageDelta(message_delta) => {
+                    self.span.record(
+                        "output_tokens",
+                        message_delta.usage.output_tokens,
+                    );
+                },
+                | _ => {},
+            }
+
+            self.span.in_scope(|| {
+                tracing::debug!(
+                    chunk = %redact::to_string(chunk),
+                    "message chunk"
+                );
+            });
+        }
+
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = chunk;
+        }
+    }
+}
+
+/// The stream of chunks recorded in the span.
+pub(crate) struct InstrumentedStream<S> {
+    stream: S,
+    span: CallSpan,
+}
+
+impl<S> InstrumentedStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    /// Creates a new stream recorded in the span.
+    pub(crate) fn new(
+        stream: S,
+        span: CallSpan,
+    ) -> Self {
+        Self {
+            stream,
+            span,
+        }
+    }
+}
+
+impl<S> Stream for InstrumentedStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    type Item = Result<MessageChunk, StreamError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.stream).poll_next(cx);
+
+        match &poll {
+            | Poll::Ready(Some(Ok(chunk))) => self.span.record_chunk(chunk),
+            | Poll::Ready(Some(Err(error))) => self.span.record_error(error),
+            | _ => {},
+        }
+
+        poll
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod redact {
+    use serde_json::Value;
+
+    /// The placeholder of redacted values.
+    const REDACTED: &str = "[REDACTED]";
+
+    /// Serializes the value into JSON with secrets and base64 data redacted.
+    pub(super) fn to_string<T>(value: &T) -> String
+    where
+        T: serde::Serialize,
+    {
+        match serde_json::to_value(value) {
+            | Ok(mut value) => {
+                redact(&mut value);
+                value.to_string()
+            },
+            | Err(_) => REDACTED.to_string(),
+        }
+    }
+
+    fn redact(value: &mut Value) {
+        match value {
+            | Value::Object(map) => {
+                let is_base64 = map
+                    .get("type")
+                    .and_then(Value::as_str)
+                    == Some("base64");
+
+                for (key, value) in map.iter_mut() {
+                    let key = key.to_ascii_lowercase();
+                    if (is_base64 && key == "data")
+                        || key.contains("api_key")
+                        || key.contains("api-key")
+                    {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        redact(value);
+                    }
+                }
+            },
+            | Value::Array(values) => {
+                values
+                    .iter_mut()
+                    .for_each(redact);
+            },
+            | _ => {},
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn redact_base64_and_api_key() {
+            let value = serde_json::json!({
+                "messages": [{
+                    "content": [
+                        {"type": "text", "text": "Hello"},
+                        {
+                            "type": "image",
+                            "source": {
+                                "type": "base64",
+                                "media_type": "image/png",
+                                "data": "iVBORw0KGgo="
+                            }
+                        }
+                    ]
+                }],
+                "x-api-key": "sk-ant-secret"
+            });
+
+            let redacted = to_string(&value);
+            assert!(!redacted.contains("iVBORw0KGgo="));
+            assert!(!redacted.contains("sk-ant-secret"));
+            assert!(redacted.contains("Hello"));
+            assert!(redacted.contains("image/png"));
+        }
+    }
+}
@@ -1,13 +1,48 @@
 //! An unofficial Rust client for [the Anthropic/Claude API](https://docs.anthropic.com/claude/reference/getting-started-with-the-api).
 //!
 //! ## Supported APIs
+//! - [Admin](`crate::admin`)
+//!     - [x] [Users](https://docs.anthropic.com/en/api/admin-api/users/list-users)
+//!     - [x] [Invites](https://docs.anthropic.com/en/api/admin-api/invites/list-invites)
+//!     - [x] [Workspaces](https://docs.anthropic.com/en/api/admin-api/workspaces/list-workspaces)
+//!     - [x] [Workspace Members](https://docs.anthropic.com/en/api/admin-api/workspace_members/list-workspace-members)
+//!     - [x] [API Keys](https://docs.anthropic.com/en/api/admin-api/apikeys/list-api-keys)
+//!     - [x] [Usage Report](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-messages-usage-report)
+//!     - [x] [Cost Report](https://docs.anthropic.com/en/api/admin-api/usage-cost/get-cost-report)
 //! - [Messages](`crate::messages`)
 //!     - [x] [Create a Message](https://docs.anthropic.com/claude/reference/messages_post)
 //!     - [x] [Streaming Messages](https://docs.anthropic.com/claude/reference/messages-streaming)
+//!     - [x] [Count Message tokens](https://docs.anthropic.com/en/api/messages-count-tokens)
+//! - [Message Batches](`crate::batches`)
+//!     - [x] [Create a Message Batch](https://docs.anthropic.com/en/api/creating-message-batches)
+//!     - [x] [Retrieve a Message Batch](https://docs.anthropic.com/en/api/retrieving-message-batches)
+//!     - [x] [Retrieve Message Batch Results](https://docs.anthropic.com/en/api/retrieving-message-batch-results)
+//!     - [x] [List Message Batches](https://docs.anthropic.com/en/api/listing-message-batches)
+//!     - [x] [Cancel a Message Batch](https://docs.anthropic.com/en/api/canceling-message-batches)
+//!     - [x] [Delete a Message Batch](https://docs.anthropic.com/en/api/deleting-message-batches)
+//! - [Files](`crate::files`)
+//!     - [x] [Upload File](https://docs.anthropic.com/en/api/files-create)
+//!     - [x] [List Files](https://docs.anthropic.com/en/api/files-list)
+//!     - [x] [Get File Metadata](https://docs.anthropic.com/en/api/files-metadata)
+//!     - [x] [Download a File](https://docs.anthropic.com/en/api/files-content)
+//!     - [x] [Delete a File](https://docs.anthropic.com/en/api/files-delete)
+//! - [Text Completions (legacy)](`crate::completions`)
+//!     - [x] [Create a Text Completion](https://docs.anthropic.com/en/api/complete)
+//!     - [x] [Streaming Text Completions](https://docs.anthropic.com/en/api/streaming)
+//! - [Models](`crate::models`)
+//!     - [x] [List Models](https://docs.anthropic.com/en/api/models-list)
+//!     - [x] [Get a Model](https://docs.anthropic.com/en/api/models)
 //!
 //! ## Feature flags
 //! - `macros`: Enable the [`attributes::clust_tool`] attribute macro for generating [`messages::ToolDefinition`]
 //!   or [`messages::AsyncTool`] from a Rust function.
+//! - `tracing`: Enable [tracing](https://docs.rs/tracing) spans for each API call with the model, max tokens, stream flag,
+//!   status, latency, request ID and usage, and debug events for each request body and message chunk
+//!   with API keys and base64 image data redacted.
+//! - `blocking`: Enable the [`blocking::Client`] for synchronous code with an iterator of message chunks
+//!   and tool use dispatch by [`messages::ToolList`].
+//! - `bedrock`: Enable the [Amazon Bedrock](`bedrock`) provider by [`ClientBuilder::bedrock`]
+//!   with AWS Signature Version 4 signing and decoding of the event stream.
 //!
 //! ## Usages
 //!
@@ -37,6 +72,7 @@
 //!
 //! let client = ClientBuilder::new(ApiKey::new("your-api-key"))
 //!     .version(Version::V2023_06_01)
+//!     .base_url("https://api.anthropic.com")
 //!     .client(reqwest::ClientBuilder::new().timeout(std::time::Duration::from_secs(10)).build().unwrap())
 //!     .build();
 //! ```
@@ -470,14 +506,37 @@ mod api_key;
 mod beta;
 mod client;
 mod error;
+mod instrument;
+mod middleware;
+mod page;
+mod rate_limiter;
+mod request;
+mod request_options;
+mod response_meta;
+mod retry;
 mod version;
 
+#[cfg(test)]
+mod test_server;
+
 pub(crate) mod macros;
 
+pub mod admin;
+pub mod batches;
+pub mod completions;
+pub mod credentials;
+pub mod files;
 pub mod messages;
+pub mod models;
+pub mod transport;
+pub mod vertex;
 
 #[cfg(feature = "macros")]
 pub mod attributes;
+#[cfg(feature = "bedrock")]
+pub mod bedrock;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 
 pub use api_key::ApiKey;
 pub use beta::Beta;
@@ -489,9 +548,22 @@ pub use error::ApiErrorResponse;
 pub use error::ApiErrorType;
 pub use error::ClientError;
 pub use error::ValidationError;
+pub use middleware::Middleware;
+pub use middleware::MiddlewareAction;
+pub use page::CursorParameters;
+pub use page::Page;
+pub use page::PageStream;
+pub use page::Paginate;
+pub use rate_limiter::RateLimiter;
+pub use request_options::RequestOptions;
+pub use response_meta::RateLimit;
+pub use response_meta::RateLimitInfo;
+pub use response_meta::ResponseMeta;
+pub use retry::RetryPolicy;
 pub use version::Version;
 
 pub use futures_core;
+pub use http;
 pub use reqwest;
 pub use serde_json;
 
@@ -1,12 +1,16 @@
 //! The [Messages API](https://docs.anthropic.com/claude/reference/messages_post) implementations.
 
 mod cache_control;
-mod chunk_stream;
+pub(crate) mod chunk_stream;
+mod citation;
 mod claude_model;
 mod content;
+mod count_tokens_request_body;
+mod count_tokens_response_body;
 mod error;
 mod max_tokens;
 mod message;
+mod message_accumulator;
 mod message_chunk;
 mod messages_request_body;
 mod messages_response_body;
@@ -25,10 +29,20 @@ pub(crate) mod api;
 mod tool;
 
 pub use cache_control::{CacheControl, CacheControlType, CacheTtl};
+pub use citation::CharLocationCitation;
+pub use citation::Citation;
+pub use citation::ContentBlockLocationCitation;
+pub use citation::PageLocationCitation;
+pub use citation::SearchResultLocationCitation;
+pub use citation::WebSearchResultLocationCitation;
 pub use claude_model::ClaudeModel;
+pub use content::CitationsConfig;
 pub use content::Content;
 pub use content::ContentBlock;
 pub use content::ContentType;
+pub use content::DocumentContentBlock;
+pub use content::DocumentContentSource;
+pub use content::DocumentMediaType;
 pub use content::ImageContentBlock;
 pub use content::ImageContentSource;
 pub use content::ImageMediaType;
@@ -37,7 +51,11 @@ pub use content::TextContentBlock;
 pub use content::ThinkingContentBlock;
 pub use content::ToolResultContentBlock;
 pub use content::ToolUseContentBlock;
+pub use count_tokens_request_body::CountTokensRequestBody;
+pub use count_tokens_response_body::CountTokensResponseBody;
 pub use error::ContentFlatteningError;
+pub use error::DocumentLoadError;
+pub use error::DocumentMediaTypeParseError;
 pub use error::ImageMediaTypeParseError;
 pub use error::MessageChunkTypeError;
 pub use error::MessagesError;
@@ -45,6 +63,8 @@ pub use error::StreamError;
 pub use error::ToolCallError;
 pub use max_tokens::MaxTokens;
 pub use message::Message;
+pub use message_accumulator::MessageAccumulator;
+pub use message_chunk::CitationsDeltaContentBlock;
 pub use message_chunk::ContentBlockDelta;
 pub use message_chunk::ContentBlockDeltaChunk;
 pub use message_chunk::ContentBlockStart;
@@ -1,24 +1,41 @@
-use crate::ApiError;
 use crate::Client;
 use crate::ClientError;
+use crate::RateLimiter;
+use crate::RequestOptions;
 use crate::Beta;
+use crate::ResponseMeta;
+use crate::instrument::{CallSpan, InstrumentedStream};
 use crate::messages::chunk_stream::ChunkStream;
+use crate::middleware::{self, MiddlewareStream};
+use crate::rate_limiter::{RateLimitPermit, RateLimitedStream};
+use crate::request::{read_json, send_request, with_timeout};
 use crate::messages::{
-    MessageChunk, MessagesError, MessagesRequestBody, MessagesResponseBody,
-    StreamError, StreamOption, CacheTtl,
+    CountTokensRequestBody, CountTokensResponseBody, MessageChunk,
+    MessagesError, MessagesRequestBody, MessagesResponseBody, StreamError,
+    StreamOption, CacheTtl, Content, ContentBlock, DocumentContentSource,
+    ImageContentSource, Message, SystemPrompt, Thinking, ToolDefinition,
 };
 
 use futures_core::Stream;
 
-/// Check if any content block in the request body uses 1-hour TTL
-fn has_one_hour_ttl(request_body: &MessagesRequestBody) -> bool {
+/// The path of the Messages API.
+const MESSAGES_PATH: &str = "/v1/messages";
+
+/// The path of the count message tokens API.
+const COUNT_TOKENS_PATH: &str = "/v1/messages/count_tokens";
+
+/// Check if any content block in the messages or system prompt uses 1-hour TTL
+fn has_one_hour_ttl(
+    messages: &[Message],
+    system: Option<&SystemPrompt>,
+) -> bool {
     // Check messages for content blocks with 1-hour TTL
-    for message in &request_body.messages {
+    for message in messages {
         match &message.content {
-            crate::messages::Content::SingleText(_) => {
+            Content::SingleText(_) => {
                 // Single text content doesn't have cache control
             },
-            crate::messages::Content::MultipleBlocks(blocks) => {
+            Content::MultipleBlocks(blocks) => {
                 for content_block in blocks {
                     if let Some(cache_control) = &content_block.cache_control() {
                         if let Some(ttl) = &cache_control.ttl {
@@ -33,12 +50,12 @@ fn has_one_hour_ttl(request_body: &MessagesRequestBody) -> bool {
     }
     
     // Check system prompt for content blocks with 1-hour TTL
-    if let Some(system_prompt) = &request_body.system {
+    if let Some(system_prompt) = system {
         match system_prompt {
-            crate::messages::SystemPrompt::Simple(_) => {
+            SystemPrompt::Simple(_) => {
                 // Simple system prompt doesn't have cache control
             },
-            crate::messages::SystemPrompt::Advanced(blocks) => {
+            SystemPrompt::Advanced(blocks) => {
                 for content_block in blocks {
                     if let Some(cache_control) = &content_block.cache_control() {
                         if let Some(ttl) = &cache_control.ttl {
@@ -55,11 +72,67 @@ fn has_one_hour_ttl(request_body: &MessagesRequestBody) -> bool {
     false
 }
 
+/// Check if extended thinking is enabled with tools, which interleaves thinking between tool calls
+fn has_thinking_with_tools(
+    tools: Option<&[ToolDefinition]>,
+    thinking: Option<&Thinking>,
+) -> bool {
+    let thinking_enabled =
+        thinking.is_some_and(|thinking| thinking.r#type == "enabled");
+    let has_tools = tools.is_some_and(|tools| !tools.is_empty());
+
+    thinking_enabled && has_tools
+}
+
+/// Check if any content block references a file uploaded by the Files API
+fn has_file_source(messages: &[Message]) -> bool {
+    messages
+        .iter()
+        .filter_map(|message| match &message.content {
+            | Content::SingleText(_) => None,
+            | Content::MultipleBlocks(blocks) => Some(blocks),
+        })
+        .flatten()
+        .any(|content_block| match content_block {
+            | ContentBlock::Image(image) => {
+                matches!(image.source, ImageContentSource::File { .. })
+            },
+            | ContentBlock::Document(document) => {
+                matches!(document.source, DocumentContentSource::File { .. })
+            },
+            | _ => false,
+        })
+}
+
+/// Detects the beta features required by the parts of a request body.
+fn required_betas(
+    messages: &[Message],
+    system: Option<&SystemPrompt>,
+    tools: Option<&[ToolDefinition]>,
+    thinking: Option<&Thinking>,
+) -> Vec<Beta> {
+    let mut betas = Vec::new();
+
+    if has_one_hour_ttl(messages, system) {
+        betas.push(Beta::ExtendedCacheTtl2025_04_11);
+    }
+
+    if has_thinking_with_tools(tools, thinking) {
+        betas.push(Beta::InterleavedThinking2025_05_14);
+    }
+
+    if has_file_source(messages) {
+        betas.push(Beta::FilesApi2025_04_14);
+    }
+
+    betas
+}
+
 pub(crate) async fn create_a_message(
     client: &Client,
-    request_body: MessagesRequestBody,
-    endpoint: &str,
-) -> Result<MessagesResponseBody, MessagesError> {
+    mut request_body: MessagesRequestBody,
+    options: &RequestOptions,
+) -> Result<(MessagesResponseBody, ResponseMeta), MessagesError> {
     // Validate stream option.
     if let Some(stream) = &request_body.stream {
         if *stream != StreamOption::ReturnOnce {
@@ -67,63 +140,85 @@ pub(crate) async fn create_a_message(
         }
     }
 
-    // Check if we need to add the extended cache beta header
-    let mut request_builder = client.post(endpoint);
-    
-    if has_one_hour_ttl(&request_body) {
-        request_builder = request_builder.header("anthropic-beta", Beta::ExtendedCacheTtl2025_04_11.to_string());
+    // Run the middlewares, which may respond without sending the request.
+    if let Some((mut response_body, middlewares)) =
+        middleware::run_request_body(client.middlewares(), &mut request_body)
+    {
+        let meta = ResponseMeta::default();
+        middleware::run_response(&middlewares, &mut response_body, &meta);
+        return Ok((response_body, meta));
     }
 
-    // Send the request.
-    let response = request_builder
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(ClientError::HttpRequestError)?;
+    let span = CallSpan::messages(&request_body);
+    let call = with_timeout(options.timeout, async {
+            // Wait for the rate limiter.
+            let permit = acquire_permit(client, &request_body).await;
 
-    // Check the response status code.
-    let status_code = response.status();
+            // Send the request.
+            let betas = required_betas(
+                &request_body.messages,
+                request_body.system.as_ref(),
+                request_body.tools.as_deref(),
+                request_body.thinking.as_ref(),
+            );
+            let response = send_request(
+                client,
+                MESSAGES_PATH,
+                |endpoint| client.post(endpoint, &request_body, &betas),
+                options,
+                &span,
+            )
+            .await?;
+            let meta = ResponseMeta::from_headers(&response.headers);
 
-    // Read the response text.
-    let response_text = response
-        .text()
-        .await
-        .map_err(ClientError::ReadResponseTextFailed)?;
+            // Read the response text.
+            let response_text = response
+                .text()
+                .await
+                .map_err(ClientError::ReadResponseTextFailed)?;
 
-    // Ok
-    if status_code.is_success() {
-        // Deserialize the response.
-        serde_json::from_str(&response_text).map_err(|error| {
-            {
-                ClientError::ResponseDeserializationFailed {
-                    error,
-                    text: response_text,
-                }
+            // Deserialize the response.
+            let response_body: MessagesResponseBody =
+                serde_json::from_str(&response_text).map_err(|error| {
+                    ClientError::ResponseDeserializationFailed {
+                        error,
+                        text: response_text,
+                    }
+                })?;
+
+            if let Some(permit) = permit {
+                permit.reconcile(&response_body.usage);
             }
-            .into()
-        })
-    }
-    // Error
-    else {
-        // Deserialize the error response.
-        let error_response =
-            serde_json::from_str(&response_text).map_err(|error| {
-                ClientError::ErrorResponseDeserializationFailed {
-                    error,
-                    text: response_text,
-                }
-            })?;
 
-        Err(ApiError::new(status_code, error_response).into())
-    }
+            Ok((response_body, meta))
+        });
+    let result: Result<(MessagesResponseBody, ResponseMeta), MessagesError> =
+        match span.instrument(call).await {
+            | Ok(result) => result,
+            | Err(timeout) => Err(MessagesError::Timeout(timeout)),
+        };
+
+    let (mut response_body, meta) = result.inspect_err(|error| {
+        span.record_error(error);
+    })?;
+    span.record_usage(&response_body.usage);
+
+    middleware::run_response(client.middlewares(), &mut response_body, &meta);
+
+    Ok((response_body, meta))
 }
 
 pub(crate) async fn create_a_message_stream(
     client: &Client,
-    request_body: MessagesRequestBody,
-    endpoint: &str,
-) -> Result<impl Stream<Item = Result<MessageChunk, StreamError>>, MessagesError>
-{
+    mut request_body: MessagesRequestBody,
+    options: &RequestOptions,
+) -> Result<
+    (
+        impl Stream<Item = Result<MessageChunk, StreamError>>,
+        ResponseMeta,
+    ),
+    MessagesError,
+> {
     // Validate stream option.
     if request_body.stream.is_none() {
         return Err(MessagesError::StreamOptionMismatch);
@@ -134,55 +229,128 @@ pub(crate) async fn create_a_message_stream(
         }
     }
 
-    eprintln!("endpoint: {}", endpoint);
-    // Check if we need to add the extended cache beta header
-    let mut request_builder = client.post(endpoint);
-    
-    if has_one_hour_ttl(&request_body) {
-        request_builder = request_builder.header("anthropic-beta", Beta::ExtendedCacheTtl2025_04_11.to_string());
+    // Run the middlewares, which may respond without sending the request.
+    if let Some((response_body, middlewares)) =
+        middleware::run_request_body(client.middlewares(), &mut request_body)
+    {
+        return Ok((
+            MiddlewareStream::synthetic(response_body, middlewares),
+            ResponseMeta::default(),
+        ));
     }
 
-    // Send the request.
-    let response = request_builder
-        .json(&request_body)
-        .send()
+    // Send the request, retries only happen before the first chunk.
+    let span = CallSpan::messages(&request_body);
+    let call = with_timeout(options.timeout, async {
+        // Wait for the rate limiter.
+        let permit = acquire_permit(client, &request_body).await;
+
+        let betas = required_betas(
+            &request_body.messages,
+            request_body.system.as_ref(),
+            request_body.tools.as_deref(),
+            request_body.thinking.as_ref(),
+        );
+        send_request(
+            client,
+            MESSAGES_PATH,
+            |endpoint| client.post(endpoint, &request_body, &betas),
+            options,
+            &span,
+        )
         .await
-        .map_err(ClientError::HttpRequestError)?;
+        .map_err(MessagesError::from)
+            .map(|response| (response, permit))
+    });
+    let (response, permit) = match span.instrument(call).await {
+        | Ok(result) => result,
+        | Err(timeout) => Err(MessagesError::Timeout(timeout)),
+    }
+    .inspect_err(|error| {
+        span.record_error(error);
+    })?;
+    let meta = ResponseMeta::from_headers(&response.headers);
 
-    // Check the response status code.
-    let status_code = response.status();
+    // Create a chunk stream from response bytes stream.
+    let chunk_stream = RateLimitedStream::new(
+        InstrumentedStream::new(
+            ChunkStream::new(response.body).with_timeouts(
+                options.first_chunk_timeout,
+                options.idle_timeout,
+            ),
+            span,
+        ),
+        permit,
+    );
+    Ok((
+        MiddlewareStream::new(chunk_stream, client.middlewares().to_vec()),
+        meta,
+    ))
+}
 
-    // Ok
-    if status_code.is_success() {
-        // Create a chunk stream from response bytes stream.
-        let byte_stream = response.bytes_stream();
-        let chunk_stream = ChunkStream::new(byte_stream);
-        Ok(chunk_stream)
+pub(crate) async fn count_tokens(
+    client: &Client,
+    request_body: CountTokensRequestBody,
+    options: &RequestOptions,
+) -> Result<(CountTokensResponseBody, ResponseMeta), MessagesError> {
+    let span = CallSpan::count_tokens(&request_body);
+    let call = with_timeout(options.timeout, async {
+        // Send the request.
+        let betas = required_betas(
+            &request_body.messages,
+            request_body.system.as_ref(),
+            request_body.tools.as_deref(),
+            request_body.thinking.as_ref(),
+        );
+        let response = send_request(
+            client,
+            COUNT_TOKENS_PATH,
+            |endpoint| client.post(endpoint, &request_body, &betas),
+            options,
+            &span,
+        )
+        .await?;
+        let meta = ResponseMeta::from_headers(&response.headers);
+        let response_body: CountTokensResponseBody =
+            read_json(response).await?;
+
+        Ok((response_body, meta))
+    });
+
+    match span.instrument(call).await {
+        | Ok(result) => result,
+        | Err(timeout) => Err(MessagesError::Timeout(timeout)),
     }
-    // Error
-    else {
-        // Read the response text.
-        let response_text = response
-            .text()
-            .await
-            .map_err(ClientError::ReadResponseTextFailed)?;
-
-        // Deserialize the error response.
-        let error_response =
-            serde_json::from_str(&response_text).map_err(|error| {
-                ClientError::ErrorResponseDeserializationFailed {
-                    error,
-                    text: response_text,
-                }
-            })?;
+    .inspect_err(|error| {
+        span.record_error(error);
+    })
+}
 
-        Err(ApiError::new(status_code, error_response).into())
+/// Waits for the permit of the rate limiter of the client if configured.
+async fn acquire_permit(
+    client: &Client,
+    request_body: &MessagesRequestBody,
+) -> Option<RateLimitPermit> {
+    match client.rate_limiter() {
+        | Some(rate_limiter) => Some(
+            rate_limiter
+                .acquire(RateLimiter::estimate_tokens(request_body))
+                .await,
+        ),
+        | None => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transport::TransportResponse;
+    use crate::RetryPolicy;
+    use http::StatusCode;
+    use std::time::Duration;
+    use crate::test_server::{StubResponse, StubServer};
+    use http::header::HeaderValue;
+    use crate::ApiErrorType;
     use crate::messages::{
         CacheControl, CacheControlType, ClaudeModel, ContentBlock, MaxTokens, Message,
         MessagesRequestBody, Role, SystemPrompt, TextContentBlock,
@@ -197,7 +365,10 @@ mod tests {
             messages: vec![Message::user("Hello")],
             ..Default::default()
         };
-        assert!(!has_one_hour_ttl(&request_body));
+        assert!(!has_one_hour_ttl(
+            &request_body.messages,
+            request_body.system.as_ref()
+        ));
 
         // Test with 1-hour TTL in message content
         let message = Message {
@@ -218,7 +389,10 @@ mod tests {
             messages: vec![message],
             ..Default::default()
         };
-        assert!(has_one_hour_ttl(&request_body));
+        assert!(has_one_hour_ttl(
+            &request_body.messages,
+            request_body.system.as_ref()
+        ));
 
         // Test with 1-hour TTL in system prompt
         let system_prompt = SystemPrompt::from_text_blocks_with_cache_control(vec![
@@ -238,6 +412,1045 @@ mod tests {
             system: Some(system_prompt),
             ..Default::default()
         };
-        assert!(has_one_hour_ttl(&request_body));
+        assert!(has_one_hour_ttl(
+            &request_body.messages,
+            request_body.system.as_ref()
+        ));
+    }
+
+    fn betas_of(request_body: &MessagesRequestBody) -> Vec<Beta> {
+        required_betas(
+            &request_body.messages,
+            request_body.system.as_ref(),
+            request_body.tools.as_deref(),
+            request_body.thinking.as_ref(),
+        )
+    }
+
+    #[test]
+    fn test_required_betas() {
+        let request_body = MessagesRequestBody::default();
+        assert!(betas_of(&request_body).is_empty());
+
+        let request_body = MessagesRequestBody {
+            messages: vec![Message {
+                role: Role::User,
+                content: crate::messages::Content::MultipleBlocks(vec![
+                    ContentBlock::Text(TextContentBlock::new_with_cache_control(
+                        "Hello",
+                        CacheControl {
+                            _type: CacheControlType::Ephemeral,
+                            ttl: Some(CacheTtl::OneHour),
+                        },
+                    )),
+                ]),
+            }],
+            thinking: Some(crate::messages::Thinking {
+                r#type: "enabled".to_string(),
+                budget_tokens: 1024,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            betas_of(&request_body),
+            vec![Beta::ExtendedCacheTtl2025_04_11]
+        );
+
+        let request_body = MessagesRequestBody {
+            tools: Some(vec![crate::messages::ToolDefinition::new(
+                "get_weather",
+                Some("Get the weather"),
+                serde_json::json!({"type": "object"}),
+            )]),
+            ..request_body
+        };
+        assert_eq!(
+            betas_of(&request_body),
+            vec![
+                Beta::ExtendedCacheTtl2025_04_11,
+                Beta::InterleavedThinking2025_05_14,
+            ]
+        );
+
+        let request_body = MessagesRequestBody {
+            messages: vec![Message::user(
+                crate::messages::Content::MultipleBlocks(vec![
+                    ContentBlock::from(
+                        crate::messages::DocumentContentSource::file("file_01"),
+                    ),
+                    ContentBlock::from("Summarize the document."),
+                ]),
+            )],
+            ..Default::default()
+        };
+        assert_eq!(
+            betas_of(&request_body),
+            vec![Beta::FilesApi2025_04_14]
+        );
+    }
+
+    const MESSAGE_RESPONSE: &str = r#"{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"text","text":"Hello!"}],"model":"claude-3-sonnet-20240229","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}"#;
+
+    const OVERLOADED_RESPONSE: &str = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+
+    const INVALID_REQUEST_RESPONSE: &str = r#"{"type":"error","error":{"type":"invalid_request_error","message":"Invalid"}}"#;
+
+    fn retrying_client() -> Client {
+        crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .retry_policy(
+                RetryPolicy::new()
+                    .max_attempts(3)
+                    .initial_backoff(std::time::Duration::ZERO)
+                    .jitter(false),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn retry_on_overloaded() {
+        let server = StubServer::start(vec![
+            StubResponse::json(529, OVERLOADED_RESPONSE),
+            StubResponse::json(200, MESSAGE_RESPONSE),
+        ])
+        .await;
+
+        let (response, _) = create_a_message(
+            &retrying_client(),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.id, "msg_01");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].request_line,
+            "POST /v1/messages HTTP/1.1"
+        );
+        assert_eq!(
+            requests[1].header("x-api-key"),
+            Some("api-key")
+        );
+        assert_eq!(
+            requests[0].body_text(),
+            requests[1].body_text()
+        );
+    }
+
+    #[tokio::test]
+    async fn key_pool_failover() {
+        let server = StubServer::start(vec![
+            StubResponse::json(
+                401,
+                r#"{"type":"error","error":{"type":"authentication_error","message":"Invalid API key"}}"#,
+            ),
+            StubResponse::json(200, MESSAGE_RESPONSE),
+        ])
+        .await;
+        let pool = std::sync::Arc::new(crate::credentials::KeyPool::new([
+            crate::ApiKey::new("revoked-key"),
+            crate::ApiKey::new("api-key"),
+        ]));
+        let client = crate::ClientBuilder::with_credentials(pool.clone()).build();
+
+        let (response, _) = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.id, "msg_01");
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].header("x-api-key"),
+            Some("revoked-key")
+        );
+        assert_eq!(
+            requests[1].header("x-api-key"),
+            Some("api-key")
+        );
+        assert_eq!(
+            pool.status()[0].health,
+            crate::credentials::KeyHealth::Removed(
+                crate::ApiErrorType::AuthenticationError
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn key_pool_failover_is_bounded() {
+        // The keys are available again immediately after every rate limit error.
+        let server = StubServer::start(vec![StubResponse::json(
+            429,
+            r#"{"type":"error","error":{"type":"rate_limit_error","message":"Rate limited"}}"#,
+        )
+        .header("retry-after", "0")])
+        .await;
+        let pool = std::sync::Arc::new(crate::credentials::KeyPool::new([
+            crate::ApiKey::new("key-1"),
+            crate::ApiKey::new("key-2"),
+        ]));
+        let client = crate::ClientBuilder::with_credentials(pool).build();
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            create_a_message(
+                &client,
+                MessagesRequestBody::default(),
+                &RequestOptions::new().endpoint(server.url("/v1/messages")),
+            ),
+        )
+        .await
+        .expect("the failover should end");
+        assert!(result.is_err());
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].header("x-api-key"),
+            Some("key-1")
+        );
+        assert_eq!(
+            requests[1].header("x-api-key"),
+            Some("key-2")
+        );
+    }
+
+    #[tokio::test]
+    async fn count_tokens() {
+        let server = StubServer::start(vec![StubResponse::json(
+            200,
+            r#"{"input_tokens":14}"#,
+        )])
+        .await;
+        let client = Client::from_api_key(crate::ApiKey::new("api-key"));
+
+        let (response, _) = super::count_tokens(
+            &client,
+            MessagesRequestBody {
+                messages: vec![crate::messages::Message::user("Hello!")],
+                temperature: Some(
+                    crate::messages::Temperature::new(0.5).unwrap(),
+                ),
+                ..Default::default()
+            }
+            .into(),
+            &RequestOptions::new()
+                .endpoint(server.url("/v1/messages/count_tokens")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.input_tokens, 14);
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "POST /v1/messages/count_tokens HTTP/1.1"
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[0].body).unwrap();
+        assert!(body.get("max_tokens").is_none());
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["messages"][0]["content"], "Hello!");
+    }
+
+    #[tokio::test]
+    async fn count_tokens_with_betas() {
+        let server = StubServer::start(vec![StubResponse::json(
+            200,
+            r#"{"input_tokens":1024}"#,
+        )])
+        .await;
+        let client = Client::from_api_key(crate::ApiKey::new("api-key"));
+
+        super::count_tokens(
+            &client,
+            CountTokensRequestBody {
+                messages: vec![Message::user(
+                    crate::messages::Content::MultipleBlocks(vec![
+                        ContentBlock::from(
+                            crate::messages::DocumentContentSource::file(
+                                "file_01",
+                            ),
+                        ),
+                        ContentBlock::from("Summarize the document."),
+                    ]),
+                )],
+                ..Default::default()
+            },
+            &RequestOptions::new()
+                .endpoint(server.url("/v1/messages/count_tokens")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            server.requests()[0].header("anthropic-beta"),
+            Some("files-api-2025-04-14")
+        );
+    }
+
+    #[tokio::test]
+    async fn count_tokens_api_error() {
+        let server = StubServer::start(vec![StubResponse::json(
+            400,
+            INVALID_REQUEST_RESPONSE,
+        )])
+        .await;
+        let client = Client::from_api_key(crate::ApiKey::new("api-key"));
+
+        let error = super::count_tokens(
+            &client,
+            CountTokensRequestBody::default(),
+            &RequestOptions::new()
+                .endpoint(server.url("/v1/messages/count_tokens")),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            MessagesError::ApiError(error)
+                if error._type == crate::ApiErrorType::InvalidRequestError
+        ));
+    }
+
+    #[tokio::test]
+    async fn retry_honors_retry_after() {
+        let server = StubServer::start(vec![
+            StubResponse::json(429, OVERLOADED_RESPONSE)
+                .header("retry-after-ms", "10"),
+            StubResponse::json(200, MESSAGE_RESPONSE),
+        ])
+        .await;
+
+        let started = std::time::Instant::now();
+        create_a_message(
+            &retrying_client(),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap();
+
+        assert!(started.elapsed() >= std::time::Duration::from_millis(10));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn no_retry_on_disconnect() {
+        let server = StubServer::start(vec![
+            StubResponse::Disconnect,
+            StubResponse::json(200, MESSAGE_RESPONSE),
+        ])
+        .await;
+
+        // The server may have accepted the request before disconnecting.
+        let error = create_a_message(
+            &retrying_client(),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            MessagesError::ClientError(ClientError::HttpRequestError(_))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retry_exhausted() {
+        let server = StubServer::start(vec![StubResponse::json(
+            500,
+            OVERLOADED_RESPONSE,
+        )])
+        .await;
+
+        let error = create_a_message(
+            &retrying_client(),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap_err();
+
+        match error {
+            | MessagesError::ApiError(error) => {
+                assert_eq!(error._type, ApiErrorType::ApiError);
+            },
+            | _ => panic!("unexpected error: {error:?}"),
+        }
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn no_retry_on_invalid_request() {
+        let server = StubServer::start(vec![StubResponse::json(
+            400,
+            INVALID_REQUEST_RESPONSE,
+        )])
+        .await;
+
+        let error = create_a_message(
+            &retrying_client(),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap_err();
+
+        match error {
+            | MessagesError::ApiError(error) => {
+                assert_eq!(
+                    error._type,
+                    ApiErrorType::InvalidRequestError
+                );
+            },
+            | _ => panic!("unexpected error: {error:?}"),
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn response_meta() {
+        let server = StubServer::start(vec![StubResponse::json(
+            200,
+            MESSAGE_RESPONSE,
+        )
+        .header("request-id", "req_01")
+        .header("anthropic-ratelimit-requests-limit", "50")
+        .header(
+            "anthropic-ratelimit-requests-remaining",
+            "49",
+        )
+        .header(
+            "anthropic-ratelimit-output-tokens-reset",
+            "2024-06-30T00:00:00Z",
+        )])
+        .await;
+
+        let (response, meta) = create_a_message(
+            &Client::from_api_key(crate::ApiKey::new("api-key")),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.id, "msg_01");
+        assert_eq!(meta.request_id, Some("req_01".to_string()));
+        assert_eq!(
+            meta.rate_limit.requests.limit,
+            Some(50)
+        );
+        assert_eq!(
+            meta.rate_limit.requests.remaining,
+            Some(49)
+        );
+        assert_eq!(
+            meta.rate_limit
+                .output_tokens
+                .reset,
+            Some("2024-06-30T00:00:00Z".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn api_error_has_request_id() {
+        let server = StubServer::start(vec![StubResponse::json(
+            400,
+            INVALID_REQUEST_RESPONSE,
+        )
+        .header("request-id", "req_02")])
+        .await;
+
+        let error = create_a_message(
+            &Client::from_api_key(crate::ApiKey::new("api-key")),
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap_err();
+
+        match error {
+            | MessagesError::ApiError(error) => {
+                assert_eq!(
+                    error.request_id,
+                    Some("req_02".to_string())
+                );
+                assert!(error
+                    .to_string()
+                    .ends_with("request ID: req_02"));
+            },
+            | _ => panic!("unexpected error: {error:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn no_retry_by_default() {
+        let server = StubServer::start(vec![
+            StubResponse::json(529, OVERLOADED_RESPONSE),
+            StubResponse::json(200, MESSAGE_RESPONSE),
+        ])
+        .await;
+
+        let client = Client::from_api_key(crate::ApiKey::new("api-key"));
+        let result = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stream_retries_before_first_chunk() {
+        use futures_util::StreamExt;
+
+        let server = StubServer::start(vec![
+            StubResponse::json(529, OVERLOADED_RESPONSE),
+            StubResponse::event_stream(
+                "event: ping\ndata: {\"type\": \"ping\"}\n\n",
+            ),
+        ])
+        .await;
+
+        let request_body = MessagesRequestBody {
+            stream: Some(StreamOption::ReturnStream),
+            ..Default::default()
+        };
+        let (stream, _) = create_a_message_stream(
+            &retrying_client(),
+            request_body,
+            &RequestOptions::new().endpoint(server.url("/v1/messages")),
+        )
+        .await
+        .unwrap();
+
+        let chunks = stream.collect::<Vec<_>>().await;
+        assert_eq!(chunks.len(), 1);
+        assert!(matches!(
+            chunks[0],
+            Ok(MessageChunk::Ping(_))
+        ));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    /// A transport that records requests and responds without the network.
+    struct InMemoryTransport {
+        requests: std::sync::Mutex<Vec<crate::transport::TransportRequest>>,
+    }
+
+    impl crate::transport::Transport for InMemoryTransport {
+        fn send(
+            &self,
+            request: crate::transport::TransportRequest,
+        ) -> crate::transport::BoxFuture<
+            '_,
+            Result<TransportResponse, crate::transport::TransportError>,
+        > {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request);
+
+            Box::pin(async {
+                let mut headers = http::HeaderMap::new();
+                headers.insert("request-id", HeaderValue::from_static("req_01"));
+
+                Ok(TransportResponse::from_bytes(
+                    StatusCode::OK,
+                    headers,
+                    MESSAGE_RESPONSE,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_transport() {
+        let transport = std::sync::Arc::new(InMemoryTransport {
+            requests: std::sync::Mutex::new(Vec::new()),
+        });
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .base_url("https://example.com")
+            .transport(transport.clone())
+            .build();
+
+        let (response, meta) = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.id, "msg_01");
+        assert_eq!(meta.request_id, Some("req_01".to_string()));
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, http::Method::POST);
+        assert_eq!(
+            requests[0].url,
+            "https://example.com/v1/messages"
+        );
+        assert_eq!(
+            requests[0]
+                .headers
+                .get("x-api-key")
+                .unwrap(),
+            "api-key"
+        );
+        assert_eq!(
+            requests[0]
+                .headers
+                .get("content-type")
+                .unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            requests[0].body,
+            serde_json::to_vec(&MessagesRequestBody::default()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn base_url_routing() {
+        let server =
+            StubServer::start(vec![StubResponse::json(200, MESSAGE_RESPONSE)])
+                .await;
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .base_url(server.url("/anthropic/"))
+            .build();
+
+        create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            server.requests()[0].request_line,
+            "POST /anthropic/v1/messages HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn merged_beta_header() {
+        let server =
+            StubServer::start(vec![StubResponse::json(200, MESSAGE_RESPONSE)])
+                .await;
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .beta(Beta::Tools2024_04_04)
+            .beta(Beta::Other("custom-beta-2025-01-01".to_string()))
+            .beta(Beta::ExtendedCacheTtl2025_04_11)
+            .build();
+
+        let request_body = MessagesRequestBody {
+            system: Some(SystemPrompt::from_text_blocks_with_cache_control(
+                vec![(
+                    "Cached information",
+                    Some(CacheControl {
+                        _type: CacheControlType::Ephemeral,
+                        ttl: Some(CacheTtl::OneHour),
+                    }),
+                )],
+            )),
+            ..Default::default()
+        };
+
+        create_a_message(
+            &client,
+            request_body,
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(
+            request
+                .headers
+                .iter()
+                .filter(|(key, _)| key == "anthropic-beta")
+                .count(),
+            1
+        );
+        assert_eq!(
+            request.header("anthropic-beta"),
+            Some("tools-2024-04-04,custom-beta-2025-01-01,extended-cache-ttl-2025-04-11")
+        );
+    }
+
+    struct RewritingMiddleware;
+
+    impl crate::Middleware for RewritingMiddleware {
+        fn on_request_body(
+            &self,
+            request_body: &mut MessagesRequestBody,
+        ) -> crate::MiddlewareAction {
+            request_body.metadata = Some(crate::messages::Metadata {
+                user_id: crate::messages::UserId::new("forced-user"),
+            });
+            crate::MiddlewareAction::Continue
+        }
+
+        fn on_request(
+            &self,
+            request: &mut crate::transport::TransportRequest,
+        ) {
+            request
+                .headers
+                .insert("x-custom", HeaderValue::from_static("value"));
+        }
+
+        fn on_response(
+            &self,
+            response_body: &mut MessagesResponseBody,
+            meta: &ResponseMeta,
+        ) {
+            response_body.id = format!(
+                "{}:{}",
+                response_body.id,
+                meta.request_id
+                    .clone()
+                    .unwrap_or_default()
+            );
+        }
+
+        fn on_chunk(
+            &self,
+            chunk: &mut MessageChunk,
+        ) {
+            *chunk = MessageChunk::Ping(Default::default());
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_hooks() {
+        use futures_util::StreamExt;
+
+        let server = StubServer::start(vec![
+            StubResponse::json(200, MESSAGE_RESPONSE).header("request-id", "req_01"),
+            StubResponse::event_stream(
+                "event: message_stop\ndata: {\"type\": \"message_stop\"}\n\n",
+            ),
+        ])
+        .await;
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .middleware(RewritingMiddleware)
+            .build();
+
+        let (response, _) = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.id, "msg_01:req_01");
+
+        let request_body = MessagesRequestBody {
+            stream: Some(StreamOption::ReturnStream),
+            ..Default::default()
+        };
+        let (stream, _) = create_a_message_stream(
+            &client,
+            request_body,
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        let chunks = stream.collect::<Vec<_>>().await;
+        assert!(matches!(
+            chunks[..],
+            [Ok(MessageChunk::Ping(_))]
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            assert_eq!(request.header("x-custom"), Some("value"));
+            assert!(request
+                .body_text()
+                .contains(r#""user_id":"forced-user""#));
+        }
+    }
+
+    struct RespondingMiddleware;
+
+    impl crate::Middleware for RespondingMiddleware {
+        fn on_request_body(
+            &self,
+            _request_body: &mut MessagesRequestBody,
+        ) -> crate::MiddlewareAction {
+            crate::MiddlewareAction::Respond(MessagesResponseBody {
+                id: "synthetic".to_string(),
+                content: "cached".into(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_short_circuit() {
+        use futures_util::StreamExt;
+
+        let server =
+            StubServer::start(vec![StubResponse::json(200, MESSAGE_RESPONSE)])
+                .await;
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .middleware(RespondingMiddleware)
+            .build();
+
+        let (response, meta) = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.id, "synthetic");
+        assert_eq!(meta, ResponseMeta::default());
+
+        let request_body = MessagesRequestBody {
+            stream: Some(StreamOption::ReturnStream),
+            ..Default::default()
+        };
+        let (stream, _) = create_a_message_stream(
+            &client,
+            request_body,
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        let chunks = stream
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(matches!(
+            chunks.first(),
+            Some(MessageChunk::MessageStart(_))
+        ));
+        assert!(matches!(
+            chunks.last(),
+            Some(MessageChunk::MessageStop(_))
+        ));
+
+        assert!(server.requests().is_empty());
+    }
+
+    /// The transport responding headers after the delay and never finishing the body.
+    struct SlowTransport {
+        delay: Duration,
+        attempts: std::sync::atomic::AtomicU32,
+    }
+
+    impl crate::transport::Transport for SlowTransport {
+        fn send(
+            &self,
+            _request: crate::transport::TransportRequest,
+        ) -> crate::transport::BoxFuture<
+            '_,
+            Result<TransportResponse, crate::transport::TransportError>,
+        > {
+            self.attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+            Box::pin(async {
+                tokio::time::sleep(self.delay).await;
+
+                Ok(TransportResponse::new(
+                    StatusCode::OK,
+                    http::HeaderMap::new(),
+                    Box::pin(futures_util::stream::pending()),
+                ))
+            })
+        }
+    }
+
+    fn slow_client(delay: Duration) -> (Client, std::sync::Arc<SlowTransport>) {
+        let transport = std::sync::Arc::new(SlowTransport {
+            delay,
+            attempts: std::sync::atomic::AtomicU32::new(0),
+        });
+
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .transport(transport.clone())
+            .retry_policy(
+                RetryPolicy::new()
+                    .max_attempts(2)
+                    .initial_backoff(Duration::ZERO)
+                    .jitter(false),
+            )
+            .build();
+
+        (client, transport)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn total_timeout() {
+        let (client, transport) = slow_client(Duration::from_secs(1));
+
+        let error = create_a_message(
+            &client,
+            MessagesRequestBody::default(),
+            &RequestOptions::new()
+                .headers_timeout(Duration::from_secs(10))
+                .timeout(Duration::from_secs(30)),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            MessagesError::Timeout(timeout) if timeout == Duration::from_secs(30)
+        ));
+        assert_eq!(
+            transport
+                .attempts
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stream_first_chunk_timeout() {
+        use futures_util::StreamExt;
+
+        let (client, _) = slow_client(Duration::from_secs(1));
+
+        let request_body = MessagesRequestBody {
+            stream: Some(StreamOption::ReturnStream),
+            ..Default::default()
+        };
+        let (mut stream, _) = create_a_message_stream(
+            &client,
+            request_body,
+            &RequestOptions::new()
+                .timeout(Duration::from_secs(30))
+                .first_chunk_timeout(Duration::from_secs(60)),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(StreamError::FirstChunkTimeout(_)))
+        ));
+    }
+
+    /// The transport responding the message with no remaining requests.
+    struct ExhaustedTransport;
+
+    impl crate::transport::Transport for ExhaustedTransport {
+        fn send(
+            &self,
+            _request: crate::transport::TransportRequest,
+        ) -> crate::transport::BoxFuture<
+            '_,
+            Result<TransportResponse, crate::transport::TransportError>,
+        > {
+            Box::pin(async {
+                let mut headers = http::HeaderMap::new();
+                headers.insert(
+                    "anthropic-ratelimit-requests-remaining",
+                    HeaderValue::from_static("0"),
+                );
+
+                Ok(TransportResponse::from_bytes(
+                    StatusCode::OK,
+                    headers,
+                    MESSAGE_RESPONSE,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_observes_headers() {
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .transport(ExhaustedTransport)
+            .rate_limiter(RateLimiter::new().requests_per_minute(60))
+            .build();
+
+        let started_at = tokio::time::Instant::now();
+        for _ in 0..2 {
+            create_a_message(
+                &client,
+                MessagesRequestBody::default(),
+                &RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        }
+
+        // The second request waits for a request refilled at 1 request per second.
+        assert_eq!(started_at.elapsed(), Duration::from_secs(1));
+    }
+
+    /// The transport responding an invalid request error.
+    struct InvalidRequestTransport;
+
+    impl crate::transport::Transport for InvalidRequestTransport {
+        fn send(
+            &self,
+            _request: crate::transport::TransportRequest,
+        ) -> crate::transport::BoxFuture<
+            '_,
+            Result<TransportResponse, crate::transport::TransportError>,
+        > {
+            Box::pin(async {
+                Ok(TransportResponse::from_bytes(
+                    StatusCode::BAD_REQUEST,
+                    http::HeaderMap::new(),
+                    INVALID_REQUEST_RESPONSE,
+                ))
+            })
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_gives_back_tokens_on_error() {
+        let client = crate::ClientBuilder::new(crate::ApiKey::new("api-key"))
+            .transport(InvalidRequestTransport)
+            .rate_limiter(RateLimiter::new().tokens_per_minute(5_000))
+            .build();
+
+        let started_at = tokio::time::Instant::now();
+        for _ in 0..2 {
+            create_a_message(
+                &client,
+                MessagesRequestBody::default(),
+                &RequestOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        }
+
+        // The estimated tokens of the failed request are available again.
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
     }
 }
@@ -1,41 +1,87 @@
+use std::future::Future;
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use bytes::{Buf, BytesMut};
 use futures_core::Stream;
 use pin_project::pin_project;
+use tokio::time::{Instant, Sleep};
 
 use crate::messages::{MessageChunk, StreamError};
+use crate::transport::TransportError;
 
-/// The stream of message chunks with `tokio` backend.
+/// A chunk of server-sent events parsed from an `event` line and a `data` line.
+pub(crate) trait ParseChunk: Sized {
+    /// Parses the chunk from the lines of an event.
+    fn parse(source: &str) -> Result<Self, StreamError>;
+}
+
+impl ParseChunk for MessageChunk {
+    fn parse(source: &str) -> Result<Self, StreamError> {
+        MessageChunk::parse(source)
+    }
+}
+
+/// The stream of chunks from the response body of a transport, message chunks by default.
 #[pin_project]
-pub(crate) struct ChunkStream<S>
+pub(crate) struct ChunkStream<S, C = MessageChunk>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, TransportError>> + Unpin,
 {
     #[pin]
     stream: S,
     buffer: BytesMut,
+    /// The timeout waiting for the first data.
+    first_chunk_timeout: Option<Duration>,
+    /// The timeout waiting for the next data.
+    idle_timeout: Option<Duration>,
+    /// Whether any data has been received.
+    received: bool,
+    /// The timer of the current timeout, started when the stream is pending.
+    timer: Option<Pin<Box<Sleep>>>,
+    /// Whether the stream has been terminated by a timeout.
+    terminated: bool,
+    _chunk: PhantomData<fn() -> C>,
 }
 
-impl<S> ChunkStream<S>
+impl<S, C> ChunkStream<S, C>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, TransportError>> + Unpin,
 {
     /// Create a new chunk stream.
     pub fn new(stream: S) -> Self {
         ChunkStream {
             stream,
             buffer: BytesMut::new(),
+            first_chunk_timeout: None,
+            idle_timeout: None,
+            received: false,
+            timer: None,
+            terminated: false,
+            _chunk: PhantomData,
         }
     }
+
+    /// Sets the timeouts waiting for the first data and the next data.
+    pub fn with_timeouts(
+        mut self,
+        first_chunk_timeout: Option<Duration>,
+        idle_timeout: Option<Duration>,
+    ) -> Self {
+        self.first_chunk_timeout = first_chunk_timeout;
+        self.idle_timeout = idle_timeout;
+        self
+    }
 }
 
-impl<S> Stream for ChunkStream<S>
+impl<S, C> Stream for ChunkStream<S, C>
 where
-    S: Stream<Item = Result<bytes::Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<bytes::Bytes, TransportError>> + Unpin,
+    C: ParseChunk,
 {
-    type Item = Result<MessageChunk, StreamError>;
+    type Item = Result<C, StreamError>;
 
     fn poll_next(
         self: Pin<&mut Self>,
@@ -43,6 +89,10 @@ where
     ) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
 
+        if *this.terminated {
+            return Poll::Ready(None);
+        }
+
         loop {
             if let Some(position) = this
                 .buffer
@@ -70,7 +120,7 @@ where
                         let chunk = String::from_utf8(chunk)
                             .map_err(StreamError::StringDecodingError)?;
 
-                        let chunk = MessageChunk::parse(&chunk)?;
+                        let chunk = C::parse(&chunk)?;
                         return Poll::Ready(Some(Ok(chunk)));
                     }
                 }
@@ -84,13 +134,16 @@ where
                 // The stream has more data.
                 | Poll::Ready(Some(Ok(chunk))) => {
                     this.buffer.extend(&chunk);
+                    // Restart the timer on the next pending.
+                    *this.received = true;
+                    *this.timer = None;
                     // Continue to the next iteration of the loop.
                 },
                 // The stream has an error.
                 | Poll::Ready(Some(Err(error))) => {
-                    return Poll::Ready(Some(Err(StreamError::ReqwestError(
-                        error,
-                    ))));
+                    return Poll::Ready(Some(Err(
+                        StreamError::TransportError(error),
+                    )));
                 },
                 // The stream has no more data.
                 | Poll::Ready(None) => {
@@ -101,12 +154,41 @@ where
                         let remaining =
                             String::from_utf8(remaining.to_vec())
                                 .map_err(StreamError::StringDecodingError)?;
-                        let chunk = MessageChunk::parse(&remaining)?;
+                        let chunk = C::parse(&remaining)?;
                         Poll::Ready(Some(Ok(chunk)))
                     };
                 },
                 // The stream has no more data for now.
-                | Poll::Pending => return Poll::Pending,
+                | Poll::Pending => {
+                    let (timeout, error): (_, fn(Duration) -> StreamError) =
+                        if *this.received {
+                            (*this.idle_timeout, StreamError::IdleTimeout)
+                        } else {
+                            (
+                                *this.first_chunk_timeout,
+                                StreamError::FirstChunkTimeout,
+                            )
+                        };
+
+                    let Some(timeout) = timeout
+                    else {
+                        return Poll::Pending;
+                    };
+
+                    let timer = this.timer.get_or_insert_with(|| {
+                        Box::pin(tokio::time::sleep_until(
+                            Instant::now() + timeout,
+                        ))
+                    });
+
+                    return match timer.as_mut().poll(cx) {
+                        | Poll::Ready(()) => {
+                            *this.terminated = true;
+                            Poll::Ready(Some(Err(error(timeout))))
+                        },
+                        | Poll::Pending => Poll::Pending,
+                    };
+                },
             }
         }
     }
@@ -450,4 +532,49 @@ data: {"type": "message_delta", "delta": {"stop_reason": "end_turn", "stop_seque
                 .is_none()
         );
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn first_chunk_timeout() {
+        use futures_util::StreamExt;
+
+        let mut chunk_stream: ChunkStream<_> = ChunkStream::new(futures_util::stream::pending())
+            .with_timeouts(Some(Duration::from_secs(10)), None);
+
+        assert!(matches!(
+            chunk_stream.next().await,
+            Some(Err(StreamError::FirstChunkTimeout(timeout)))
+                if timeout == Duration::from_secs(10)
+        ));
+        assert!(chunk_stream.next().await.is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_timeout() {
+        use futures_util::StreamExt;
+
+        let source = r#"event: ping
+data: {"type": "ping"}
+
+"#;
+        let input_stream = futures_util::stream::iter(vec![Ok(
+            bytes::Bytes::from(source),
+        )])
+        .chain(futures_util::stream::pending());
+
+        let mut chunk_stream = ChunkStream::new(input_stream).with_timeouts(
+            Some(Duration::from_secs(10)),
+            Some(Duration::from_secs(5)),
+        );
+
+        assert!(matches!(
+            chunk_stream.next().await,
+            Some(Ok(MessageChunk::Ping(_)))
+        ));
+        assert!(matches!(
+            chunk_stream.next().await,
+            Some(Err(StreamError::IdleTimeout(timeout)))
+                if timeout == Duration::from_secs(5)
+        ));
+        assert!(chunk_stream.next().await.is_none());
+    }
 }
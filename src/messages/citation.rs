@@ -0,0 +1,207 @@
+use crate::macros::impl_display_for_serialize;
+
+/// The citation of a text content block to a source of the response.
+///
+/// Citations are returned when a document is sent with citations enabled, or by the search results and the web search tool.
+///
+/// ## Example
+/// ```
+/// use clust::messages::{CharLocationCitation, Citation};
+///
+/// let citation: Citation = serde_json::from_str(
+///     r#"{"type":"char_location","cited_text":"The grass is green.","document_index":0,"document_title":"My Document","start_char_index":0,"end_char_index":20}"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(citation.cited_text(), "The grass is green.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Citation {
+    /// The character range of a plain text document.
+    CharLocation(CharLocationCitation),
+    /// The page range of a PDF document.
+    PageLocation(PageLocationCitation),
+    /// The block range of a custom content document.
+    ContentBlockLocation(ContentBlockLocationCitation),
+    /// The block range of a search result.
+    SearchResultLocation(SearchResultLocationCitation),
+    /// The result of the web search tool.
+    WebSearchResultLocation(WebSearchResultLocationCitation),
+}
+
+impl_display_for_serialize!(Citation);
+
+impl Citation {
+    /// Gets the cited text.
+    pub fn cited_text(&self) -> &str {
+        match self {
+            | Citation::CharLocation(citation) => &citation.cited_text,
+            | Citation::PageLocation(citation) => &citation.cited_text,
+            | Citation::ContentBlockLocation(citation) => &citation.cited_text,
+            | Citation::SearchResultLocation(citation) => &citation.cited_text,
+            | Citation::WebSearchResultLocation(citation) => {
+                &citation.cited_text
+            },
+        }
+    }
+}
+
+/// The citation to the character range of a plain text document.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CharLocationCitation {
+    /// The cited text.
+    pub cited_text: String,
+    /// The index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The start index of the characters (inclusive), from 0.
+    pub start_char_index: u32,
+    /// The end index of the characters (exclusive).
+    pub end_char_index: u32,
+    /// The ID of the file when the document is uploaded by the Files API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+impl_display_for_serialize!(CharLocationCitation);
+
+/// The citation to the page range of a PDF document.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct PageLocationCitation {
+    /// The cited text.
+    pub cited_text: String,
+    /// The index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The start page number (inclusive), from 1.
+    pub start_page_number: u32,
+    /// The end page number (exclusive).
+    pub end_page_number: u32,
+    /// The ID of the file when the document is uploaded by the Files API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+impl_display_for_serialize!(PageLocationCitation);
+
+/// The citation to the block range of a custom content document.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ContentBlockLocationCitation {
+    /// The cited text.
+    pub cited_text: String,
+    /// The index of the document in the request.
+    pub document_index: u32,
+    /// The title of the document.
+    pub document_title: Option<String>,
+    /// The start index of the content blocks (inclusive), from 0.
+    pub start_block_index: u32,
+    /// The end index of the content blocks (exclusive).
+    pub end_block_index: u32,
+    /// The ID of the file when the document is uploaded by the Files API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+impl_display_for_serialize!(ContentBlockLocationCitation);
+
+/// The citation to the block range of a search result.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct SearchResultLocationCitation {
+    /// The cited text.
+    pub cited_text: String,
+    /// The source of the search result, e.g. a URL.
+    pub source: String,
+    /// The title of the search result.
+    pub title: Option<String>,
+    /// The index of the search result in the request.
+    pub search_result_index: u32,
+    /// The start index of the content blocks (inclusive), from 0.
+    pub start_block_index: u32,
+    /// The end index of the content blocks (exclusive).
+    pub end_block_index: u32,
+}
+
+impl_display_for_serialize!(SearchResultLocationCitation);
+
+/// The citation to a result of the web search tool.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct WebSearchResultLocationCitation {
+    /// The cited text, up to 150 characters.
+    pub cited_text: String,
+    /// The URL of the web page.
+    pub url: String,
+    /// The title of the web page.
+    pub title: Option<String>,
+    /// The encrypted index to pass back in multi-turn conversations.
+    pub encrypted_index: String,
+}
+
+impl_display_for_serialize!(WebSearchResultLocationCitation);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_citations() {
+        let citations: Vec<Citation> = serde_json::from_str(
+            r#"[
+                {"type":"char_location","cited_text":"a","document_index":0,"document_title":null,"start_char_index":0,"end_char_index":1},
+                {"type":"page_location","cited_text":"b","document_index":1,"document_title":"PDF","start_page_number":1,"end_page_number":2},
+                {"type":"content_block_location","cited_text":"c","document_index":2,"document_title":"Content","start_block_index":0,"end_block_index":1},
+                {"type":"search_result_location","cited_text":"d","source":"https://example.com","title":"Result","search_result_index":0,"start_block_index":0,"end_block_index":1},
+                {"type":"web_search_result_location","cited_text":"e","url":"https://example.com","title":"Page","encrypted_index":"Eo8BCioIAhgBIiQyYjQ0OWJmZi1lNm"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            citations[1],
+            Citation::PageLocation(PageLocationCitation {
+                cited_text: "b".to_string(),
+                document_index: 1,
+                document_title: Some("PDF".to_string()),
+                start_page_number: 1,
+                end_page_number: 2,
+                file_id: None,
+            })
+        );
+        assert_eq!(
+            citations
+                .iter()
+                .map(Citation::cited_text)
+                .collect::<Vec<_>>(),
+            ["a", "b", "c", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn serialize_citation() {
+        let citation = Citation::CharLocation(CharLocationCitation {
+            cited_text: "a".to_string(),
+            document_index: 0,
+            document_title: None,
+            start_char_index: 0,
+            end_char_index: 1,
+            file_id: None,
+        });
+
+        assert_eq!(
+            serde_json::to_string(&citation).unwrap(),
+            r#"{"type":"char_location","cited_text":"a","document_index":0,"document_title":null,"start_char_index":0,"end_char_index":1}"#
+        );
+    }
+}
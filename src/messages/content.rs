@@ -1,5 +1,7 @@
 use std::fmt::Display;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
 
 use crate::macros::{
     impl_display_for_serialize, impl_enum_string_serialization,
@@ -7,8 +9,8 @@ use crate::macros::{
     impl_enum_with_string_or_array_serialization,
 };
 use crate::messages::{
-    CacheControl, ContentFlatteningError, ImageMediaTypeParseError, ToolResult,
-    ToolUse,
+    CacheControl, Citation, ContentFlatteningError, DocumentLoadError,
+    DocumentMediaTypeParseError, ImageMediaTypeParseError, ToolResult, ToolUse,
 };
 
 /// The content of the message.
@@ -214,6 +216,8 @@ pub enum ContentBlock {
     Thinking(ThinkingContentBlock),
     /// The image content block.
     Image(ImageContentBlock),
+    /// The document content block.
+    Document(DocumentContentBlock),
     /// The tool use content block.
     ToolUse(ToolUseContentBlock),
     /// The tool result content block.
@@ -244,6 +248,12 @@ impl From<ImageContentSource> for ContentBlock {
     }
 }
 
+impl From<DocumentContentSource> for ContentBlock {
+    fn from(document: DocumentContentSource) -> Self {
+        Self::Document(DocumentContentBlock::new(document))
+    }
+}
+
 impl From<ToolUse> for ContentBlock {
     fn from(tool_use: ToolUse) -> Self {
         Self::ToolUse(tool_use.into())
@@ -262,6 +272,7 @@ impl_enum_struct_serialization!(
     Text(TextContentBlock, "text"),
     Thinking(ThinkingContentBlock, "thinking"),
     Image(ImageContentBlock, "image"),
+    Document(DocumentContentBlock, "document"),
     ToolUse(ToolUseContentBlock, "tool_use"),
     ToolResult(ToolResultContentBlock, "tool_result")
 );
@@ -275,6 +286,7 @@ impl ContentBlock {
             ContentBlock::Text(block) => block.cache_control.as_ref(),
             ContentBlock::Thinking(_) => None,
             ContentBlock::Image(_) => None,
+            ContentBlock::Document(block) => block.cache_control.as_ref(),
             ContentBlock::ToolUse(_) => None,
             ContentBlock::ToolResult(_) => None,
         }
@@ -289,6 +301,9 @@ pub struct TextContentBlock {
     pub _type: ContentType,
     /// The text content.
     pub text: String,
+    /// The citations supporting the text, returned when citations are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<Citation>>,
     /// Optional cache control for this content block.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_control: Option<CacheControl>,
@@ -299,6 +314,7 @@ impl Default for TextContentBlock {
         Self {
             _type: ContentType::Text,
             text: String::new(),
+            citations: None,
             cache_control: None,
         }
     }
@@ -327,6 +343,7 @@ impl TextContentBlock {
         Self {
             _type: ContentType::Text,
             text: text.into(),
+            citations: None,
             cache_control: None,
         }
     }
@@ -342,6 +359,7 @@ impl TextContentBlock {
         Self {
             _type: ContentType::Text,
             text: text.into(),
+            citations: None,
             cache_control: Some(cache_control),
         }
     }
@@ -446,12 +464,16 @@ pub enum ContentType {
     Thinking,
     /// image
     Image,
+    /// document
+    Document,
     /// text_delta
     TextDelta,
     /// thinking delta
     ThinkingDelta,
     /// signature delta
     SignatureDelta,
+    /// citations_delta
+    CitationsDelta,
     /// tool_use
     ToolUse,
     /// tool_result
@@ -478,6 +500,9 @@ impl Display for ContentType {
             | ContentType::Image => {
                 write!(f, "image")
             },
+            | ContentType::Document => {
+                write!(f, "document")
+            },
             | ContentType::TextDelta => {
                 write!(f, "text_delta")
             },
@@ -499,6 +524,9 @@ impl Display for ContentType {
             | ContentType::SignatureDelta => {
                 write!(f, "signature_delta")
             },
+            | ContentType::CitationsDelta => {
+                write!(f, "citations_delta")
+            },
         }
     }
 }
@@ -507,28 +535,48 @@ impl_enum_string_serialization!(
     ContentType,
     Text => "text",
     Image => "image",
+    Document => "document",
     TextDelta => "text_delta",
     ToolUse => "tool_use",
     ToolResult => "tool_result",
     InputJsonDelta => "input_json_delta",
     Thinking => "thinking",
     ThinkingDelta => "thinking_delta",
-    SignatureDelta => "signature_delta"
+    SignatureDelta => "signature_delta",
+    CitationsDelta => "citations_delta"
 );
 
 /// The image content source.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-#[derive(Default)]
-pub struct ImageContentSource {
-    /// The source type.
-    #[serde(rename = "type")]
-    pub _type: ImageSourceType,
-    /// The media type.
-    pub media_type: ImageMediaType,
-    ///  The data of the image.
-    pub data: String,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageContentSource {
+    /// The Base64 encoded image data.
+    Base64 {
+        /// The media type.
+        media_type: ImageMediaType,
+        ///  The data of the image.
+        data: String,
+    },
+    /// The image at the URL.
+    Url {
+        /// The URL of the image.
+        url: String,
+    },
+    /// The image uploaded by the Files API.
+    File {
+        /// The ID of the uploaded file.
+        file_id: String,
+    },
 }
 
+impl Default for ImageContentSource {
+    fn default() -> Self {
+        Self::Base64 {
+            media_type: ImageMediaType::default(),
+            data: String::new(),
+        }
+    }
+}
 
 impl_display_for_serialize!(ImageContentSource);
 
@@ -545,12 +593,46 @@ impl ImageContentSource {
     where
         S: Into<String>,
     {
-        Self {
-            _type: ImageSourceType::Base64,
+        Self::Base64 {
             media_type,
             data: data.into(),
         }
     }
+
+    /// Creates a new image content source referencing an image by the URL.
+    ///
+    /// ## Arguments
+    /// - `url` - The URL of the image.
+    pub fn url<S>(url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Url {
+            url: url.into(),
+        }
+    }
+
+    /// Creates a new image content source referencing a file uploaded by the Files API.
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the uploaded file.
+    pub fn file<S>(file_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::File {
+            file_id: file_id.into(),
+        }
+    }
+
+    /// Gets the source type.
+    pub fn source_type(&self) -> ImageSourceType {
+        match self {
+            | ImageContentSource::Base64 { .. } => ImageSourceType::Base64,
+            | ImageContentSource::Url { .. } => ImageSourceType::Url,
+            | ImageContentSource::File { .. } => ImageSourceType::File,
+        }
+    }
 }
 
 /// The source type of the image.
@@ -558,6 +640,10 @@ impl ImageContentSource {
 pub enum ImageSourceType {
     /// base64
     Base64,
+    /// url
+    Url,
+    /// file
+    File,
 }
 
 impl Default for ImageSourceType {
@@ -575,13 +661,21 @@ impl Display for ImageSourceType {
             | ImageSourceType::Base64 => {
                 write!(f, "base64")
             },
+            | ImageSourceType::Url => {
+                write!(f, "url")
+            },
+            | ImageSourceType::File => {
+                write!(f, "file")
+            },
         }
     }
 }
 
 impl_enum_string_serialization!(
     ImageSourceType,
-    Base64 => "base64"
+    Base64 => "base64",
+    Url => "url",
+    File => "file"
 );
 
 /// The media type of the image.
@@ -652,6 +746,317 @@ impl ImageMediaType {
     }
 }
 
+/// The document content block, e.g. a PDF or a plain text document.
+///
+/// ## Example
+/// ```
+/// use clust::messages::{CacheControl, ContentBlock, DocumentContentBlock, DocumentContentSource};
+///
+/// let block = ContentBlock::Document(
+///     DocumentContentBlock::new(DocumentContentSource::text("The grass is green."))
+///         .title("My Document")
+///         .context("This is a trustworthy document.")
+///         .citations(true)
+///         .cache_control(CacheControl::default()),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentContentBlock {
+    /// The content type. It is always `document`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The document content source.
+    pub source: DocumentContentSource,
+    /// Optional title of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Optional context about the document, which is not cited from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Optional citations configuration of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<CitationsConfig>,
+    /// Optional cache control for this content block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl_display_for_serialize!(DocumentContentBlock);
+
+impl From<DocumentContentSource> for DocumentContentBlock {
+    fn from(source: DocumentContentSource) -> Self {
+        Self::new(source)
+    }
+}
+
+impl DocumentContentBlock {
+    /// Creates a new document content block.
+    pub fn new(source: DocumentContentSource) -> Self {
+        Self {
+            _type: ContentType::Document,
+            source,
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Sets the title of the document.
+    pub fn title<S>(
+        mut self,
+        title: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the context about the document.
+    pub fn context<S>(
+        mut self,
+        context: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// Enables or disables citations of the document.
+    pub fn citations(
+        mut self,
+        enabled: bool,
+    ) -> Self {
+        self.citations = Some(CitationsConfig {
+            enabled,
+        });
+        self
+    }
+
+    /// Sets the cache control of the document.
+    pub fn cache_control(
+        mut self,
+        cache_control: CacheControl,
+    ) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+}
+
+/// The citations configuration of a document.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CitationsConfig {
+    /// Whether to cite the document in the response.
+    pub enabled: bool,
+}
+
+impl_display_for_serialize!(CitationsConfig);
+
+/// The document content source.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DocumentContentSource {
+    /// The Base64 encoded PDF document.
+    Base64 {
+        /// The media type. It is always `application/pdf`.
+        media_type: DocumentMediaType,
+        /// The data of the document.
+        data: String,
+    },
+    /// The plain text document.
+    Text {
+        /// The media type. It is always `text/plain`.
+        media_type: DocumentMediaType,
+        /// The text of the document.
+        data: String,
+    },
+    /// The PDF document at the URL.
+    Url {
+        /// The URL of the document.
+        url: String,
+    },
+    /// The custom content document of text or image content blocks, cited by block.
+    Content {
+        /// The content blocks of the document.
+        content: Vec<ContentBlock>,
+    },
+    /// The document uploaded by the Files API.
+    File {
+        /// The ID of the uploaded file.
+        file_id: String,
+    },
+}
+
+impl_display_for_serialize!(DocumentContentSource);
+
+impl DocumentContentSource {
+    /// Creates a new document content source from Base64 encoded PDF data.
+    ///
+    /// ## Arguments
+    /// - `data` - The Base64 encoded data of the PDF.
+    pub fn base64_pdf<S>(data: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Base64 {
+            media_type: DocumentMediaType::Pdf,
+            data: data.into(),
+        }
+    }
+
+    /// Creates a new document content source from plain text.
+    ///
+    /// ## Arguments
+    /// - `text` - The text of the document.
+    pub fn text<S>(text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Text {
+            media_type: DocumentMediaType::PlainText,
+            data: text.into(),
+        }
+    }
+
+    /// Creates a new document content source referencing a PDF by the URL.
+    ///
+    /// ## Arguments
+    /// - `url` - The URL of the PDF.
+    pub fn url<S>(url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Url {
+            url: url.into(),
+        }
+    }
+
+    /// Creates a new document content source from custom content blocks.
+    ///
+    /// ## Arguments
+    /// - `content` - The text or image content blocks of the document.
+    pub fn content(content: Vec<ContentBlock>) -> Self {
+        Self::Content {
+            content,
+        }
+    }
+
+    /// Creates a new document content source referencing a file uploaded by the Files API.
+    ///
+    /// ## Arguments
+    /// - `file_id` - The ID of the uploaded file.
+    pub fn file<S>(file_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::File {
+            file_id: file_id.into(),
+        }
+    }
+
+    /// Creates a new document content source from the file at the path,
+    /// detecting the media type from the extension by [`DocumentMediaType::from_path`].
+    ///
+    /// A PDF is Base64 encoded and a plain text is read as UTF-8.
+    ///
+    /// ## Arguments
+    /// - `path` - The path of the document.
+    pub async fn from_path<P>(path: P) -> Result<Self, DocumentLoadError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let media_type = DocumentMediaType::from_path(path)?;
+        let content = tokio::fs::read(path)
+            .await
+            .map_err(|source| DocumentLoadError::ReadFileFailed {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        match media_type {
+            | DocumentMediaType::Pdf => Ok(Self::base64_pdf(
+                base64::engine::general_purpose::STANDARD.encode(content),
+            )),
+            | DocumentMediaType::PlainText => {
+                Ok(Self::text(String::from_utf8(content)?))
+            },
+        }
+    }
+}
+
+/// The media type of the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentMediaType {
+    /// application/pdf
+    Pdf,
+    /// text/plain
+    PlainText,
+}
+
+impl Default for DocumentMediaType {
+    fn default() -> Self {
+        Self::Pdf
+    }
+}
+
+impl Display for DocumentMediaType {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | DocumentMediaType::Pdf => {
+                write!(f, "application/pdf")
+            },
+            | DocumentMediaType::PlainText => {
+                write!(f, "text/plain")
+            },
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    DocumentMediaType,
+    Pdf => "application/pdf",
+    PlainText => "text/plain"
+);
+
+impl DocumentMediaType {
+    /// Creates the media type from the extension of the path.
+    pub fn from_path(
+        path: &Path
+    ) -> Result<Self, DocumentMediaTypeParseError> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+        {
+            | Some("pdf") => Ok(Self::Pdf),
+            | Some("txt") | Some("text") | Some("md") => Ok(Self::PlainText),
+            | Some(extension) => {
+                Err(DocumentMediaTypeParseError::NotSupported(
+                    extension.to_string(),
+                ))
+            },
+            | None => Err(DocumentMediaTypeParseError::NotFound),
+        }
+    }
+}
+
 /// The tool use content block.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct ToolUseContentBlock {
@@ -828,6 +1233,8 @@ mod tests {
             ImageSourceType::Base64.to_string(),
             "base64"
         );
+        assert_eq!(ImageSourceType::Url.to_string(), "url");
+        assert_eq!(ImageSourceType::File.to_string(), "file");
     }
 
     #[test]
@@ -836,6 +1243,14 @@ mod tests {
             serde_json::to_string(&ImageSourceType::Base64).unwrap(),
             "\"base64\""
         );
+        assert_eq!(
+            serde_json::to_string(&ImageSourceType::Url).unwrap(),
+            "\"url\""
+        );
+        assert_eq!(
+            serde_json::to_string(&ImageSourceType::File).unwrap(),
+            "\"file\""
+        );
     }
 
     #[test]
@@ -844,6 +1259,14 @@ mod tests {
             serde_json::from_str::<ImageSourceType>("\"base64\"").unwrap(),
             ImageSourceType::Base64
         );
+        assert_eq!(
+            serde_json::from_str::<ImageSourceType>("\"url\"").unwrap(),
+            ImageSourceType::Url
+        );
+        assert_eq!(
+            serde_json::from_str::<ImageSourceType>("\"file\"").unwrap(),
+            ImageSourceType::File
+        );
     }
 
     #[test]
@@ -958,8 +1381,7 @@ mod tests {
             ImageContentSource::base64(ImageMediaType::Jpeg, "data");
         assert_eq!(
             image_content_source,
-            ImageContentSource {
-                _type: ImageSourceType::Base64,
+            ImageContentSource::Base64 {
                 media_type: ImageMediaType::Jpeg,
                 data: "data".to_string(),
             }
@@ -970,8 +1392,7 @@ mod tests {
     fn default_image_content_source() {
         assert_eq!(
             ImageContentSource::default(),
-            ImageContentSource {
-                _type: ImageSourceType::Base64,
+            ImageContentSource::Base64 {
                 media_type: ImageMediaType::Jpeg,
                 data: String::new(),
             }
@@ -980,8 +1401,7 @@ mod tests {
 
     #[test]
     fn display_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
+        let image_content_source = ImageContentSource::Base64 {
             media_type: ImageMediaType::Jpeg,
             data: "data".to_string(),
         };
@@ -993,8 +1413,7 @@ mod tests {
 
     #[test]
     fn serialize_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
+        let image_content_source = ImageContentSource::Base64 {
             media_type: ImageMediaType::Jpeg,
             data: "data".to_string(),
         };
@@ -1002,12 +1421,19 @@ mod tests {
             serde_json::to_string(&image_content_source).unwrap(),
             "{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}"
         );
+        assert_eq!(
+            serde_json::to_string(&ImageContentSource::url("https://example.com/image.png")).unwrap(),
+            "{\"type\":\"url\",\"url\":\"https://example.com/image.png\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&ImageContentSource::file("file_01")).unwrap(),
+            "{\"type\":\"file\",\"file_id\":\"file_01\"}"
+        );
     }
 
     #[test]
     fn deserialize_image_content_source() {
-        let image_content_source = ImageContentSource {
-            _type: ImageSourceType::Base64,
+        let image_content_source = ImageContentSource::Base64 {
             media_type: ImageMediaType::Jpeg,
             data: "data".to_string(),
         };
@@ -1015,6 +1441,31 @@ mod tests {
             serde_json::from_str::<ImageContentSource>("{\"type\":\"base64\",\"media_type\":\"image/jpeg\",\"data\":\"data\"}").unwrap(),
             image_content_source
         );
+        assert_eq!(
+            serde_json::from_str::<ImageContentSource>("{\"type\":\"url\",\"url\":\"https://example.com/image.png\"}").unwrap(),
+            ImageContentSource::url("https://example.com/image.png")
+        );
+        assert_eq!(
+            serde_json::from_str::<ImageContentSource>("{\"type\":\"file\",\"file_id\":\"file_01\"}").unwrap(),
+            ImageContentSource::file("file_01")
+        );
+    }
+
+    #[test]
+    fn image_content_source_type() {
+        assert_eq!(
+            ImageContentSource::default().source_type(),
+            ImageSourceType::Base64
+        );
+        assert_eq!(
+            ImageContentSource::url("https://example.com/image.png")
+                .source_type(),
+            ImageSourceType::Url
+        );
+        assert_eq!(
+            ImageContentSource::file("file_01").source_type(),
+            ImageSourceType::File
+        );
     }
 
     #[test]
@@ -1025,6 +1476,7 @@ mod tests {
             TextContentBlock {
                 _type: ContentType::Text,
                 text: "text".to_string(),
+                citations: None,
                 cache_control: None,
             }
         );
@@ -1037,6 +1489,7 @@ mod tests {
             TextContentBlock {
                 _type: ContentType::Text,
                 text: String::new(),
+                citations: None,
                 cache_control: None,
             }
         );
@@ -1129,6 +1582,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_document_content_block() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::file("file_01"));
+        assert_eq!(
+            serde_json::to_string(&document_content_block).unwrap(),
+            "{\"type\":\"document\",\"source\":{\"type\":\"file\",\"file_id\":\"file_01\"}}"
+        );
+        assert_eq!(
+            serde_json::from_str::<ContentBlock>("{\"type\":\"document\",\"source\":{\"type\":\"file\",\"file_id\":\"file_01\"}}").unwrap(),
+            ContentBlock::from(DocumentContentSource::file("file_01"))
+        );
+    }
+
+    #[test]
+    fn serialize_document_content_sources() {
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::base64_pdf("data")).unwrap(),
+            "{\"type\":\"base64\",\"media_type\":\"application/pdf\",\"data\":\"data\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::text("text")).unwrap(),
+            "{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::url("https://example.com/a.pdf")).unwrap(),
+            "{\"type\":\"url\",\"url\":\"https://example.com/a.pdf\"}"
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentContentSource::content(vec![
+                "First chunk".into(),
+            ]))
+            .unwrap(),
+            "{\"type\":\"content\",\"content\":[{\"type\":\"text\",\"text\":\"First chunk\"}]}"
+        );
+        assert_eq!(
+            serde_json::from_str::<DocumentContentSource>(
+                "{\"type\":\"text\",\"media_type\":\"text/plain\",\"data\":\"text\"}"
+            )
+            .unwrap(),
+            DocumentContentSource::text("text")
+        );
+    }
+
+    #[test]
+    fn serialize_document_content_block_with_options() {
+        let document_content_block =
+            DocumentContentBlock::new(DocumentContentSource::url("https://example.com/a.pdf"))
+                .title("Title")
+                .context("Context")
+                .citations(true)
+                .cache_control(CacheControl::default());
+        assert_eq!(
+            serde_json::to_string(&document_content_block).unwrap(),
+            "{\"type\":\"document\",\"source\":{\"type\":\"url\",\"url\":\"https://example.com/a.pdf\"},\"title\":\"Title\",\"context\":\"Context\",\"citations\":{\"enabled\":true},\"cache_control\":{\"type\":\"ephemeral\"}}"
+        );
+        assert_eq!(
+            ContentBlock::Document(document_content_block).cache_control(),
+            Some(&CacheControl::default())
+        );
+    }
+
+    #[test]
+    fn from_path_document_media_type() {
+        assert_eq!(
+            DocumentMediaType::from_path(Path::new("document.pdf")).unwrap(),
+            DocumentMediaType::Pdf
+        );
+        assert_eq!(
+            DocumentMediaType::from_path(Path::new("document.txt")).unwrap(),
+            DocumentMediaType::PlainText
+        );
+        assert_eq!(
+            DocumentMediaType::from_path(Path::new("document.docx")),
+            Err(DocumentMediaTypeParseError::NotSupported(
+                "docx".to_string()
+            ))
+        );
+        assert_eq!(
+            DocumentMediaType::from_path(Path::new("document")),
+            Err(DocumentMediaTypeParseError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn document_content_source_from_path() {
+        let directory = std::env::temp_dir();
+        let pdf = directory.join(format!(
+            "clust-document-{}.pdf",
+            std::process::id()
+        ));
+        let text = directory.join(format!(
+            "clust-document-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&pdf, b"%PDF").unwrap();
+        std::fs::write(&text, "The grass is green.").unwrap();
+
+        assert_eq!(
+            DocumentContentSource::from_path(&pdf)
+                .await
+                .unwrap(),
+            DocumentContentSource::base64_pdf("JVBERg==")
+        );
+        assert_eq!(
+            DocumentContentSource::from_path(&text)
+                .await
+                .unwrap(),
+            DocumentContentSource::text("The grass is green.")
+        );
+        assert!(matches!(
+            DocumentContentSource::from_path(directory.join("missing.pdf")).await,
+            Err(DocumentLoadError::ReadFileFailed { .. })
+        ));
+
+        std::fs::remove_file(&pdf).unwrap();
+        std::fs::remove_file(&text).unwrap();
+    }
+
     #[test]
     fn new_tool_use_content_block() {
         let tool_use_content_block = ToolUseContentBlock::new(ToolUse::new(
@@ -1304,6 +1876,7 @@ mod tests {
             ContentBlock::Text(TextContentBlock {
                 _type: ContentType::Text,
                 text: "text".to_string(),
+                citations: None,
                 cache_control: None,
             })
         );
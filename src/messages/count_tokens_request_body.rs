@@ -0,0 +1,83 @@
+use crate::macros::impl_display_for_serialize;
+use crate::messages::{
+    ClaudeModel, Message, MessagesRequestBody, SystemPrompt, Thinking,
+    ToolDefinition,
+};
+
+/// The request body for counting tokens in a message.
+///
+/// The fields are the same as [`MessagesRequestBody`] except for those that do not affect the input tokens.
+///
+/// See also [the count message tokens API reference](https://docs.anthropic.com/en/api/messages-count-tokens).
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct CountTokensRequestBody {
+    /// The model that will complete your prompt.
+    pub model: ClaudeModel,
+    /// Input messages.
+    pub messages: Vec<Message>,
+    /// System prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPrompt>,
+    /// Definitions of tools that the model may use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+    /// Configuration for enabling extended thinking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+}
+
+impl_display_for_serialize!(CountTokensRequestBody);
+
+impl From<MessagesRequestBody> for CountTokensRequestBody {
+    fn from(request_body: MessagesRequestBody) -> Self {
+        Self {
+            model: request_body.model,
+            messages: request_body.messages,
+            system: request_body.system,
+            tools: request_body.tools,
+            thinking: request_body.thinking,
+        }
+    }
+}
+
+impl From<&MessagesRequestBody> for CountTokensRequestBody {
+    fn from(request_body: &MessagesRequestBody) -> Self {
+        request_body.clone().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{MaxTokens, Temperature};
+
+    #[test]
+    fn from_messages_request_body() {
+        let request_body = MessagesRequestBody {
+            model: ClaudeModel::Claude3Haiku20240307,
+            messages: vec![Message::user("Hello, Claude!")],
+            system: Some(SystemPrompt::new("system-prompt")),
+            max_tokens: MaxTokens::new(1024, ClaudeModel::Claude3Haiku20240307)
+                .unwrap(),
+            temperature: Some(Temperature::new(0.5).unwrap()),
+            ..Default::default()
+        };
+
+        let count_tokens = CountTokensRequestBody::from(&request_body);
+        assert_eq!(
+            count_tokens.to_string(),
+            r#"{
+  "model": "claude-3-haiku-20240307",
+  "messages": [
+    {
+      "role": "user",
+      "content": "Hello, Claude!"
+    }
+  ],
+  "system": "system-prompt"
+}"#
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use crate::macros::impl_display_for_serialize;
+
+/// The response body for counting tokens in a message.
+///
+/// See also [the count message tokens API reference](https://docs.anthropic.com/en/api/messages-count-tokens).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct CountTokensResponseBody {
+    /// The total number of tokens across the provided list of messages, system prompt, and tools.
+    pub input_tokens: u32,
+}
+
+impl_display_for_serialize!(CountTokensResponseBody);
@@ -1,5 +1,8 @@
+use crate::request::impl_call_error;
+use crate::transport::TransportError;
 use crate::{ApiError, ClientError};
 use std::fmt::Display;
+use std::time::Duration;
 
 /// The error type for the messages API.
 #[derive(Debug, thiserror::Error)]
@@ -13,14 +16,25 @@ pub enum MessagesError {
     /// Stream option mismatch.
     #[error("Stream option mismatch")]
     StreamOptionMismatch,
+    /// Timed out connecting to the server.
+    #[error("Connect timed out: {0}")]
+    ConnectTimeout(TransportError),
+    /// Timed out until the response headers are received.
+    #[error("Response headers timed out after {0:?}")]
+    HeadersTimeout(Duration),
+    /// Timed out the whole call.
+    #[error("Request timed out after {0:?}")]
+    Timeout(Duration),
 }
 
+impl_call_error!(MessagesError);
+
 /// The error type for the streaming messages.
 #[derive(Debug, thiserror::Error)]
 pub enum StreamError {
-    /// Reqwest error.
+    /// Transport error while reading the stream.
     #[error(transparent)]
-    ReqwestError(#[from] reqwest::Error),
+    TransportError(#[from] TransportError),
     /// String decoding error.
     #[error(transparent)]
     StringDecodingError(#[from] std::string::FromUtf8Error),
@@ -33,6 +47,15 @@ pub enum StreamError {
     /// Chunk data deserialization error.
     #[error(transparent)]
     ChunkDataDeserializationError(#[from] serde_json::Error),
+    /// Timed out waiting for the first chunk.
+    #[error("First chunk timed out after {0:?}")]
+    FirstChunkTimeout(Duration),
+    /// Timed out waiting for the next chunk.
+    #[error("Stream idle timed out after {0:?}")]
+    IdleTimeout(Duration),
+    /// The chunk does not follow the previous chunks, e.g. a delta to a missing content block.
+    #[error("Invalid chunk sequence: {0}")]
+    InvalidChunkSequence(String),
 }
 
 /// The error type for parsing message chunk type.
@@ -77,6 +100,36 @@ pub enum ImageMediaTypeParseError {
     NotFound,
 }
 
+/// The error type for parsing the document media type from an extension in a path.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DocumentMediaTypeParseError {
+    /// The extension is not supported
+    #[error("The extension is not supported: {0}")]
+    NotSupported(String),
+    /// Extension is not found
+    #[error("Extension is not found")]
+    NotFound,
+}
+
+/// The error type for loading a document content source from a file.
+#[derive(Debug, thiserror::Error)]
+pub enum DocumentLoadError {
+    /// Failed to detect the media type from the extension.
+    #[error("Failed to detect the media type of the document: {0}")]
+    MediaTypeParseFailed(#[from] DocumentMediaTypeParseError),
+    /// Failed to read the file.
+    #[error("Failed to read the file {path:?}: {source}")]
+    ReadFileFailed {
+        /// The path of the file.
+        path: std::path::PathBuf,
+        /// The I/O error.
+        source: std::io::Error,
+    },
+    /// The plain text document is not valid UTF-8.
+    #[error("The plain text document is not valid UTF-8: {0}")]
+    InvalidUtf8(#[from] std::string::FromUtf8Error),
+}
+
 /// The error type for the tool call.
 #[derive(Debug, PartialEq, thiserror::Error)]
 pub enum ToolCallError {
@@ -77,6 +77,11 @@ impl MaxTokens {
             value: model.max_tokens(),
         }
     }
+
+    /// Gets the value of the maximum number of tokens.
+    pub(crate) fn value(&self) -> u32 {
+        self.value
+    }
 }
 
 #[cfg(test)]
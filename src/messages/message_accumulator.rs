@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use crate::messages::{
+    Content, ContentBlock, ContentBlockDelta, ContentBlockStart, MessageChunk,
+    MessagesResponseBody, StreamError,
+};
+
+/// The accumulator of the chunks of a message stream into the whole message.
+///
+/// Text, thinking, signature and tool input deltas are appended to the content block at the index of the chunk,
+/// and each citation of a citations delta is attached to the text content block at the index.
+///
+/// ## Example
+/// ```no_run
+/// use clust::messages::{
+///     ClaudeModel, MaxTokens, Message, MessageAccumulator, MessagesRequestBody,
+///     StreamOption,
+/// };
+/// use clust::Client;
+/// use tokio_stream::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let client = Client::from_env()?;
+///     let request_body = MessagesRequestBody {
+///         model: ClaudeModel::Claude3Haiku20240307,
+///         messages: vec![Message::user("Hello, Claude!")],
+///         max_tokens: MaxTokens::new(1024, ClaudeModel::Claude3Haiku20240307)?,
+///         stream: Some(StreamOption::ReturnStream),
+///         ..Default::default()
+///     };
+///
+///     let mut stream = client
+///         .create_a_message_stream(request_body)
+///         .await?;
+///     let mut accumulator = MessageAccumulator::new();
+///     while let Some(chunk) = stream.next().await {
+///         accumulator.push(chunk?)?;
+///     }
+///
+///     if let Some(message) = accumulator.finish() {
+///         println!("{}", message.content);
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageAccumulator {
+    message: Option<MessagesResponseBody>,
+    blocks: Vec<ContentBlock>,
+    partial_json: HashMap<u32, String>,
+}
+
+impl MessageAccumulator {
+    /// Creates a new empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulates the chunk into the message.
+    pub fn push(
+        &mut self,
+        chunk: MessageChunk,
+    ) -> Result<(), StreamError> {
+        match chunk {
+            | MessageChunk::MessageStart(chunk) => {
+                let mut message = chunk.message;
+                self.blocks = match std::mem::replace(
+                    &mut message.content,
+                    Content::MultipleBlocks(Vec::new()),
+                ) {
+                    | Content::SingleText(text) if text.is_empty() => {
+                        Vec::new()
+                    },
+                    | Content::SingleText(text) => vec![text.into()],
+                    | Content::MultipleBlocks(blocks) => blocks,
+                };
+                self.message = Some(message);
+            },
+            | MessageChunk::ContentBlockStart(chunk) => {
+                let block = match chunk.content_block {
+                    | ContentBlockStart::TextContentBlock(block) => {
+                        ContentBlock::Text(block)
+                    },
+                    | ContentBlockStart::ThinkingContentBlock(block) => {
+                        ContentBlock::Thinking(block)
+                    },
+                    | ContentBlockStart::ToolUseContentBlock(block) => {
+                        ContentBlock::ToolUse(block)
+                    },
+                };
+                let index = chunk.index as usize;
+                if index < self.blocks.len() {
+                    self.blocks[index] = block;
+                } else if index == self.blocks.len() {
+                    self.blocks.push(block);
+                } else {
+                    return Err(StreamError::InvalidChunkSequence(
+                        format!(
+                            "content block start at index {} after {} blocks",
+                            chunk.index,
+                            self.blocks.len()
+                        ),
+                    ));
+                }
+            },
+            | MessageChunk::ContentBlockDelta(chunk) => {
+                let index = chunk.index;
+                let block = self
+                    .blocks
+                    .get_mut(index as usize)
+                    .ok_or_else(|| {
+                        StreamError::InvalidChunkSequence(format!(
+                            "delta to a missing content block at index {index}"
+                        ))
+                    })?;
+
+                match (block, chunk.delta) {
+                    | (
+                        ContentBlock::Text(block),
+                        ContentBlockDelta::TextDeltaContentBlock(delta),
+                    ) => block.text.push_str(&delta.text),
+                    | (
+                        ContentBlock::Text(block),
+                        ContentBlockDelta::CitationsDeltaContentBlock(delta),
+                    ) => block
+                        .citations
+                        .get_or_insert_with(Vec::new)
+                        .push(delta.citation),
+                    | (
+                        ContentBlock::Thinking(block),
+                        ContentBlockDelta::ThinkingDeltaContentBlock(delta),
+                    ) => block
+                        .thinking
+                        .push_str(&delta.thinking),
+                    | (
+                        ContentBlock::Thinking(block),
+                        ContentBlockDelta::SignatureDeltaContentBlock(delta),
+                    ) => block
+                        .signature
+                        .push_str(&delta.signature),
+                    | (
+                        ContentBlock::ToolUse(_),
+                        ContentBlockDelta::InputJsonDeltaBlock(delta),
+                    ) => self
+                        .partial_json
+                        .entry(index)
+                        .or_default()
+                        .push_str(&delta.partial_json),
+                    | (_, delta) => {
+                        return Err(StreamError::InvalidChunkSequence(
+                            format!(
+                                "unexpected delta to the content block at index {index}: {delta:?}"
+                            ),
+                        ));
+                    },
+                }
+            },
+            | MessageChunk::ContentBlockStop(chunk) => {
+                if let (
+                    Some(partial_json),
+                    Some(ContentBlock::ToolUse(block)),
+                ) = (
+                    self.partial_json
+                        .remove(&chunk.index),
+                    self.blocks
+                        .get_mut(chunk.index as usize),
+                ) {
+                    if !partial_json.is_empty() {
+                        block.tool_use.input =
+                            serde_json::from_str(&partial_json)?;
+                    }
+                }
+            },
+            | MessageChunk::MessageDelta(chunk) => {
+                if let Some(message) = self.message.as_mut() {
+                    message.stop_reason = chunk.delta.stop_reason;
+                    message.stop_sequence = chunk.delta.stop_sequence;
+                    message.usage.output_tokens = chunk.usage.output_tokens;
+                }
+            },
+            | MessageChunk::Ping(_) | MessageChunk::MessageStop(_) => {},
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the accumulation and returns the message, or `None` when no message start chunk was pushed.
+    pub fn finish(self) -> Option<MessagesResponseBody> {
+        self.message
+            .map(|message| MessagesResponseBody {
+                content: Content::MultipleBlocks(self.blocks),
+                ..message
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        CharLocationCitation, Citation, StopReason, TextContentBlock, ToolUse,
+        ToolUseContentBlock, Usage,
+    };
+
+    fn citation(cited_text: &str) -> Citation {
+        Citation::CharLocation(CharLocationCitation {
+            cited_text: cited_text.to_string(),
+            document_index: 0,
+            document_title: Some("My Document".to_string()),
+            start_char_index: 0,
+            end_char_index: cited_text.len() as u32,
+            file_id: None,
+        })
+    }
+
+    fn accumulate(chunks: &[&str]) -> Result<MessagesResponseBody, StreamError> {
+        let mut accumulator = MessageAccumulator::new();
+        for chunk in chunks {
+            accumulator.push(MessageChunk::parse(chunk)?)?;
+        }
+        Ok(accumulator.finish().unwrap())
+    }
+
+    #[test]
+    fn accumulate_citations_to_text_blocks() {
+        let message = accumulate(&[
+            r#"event: message_start
+data: {"type":"message_start","message":{"id":"msg_01","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#,
+            r#"event: content_block_start
+data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"According to the document, "}}"#,
+            r#"event: content_block_stop
+data: {"type":"content_block_stop","index":0}"#,
+            r#"event: content_block_start
+data: {"type":"content_block_start","index":1,"content_block":{"type":"text","text":"","citations":[]}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":1,"delta":{"type":"citations_delta","citation":{"type":"char_location","cited_text":"The grass is green.","document_index":0,"document_title":"My Document","start_char_index":0,"end_char_index":19}}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"the grass is green"}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":1,"delta":{"type":"citations_delta","citation":{"type":"char_location","cited_text":"green","document_index":0,"document_title":"My Document","start_char_index":0,"end_char_index":5}}}"#,
+            r#"event: content_block_stop
+data: {"type":"content_block_stop","index":1}"#,
+            r#"event: message_delta
+data: {"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":15}}"#,
+            r#"event: message_stop
+data: {"type":"message_stop"}"#,
+        ])
+        .unwrap();
+
+        assert_eq!(
+            message.content,
+            Content::MultipleBlocks(vec![
+                ContentBlock::Text(TextContentBlock::new(
+                    "According to the document, "
+                )),
+                ContentBlock::Text(TextContentBlock {
+                    citations: Some(vec![
+                        citation("The grass is green."),
+                        citation("green"),
+                    ]),
+                    ..TextContentBlock::new("the grass is green")
+                }),
+            ])
+        );
+        assert_eq!(message.stop_reason, Some(StopReason::EndTurn));
+        assert_eq!(message.usage.output_tokens, 15);
+    }
+
+    #[test]
+    fn accumulate_tool_use_input() {
+        let message = accumulate(&[
+            r#"event: message_start
+data: {"type":"message_start","message":{"id":"msg_01","type":"message","role":"assistant","content":[],"model":"claude-3-5-sonnet-20241022","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#,
+            r#"event: content_block_start
+data: {"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"toolu_01","name":"get_weather","input":{}}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"location\": "}}"#,
+            r#"event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"\"Tokyo\"}"}}"#,
+            r#"event: content_block_stop
+data: {"type":"content_block_stop","index":0}"#,
+        ])
+        .unwrap();
+
+        assert_eq!(
+            message.content,
+            Content::MultipleBlocks(vec![ContentBlock::ToolUse(
+                ToolUseContentBlock::new(ToolUse::new(
+                    "toolu_01",
+                    "get_weather",
+                    serde_json::json!({"location": "Tokyo"}),
+                ))
+            )])
+        );
+    }
+
+    #[test]
+    fn round_trip_from_message() {
+        let message = MessagesResponseBody {
+            content: Content::MultipleBlocks(vec![ContentBlock::Text(
+                TextContentBlock {
+                    citations: Some(vec![citation("The grass is green.")]),
+                    ..TextContentBlock::new("The grass is green.")
+                },
+            )]),
+            stop_reason: Some(StopReason::EndTurn),
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut accumulator = MessageAccumulator::new();
+        for chunk in MessageChunk::from_message(message.clone()) {
+            accumulator.push(chunk).unwrap();
+        }
+
+        assert_eq!(accumulator.finish(), Some(message));
+    }
+
+    #[test]
+    fn delta_to_missing_block() {
+        let mut accumulator = MessageAccumulator::new();
+        let result = accumulator.push(
+            MessageChunk::parse(
+                r#"event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"citations_delta","citation":{"type":"char_location","cited_text":"a","document_index":0,"document_title":null,"start_char_index":0,"end_char_index":1}}}"#,
+            )
+            .unwrap(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(StreamError::InvalidChunkSequence(_))
+        ));
+        assert!(accumulator.finish().is_none());
+    }
+}
@@ -6,8 +6,9 @@ use crate::macros::{
     impl_display_for_serialize, impl_enum_string_serialization,
 };
 use crate::messages::{
-    ContentType, MessageChunkTypeError, MessagesResponseBody, StopReason,
-    StopSequence, StreamError, TextContentBlock, ThinkingContentBlock,
+    Citation, Content, ContentBlock, ContentType, MessageChunkTypeError,
+    MessagesResponseBody, StopReason, StopSequence, StreamError,
+    TextContentBlock, ThinkingContentBlock, Usage,
 };
 
 use super::ToolUseContentBlock;
@@ -125,6 +126,150 @@ impl Display for MessageChunk {
 }
 
 impl MessageChunk {
+    /// Splits a whole message into the sequence of chunks as if it were streamed.
+    ///
+    /// Image and tool result blocks are skipped because they never appear in responses.
+    pub(crate) fn from_message(message: MessagesResponseBody) -> Vec<Self> {
+        let mut chunks = vec![MessageChunk::MessageStart(
+            MessageStartChunk::new(MessagesResponseBody {
+                id: message.id,
+                _type: message._type,
+                role: message.role,
+                content: Content::MultipleBlocks(Vec::new()),
+                model: message.model,
+                stop_reason: None,
+                stop_sequence: None,
+                usage: Usage {
+                    output_tokens: 0,
+                    ..message.usage
+                },
+            }),
+        )];
+
+        let blocks = match message.content {
+            | Content::SingleText(text) => {
+                vec![ContentBlock::Text(TextContentBlock::new(text))]
+            },
+            | Content::MultipleBlocks(blocks) => blocks,
+        };
+
+        let mut index = 0;
+        for block in blocks {
+            let (start, deltas) = match block {
+                | ContentBlock::Text(block) => {
+                    let mut deltas =
+                        vec![ContentBlockDelta::TextDeltaContentBlock(
+                            TextDeltaContentBlock::new(block.text),
+                        )];
+                    // Citations are streamed after the text they support.
+                    deltas.extend(
+                        block
+                            .citations
+                            .iter()
+                            .flatten()
+                            .cloned()
+                            .map(|citation| {
+                                ContentBlockDelta::CitationsDeltaContentBlock(
+                                    citation.into(),
+                                )
+                            }),
+                    );
+
+                    (
+                        ContentBlockStart::TextContentBlock(TextContentBlock {
+                            _type: ContentType::Text,
+                            text: String::new(),
+                            citations: block.citations.as_ref().map(|_| Vec::new()),
+                            cache_control: block.cache_control,
+                        }),
+                        deltas,
+                    )
+                },
+                | ContentBlock::Thinking(block) => {
+                    let mut deltas =
+                        vec![ContentBlockDelta::ThinkingDeltaContentBlock(
+                            ThinkingDeltaContentBlock::new(block.thinking),
+                        )];
+                    if !block.signature.is_empty() {
+                        deltas.push(
+                            ContentBlockDelta::SignatureDeltaContentBlock(
+                                SignatureDeltaContentBlock::new(
+                                    block.signature,
+                                ),
+                            ),
+                        );
+                    }
+
+                    (
+                        ContentBlockStart::ThinkingContentBlock(
+                            ThinkingContentBlock::new("", ""),
+                        ),
+                        deltas,
+                    )
+                },
+                | ContentBlock::ToolUse(mut block) => {
+                    let input = std::mem::replace(
+                        &mut block.tool_use.input,
+                        serde_json::Value::Object(Default::default()),
+                    );
+
+                    (
+                        ContentBlockStart::ToolUseContentBlock(block),
+                        vec![ContentBlockDelta::InputJsonDeltaBlock(
+                            InputJsonDeltaBlock {
+                                _type: ContentType::InputJsonDelta,
+                                partial_json: input.to_string(),
+                            },
+                        )],
+                    )
+                },
+                | ContentBlock::Image(_)
+                | ContentBlock::Document(_)
+                | ContentBlock::ToolResult(_) => {
+                    continue;
+                },
+            };
+
+            chunks.push(MessageChunk::ContentBlockStart(
+                ContentBlockStartChunk {
+                    _type: MessageChunkType::ContentBlockStart,
+                    index,
+                    content_block: start,
+                },
+            ));
+            for delta in deltas {
+                chunks.push(MessageChunk::ContentBlockDelta(
+                    ContentBlockDeltaChunk {
+                        _type: MessageChunkType::ContentBlockDelta,
+                        index,
+                        delta,
+                    },
+                ));
+            }
+            chunks.push(MessageChunk::ContentBlockStop(
+                ContentBlockStopChunk::new(index),
+            ));
+            index += 1;
+        }
+
+        chunks.push(MessageChunk::MessageDelta(
+            MessageDeltaChunk::new(
+                StreamStop {
+                    stop_reason: message.stop_reason,
+                    stop_sequence: message.stop_sequence,
+                },
+                DeltaUsage {
+                    output_tokens: message.usage.output_tokens,
+                },
+            ),
+        ));
+        chunks.push(MessageChunk::MessageStop(
+            MessageStopChunk::new(),
+        ));
+
+        chunks
+    }
+
     pub(crate) fn parse(source: &str) -> Result<MessageChunk, StreamError> {
         let lines = source
             .lines()
@@ -416,6 +561,7 @@ pub enum ContentBlockDelta {
     ThinkingDeltaContentBlock(ThinkingDeltaContentBlock),
     InputJsonDeltaBlock(InputJsonDeltaBlock),
     SignatureDeltaContentBlock(SignatureDeltaContentBlock),
+    CitationsDeltaContentBlock(CitationsDeltaContentBlock),
 }
 
 impl Default for ContentBlockDelta {
@@ -587,6 +733,34 @@ impl TextDeltaContentBlock {
     }
 }
 
+/// The citations delta content block, adding a citation to the text content block at the index.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CitationsDeltaContentBlock {
+    /// The content type. It is always `citations_delta`.
+    #[serde(rename = "type")]
+    pub _type: ContentType,
+    /// The citation to add.
+    pub citation: Citation,
+}
+
+impl_display_for_serialize!(CitationsDeltaContentBlock);
+
+impl From<Citation> for CitationsDeltaContentBlock {
+    fn from(citation: Citation) -> Self {
+        Self::new(citation)
+    }
+}
+
+impl CitationsDeltaContentBlock {
+    /// Creates a new citations delta content block.
+    pub fn new(citation: Citation) -> Self {
+        Self {
+            _type: ContentType::CitationsDelta,
+            citation,
+        }
+    }
+}
+
 /// The signature delta content block.
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct SignatureDeltaContentBlock {
@@ -0,0 +1,356 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::messages::{
+    MessageChunk, MessagesRequestBody, MessagesResponseBody, StreamError,
+};
+use crate::transport::TransportRequest;
+use crate::ResponseMeta;
+
+/// The middleware to intercept API calling of the Messages API.
+///
+/// Middlewares are called in the order of registration for requests,
+/// and in the reverse order for responses and chunks.
+///
+/// All methods have default implementations that do nothing,
+/// so you only need to implement the hooks you are interested in.
+///
+/// ## Example
+/// ```
+/// use clust::{ApiKey, ClientBuilder, Middleware, MiddlewareAction};
+/// use clust::messages::{Metadata, MessagesRequestBody, UserId};
+/// use clust::transport::TransportRequest;
+///
+/// struct ForceUserId;
+///
+/// impl Middleware for ForceUserId {
+///     fn on_request_body(
+///         &self,
+///         request_body: &mut MessagesRequestBody,
+///     ) -> MiddlewareAction {
+///         request_body.metadata = Some(Metadata {
+///             user_id: UserId::new("user-id"),
+///         });
+///         MiddlewareAction::Continue
+///     }
+///
+///     fn on_request(
+///         &self,
+///         request: &mut TransportRequest,
+///     ) {
+///         request.headers.insert("x-custom-header", "value".parse().unwrap());
+///     }
+/// }
+///
+/// let client = ClientBuilder::new(ApiKey::new("api-key"))
+///     .middleware(ForceUserId)
+///     .build();
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Inspects or rewrites the request body before it is sent.
+    ///
+    /// Return `MiddlewareAction::Respond` to skip sending the request and
+    /// respond with the synthetic response instead.
+    fn on_request_body(
+        &self,
+        _request_body: &mut MessagesRequestBody,
+    ) -> MiddlewareAction {
+        MiddlewareAction::Continue
+    }
+
+    /// Inspects or rewrites the outgoing HTTP request.
+    ///
+    /// This is called for each attempt including retries.
+    fn on_request(
+        &self,
+        _request: &mut TransportRequest,
+    ) {
+    }
+
+    /// Inspects or rewrites the response body of a non-streaming call.
+    fn on_response(
+        &self,
+        _response_body: &mut MessagesResponseBody,
+        _meta: &ResponseMeta,
+    ) {
+    }
+
+    /// Inspects or rewrites a chunk of a streaming call.
+    fn on_chunk(
+        &self,
+        _chunk: &mut MessageChunk,
+    ) {
+    }
+}
+
+/// The action returned by [`Middleware::on_request_body`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MiddlewareAction {
+    /// Continues to the next middleware and sends the request.
+    Continue,
+    /// Skips sending the request and responds with the synthetic response.
+    ///
+    /// For a streaming call, the response is split into chunks.
+    Respond(MessagesResponseBody),
+}
+
+/// Runs the request body hooks of the middlewares.
+///
+/// Returns the synthetic response and the middlewares that have seen the request when short-circuited.
+pub(crate) fn run_request_body(
+    middlewares: &[Arc<dyn Middleware>],
+    request_body: &mut MessagesRequestBody,
+) -> Option<(MessagesResponseBody, Vec<Arc<dyn Middleware>>)> {
+    for (index, middleware) in middlewares.iter().enumerate() {
+        if let MiddlewareAction::Respond(response_body) =
+            middleware.on_request_body(request_body)
+        {
+            return Some((
+                response_body,
+                middlewares[..=index].to_vec(),
+            ));
+        }
+    }
+
+    None
+}
+
+/// Runs the request hooks of the middlewares.
+pub(crate) fn run_request(
+    middlewares: &[Arc<dyn Middleware>],
+    request: &mut TransportRequest,
+) {
+    for middleware in middlewares {
+        middleware.on_request(request);
+    }
+}
+
+/// Runs the response hooks of the middlewares in the reverse order.
+pub(crate) fn run_response(
+    middlewares: &[Arc<dyn Middleware>],
+    response_body: &mut MessagesResponseBody,
+    meta: &ResponseMeta,
+) {
+    for middleware in middlewares.iter().rev() {
+        middleware.on_response(response_body, meta);
+    }
+}
+
+/// The stream of chunks passing through the chunk hooks of the middlewares.
+pub(crate) struct MiddlewareStream<S> {
+    source: ChunkSource<S>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+/// The source of chunks.
+enum ChunkSource<S> {
+    /// Chunks from the response.
+    Response(S),
+    /// Chunks split from a synthetic response.
+    Synthetic(std::vec::IntoIter<MessageChunk>),
+}
+
+impl<S> MiddlewareStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    /// Creates a new stream of the response chunks.
+    pub(crate) fn new(
+        stream: S,
+        middlewares: Vec<Arc<dyn Middleware>>,
+    ) -> Self {
+        Self {
+            source: ChunkSource::Response(stream),
+            middlewares,
+        }
+    }
+
+    /// Creates a new stream of the chunks split from the synthetic response.
+    pub(crate) fn synthetic(
+        response_body: MessagesResponseBody,
+        middlewares: Vec<Arc<dyn Middleware>>,
+    ) -> Self {
+        Self {
+            source: ChunkSource::Synthetic(
+                MessageChunk::from_message(response_body).into_iter(),
+            ),
+            middlewares,
+        }
+    }
+}
+
+impl<S> Stream for MiddlewareStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    type Item = Result<MessageChunk, StreamError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        let item = match &mut this.source {
+            | ChunkSource::Response(stream) => {
+                match Pin::new(stream).poll_next(cx) {
+                    | Poll::Ready(item) => item,
+                    | Poll::Pending => return Poll::Pending,
+                }
+            },
+            | ChunkSource::Synthetic(chunks) => chunks.next().map(Ok),
+        };
+
+        Poll::Ready(item.map(|result| {
+            result.map(|mut chunk| {
+                for middleware in this.middlewares.iter().rev() {
+                    middleware.on_chunk(&mut chunk);
+                }
+                chunk
+            })
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        ContentBlockDelta, MessageChunkType, StopReason, TextDeltaContentBlock,
+    };
+    use std::sync::Mutex;
+
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        respond: bool,
+    }
+
+    impl Middleware for Recorder {
+        fn on_request_body(
+            &self,
+            _request_body: &mut MessagesRequestBody,
+        ) -> MiddlewareAction {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:request_body", self.name));
+
+            if self.respond {
+                MiddlewareAction::Respond(MessagesResponseBody {
+                    content: "synthetic".into(),
+                    stop_reason: Some(StopReason::EndTurn),
+                    ..Default::default()
+                })
+            } else {
+                MiddlewareAction::Continue
+            }
+        }
+
+        fn on_chunk(
+            &self,
+            _chunk: &mut MessageChunk,
+        ) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:chunk", self.name));
+        }
+    }
+
+    fn recorder(
+        name: &'static str,
+        log: &Arc<Mutex<Vec<String>>>,
+        respond: bool,
+    ) -> Arc<dyn Middleware> {
+        Arc::new(Recorder {
+            name,
+            log: log.clone(),
+            respond,
+        })
+    }
+
+    #[test]
+    fn short_circuit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let middlewares = vec![
+            recorder("first", &log, false),
+            recorder("second", &log, true),
+            recorder("third", &log, false),
+        ];
+
+        let (response_body, seen) = run_request_body(
+            &middlewares,
+            &mut MessagesRequestBody::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response_body
+                .content
+                .flatten_into_text()
+                .unwrap(),
+            "synthetic"
+        );
+        assert_eq!(seen.len(), 2);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["first:request_body", "second:request_body"]
+        );
+    }
+
+    #[tokio::test]
+    async fn synthetic_stream() {
+        use futures_util::StreamExt;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let stream = MiddlewareStream::<
+            futures_util::stream::Empty<Result<MessageChunk, StreamError>>,
+        >::synthetic(
+            MessagesResponseBody {
+                content: "synthetic".into(),
+                stop_reason: Some(StopReason::EndTurn),
+                ..Default::default()
+            },
+            vec![
+                recorder("first", &log, false),
+                recorder("second", &log, false),
+            ],
+        );
+
+        let chunks = stream
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(chunks.len(), 6);
+        assert!(matches!(
+            chunks[0],
+            MessageChunk::MessageStart(_)
+        ));
+        assert_eq!(
+            chunks[2],
+            MessageChunk::ContentBlockDelta(
+                crate::messages::ContentBlockDeltaChunk {
+                    _type: MessageChunkType::ContentBlockDelta,
+                    index: 0,
+                    delta: ContentBlockDelta::TextDeltaContentBlock(
+                        TextDeltaContentBlock::from("synthetic")
+                    ),
+                }
+            )
+        );
+        assert!(matches!(
+            chunks[5],
+            MessageChunk::MessageStop(_)
+        ));
+
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 12);
+        assert_eq!(log[0], "second:chunk");
+        assert_eq!(log[1], "first:chunk");
+    }
+}
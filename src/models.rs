@@ -0,0 +1,14 @@
+//! The [Models API](https://docs.anthropic.com/en/api/models-list) implementations.
+
+mod error;
+mod list_models_parameters;
+mod list_models_response_body;
+mod model_info;
+
+pub(crate) mod api;
+
+pub use error::ModelsError;
+pub use list_models_parameters::{ListModels, ListModelsParameters};
+pub use list_models_response_body::ListModelsResponseBody;
+pub use model_info::ModelInfo;
+pub use model_info::ModelObjectType;
@@ -0,0 +1,160 @@
+use http::Method;
+
+use crate::models::{
+    ListModelsParameters, ListModelsResponseBody, ModelInfo, ModelsError,
+};
+use crate::request::{call_json, encode_path_segment, with_query};
+use crate::{Client, RequestOptions};
+
+/// The path of the Models API.
+const MODELS_PATH: &str = "/v1/models";
+
+pub(crate) async fn list_models(
+    client: &Client,
+    parameters: &ListModelsParameters,
+    options: &RequestOptions,
+) -> Result<ListModelsResponseBody, ModelsError> {
+    let query = parameters.query();
+
+    call_json(
+        client,
+        "models.list",
+        MODELS_PATH,
+        |endpoint| {
+            client.request(Method::GET, &with_query(endpoint, &query), &[])
+        },
+        options,
+    )
+    .await
+}
+
+pub(crate) async fn get_model(
+    client: &Client,
+    model_id: &str,
+    options: &RequestOptions,
+) -> Result<ModelInfo, ModelsError> {
+    call_json(
+        client,
+        "models.get",
+        &format!(
+            "{MODELS_PATH}/{}",
+            encode_path_segment(model_id),
+        ),
+        |endpoint| client.request(Method::GET, endpoint, &[]),
+        options,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{StubResponse, StubServer};
+    use crate::{ApiErrorType, ApiKey, ClientBuilder};
+
+    const MODELS_RESPONSE: &str = r#"{"data":[{"type":"model","id":"claude-3-5-sonnet-20240620","display_name":"Claude 3.5 Sonnet","created_at":"2024-06-20T00:00:00Z"},{"type":"model","id":"claude-3-opus-20240229","display_name":"Claude 3 Opus","created_at":"2024-02-29T00:00:00Z"}],"has_more":true,"first_id":"claude-3-5-sonnet-20240620","last_id":"claude-3-opus-20240229"}"#;
+
+    #[tokio::test]
+    async fn list_models() {
+        let server =
+            StubServer::start(vec![StubResponse::json(200, MODELS_RESPONSE)])
+                .await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        let response = super::list_models(
+            &client,
+            &ListModelsParameters::new()
+                .after_id("claude-3-5-sonnet-20240620")
+                .limit(2),
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.data.len(), 2);
+        assert!(response.has_more);
+        assert_eq!(
+            response.last_id.as_deref(),
+            Some("claude-3-opus-20240229")
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "GET /v1/models?after_id=claude-3-5-sonnet-20240620&limit=2 HTTP/1.1"
+        );
+        assert_eq!(
+            requests[0].header("x-api-key"),
+            Some("api-key")
+        );
+    }
+
+    #[tokio::test]
+    async fn get_model() {
+        let server = StubServer::start(vec![
+            StubResponse::json(
+                200,
+                r#"{"type":"model","id":"claude-3-opus-20240229","display_name":"Claude 3 Opus","created_at":"2024-02-29T00:00:00Z"}"#,
+            ),
+            StubResponse::json(
+                404,
+                r#"{"type":"error","error":{"type":"not_found_error","message":"model: unknown"}}"#,
+            ),
+        ])
+        .await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        let model_info = super::get_model(
+            &client,
+            "claude-3-opus-20240229",
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(model_info.display_name, "Claude 3 Opus");
+        assert_eq!(
+            server.requests()[0].request_line,
+            "GET /v1/models/claude-3-opus-20240229 HTTP/1.1"
+        );
+
+        let error = super::get_model(
+            &client,
+            "unknown",
+            &RequestOptions::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            ModelsError::ApiError(error)
+                if error._type == ApiErrorType::NotFoundError
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_model_encodes_id() {
+        let server = StubServer::start(vec![StubResponse::json(
+            404,
+            r#"{"type":"error","error":{"type":"not_found_error","message":"model: unknown"}}"#,
+        )])
+        .await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        let _ = super::get_model(
+            &client,
+            "../files/file_01?x#y",
+            &RequestOptions::default(),
+        )
+        .await;
+        assert_eq!(
+            server.requests()[0].request_line,
+            "GET /v1/models/..%2Ffiles%2Ffile_01%3Fx%23y HTTP/1.1"
+        );
+    }
+}
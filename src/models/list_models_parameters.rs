@@ -0,0 +1,43 @@
+use crate::models::{ModelInfo, ModelsError};
+use crate::transport::BoxFuture;
+use crate::{Client, CursorParameters, Page, Paginate, RequestOptions};
+
+/// The query parameters to list models.
+///
+/// See also [List Models](https://docs.anthropic.com/en/api/models-list).
+///
+/// ## Example
+/// ```
+/// use clust::models::ListModelsParameters;
+///
+/// let parameters = ListModelsParameters::new()
+///     .after_id("claude-3-opus-20240229")
+///     .limit(10);
+/// ```
+pub type ListModelsParameters = CursorParameters<ListModels>;
+
+/// The marker of the list models endpoint for [`ListModelsParameters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ListModels;
+
+impl Paginate for ListModelsParameters {
+    type Item = ModelInfo;
+    type Error = ModelsError;
+
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>> {
+        Box::pin(async move {
+            crate::models::api::list_models(client, &self, &options).await
+        })
+    }
+
+    fn next_page(
+        self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self> {
+        CursorParameters::next_page(self, page)
+    }
+}
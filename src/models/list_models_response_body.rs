@@ -0,0 +1,7 @@
+use crate::models::ModelInfo;
+use crate::Page;
+
+/// The response body to list models, the most recently released first.
+///
+/// See also [List Models](https://docs.anthropic.com/en/api/models-list).
+pub type ListModelsResponseBody = Page<ModelInfo>;
@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter};
+
+use crate::macros::{
+    impl_display_for_serialize, impl_enum_string_serialization,
+};
+use crate::messages::ClaudeModel;
+
+/// The information of a model.
+///
+/// See also [the Models API](https://docs.anthropic.com/en/api/models).
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ModelInfo {
+    /// Unique model identifier.
+    pub id: String,
+    /// Object type.
+    ///
+    /// For Models, this is always "model".
+    #[serde(rename = "type")]
+    pub _type: ModelObjectType,
+    /// A human-readable name for the model.
+    pub display_name: String,
+    /// RFC 3339 datetime string representing the time at which the model was released.
+    pub created_at: String,
+}
+
+impl_display_for_serialize!(ModelInfo);
+
+impl ModelInfo {
+    /// Converts the model ID into the `ClaudeModel`, falling back to `ClaudeModel::Other` for unknown models.
+    pub fn claude_model(&self) -> ClaudeModel {
+        serde_json::from_value(serde_json::Value::String(self.id.clone()))
+            .unwrap_or_else(|_| ClaudeModel::Other(self.id.clone()))
+    }
+}
+
+impl From<ModelInfo> for ClaudeModel {
+    fn from(model_info: ModelInfo) -> Self {
+        model_info.claude_model()
+    }
+}
+
+/// The object type for model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelObjectType {
+    /// model
+    Model,
+}
+
+impl Default for ModelObjectType {
+    fn default() -> Self {
+        Self::Model
+    }
+}
+
+impl Display for ModelObjectType {
+    fn fmt(
+        &self,
+        f: &mut Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | ModelObjectType::Model => write!(f, "model"),
+        }
+    }
+}
+
+impl_enum_string_serialization!(
+    ModelObjectType,
+    Model => "model"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let model_info: ModelInfo = serde_json::from_str(
+            r#"{"type":"model","id":"claude-3-haiku-20240307","display_name":"Claude 3 Haiku","created_at":"2024-03-07T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            model_info,
+            ModelInfo {
+                id: "claude-3-haiku-20240307".to_string(),
+                _type: ModelObjectType::Model,
+                display_name: "Claude 3 Haiku".to_string(),
+                created_at: "2024-03-07T00:00:00Z".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn claude_model() {
+        let model_info = ModelInfo {
+            id: "claude-3-haiku-20240307".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            ClaudeModel::from(model_info),
+            ClaudeModel::Claude3Haiku20240307
+        );
+
+        let model_info = ModelInfo {
+            id: "claude-future-1-20300101".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            model_info.claude_model(),
+            ClaudeModel::Other("claude-future-1-20300101".to_string())
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! The pagination of the list endpoints.
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::transport::BoxFuture;
+use crate::{Client, RequestOptions};
+
+/// A page of a list endpoint.
+///
+/// Models, Message Batches, Files and the Admin API share this shape.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct Page<T> {
+    /// The objects of this page.
+    pub data: Vec<T>,
+    /// Indicates if there are more results in the requested page direction.
+    pub has_more: bool,
+    /// First ID in the data list. Can be used as the `before_id` for the previous page.
+    pub first_id: Option<String>,
+    /// Last ID in the data list. Can be used as the `after_id` for the next page.
+    pub last_id: Option<String>,
+}
+
+impl<T> Display for Page<T>
+where
+    T: serde::Serialize,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{json}")
+    }
+}
+
+/// The cursor parameters of a list endpoint marked by `E`.
+///
+/// The list endpoints alias this type with their marker,
+/// e.g. [`crate::models::ListModelsParameters`] and [`crate::admin::ListParameters`],
+/// and the listed objects are given by the [`Paginate`] implementation of the alias.
+///
+/// ## Example
+/// ```
+/// use clust::models::ListModelsParameters;
+///
+/// let parameters = ListModelsParameters::new()
+///     .after_id("claude-3-opus-20240229")
+///     .limit(10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CursorParameters<E = ()> {
+    /// ID of the object to use as a cursor for pagination, returning the page of results immediately before this object.
+    pub before_id: Option<String>,
+    /// ID of the object to use as a cursor for pagination, returning the page of results immediately after this object.
+    pub after_id: Option<String>,
+    /// Number of items to return per page, from 1 to 1000. Defaults to 20.
+    pub limit: Option<u32>,
+    _endpoint: PhantomData<E>,
+}
+
+impl<E> CursorParameters<E> {
+    /// Creates new empty parameters.
+    pub fn new() -> Self {
+        Self {
+            before_id: None,
+            after_id: None,
+            limit: None,
+            _endpoint: PhantomData,
+        }
+    }
+
+    /// Sets the cursor to return the page before the object.
+    pub fn before_id<S>(
+        mut self,
+        before_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Sets the cursor to return the page after the object.
+    pub fn after_id<S>(
+        mut self,
+        after_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Sets the number of items per page.
+    pub fn limit(
+        mut self,
+        limit: u32,
+    ) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Gets the query pairs.
+    pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(before_id) = &self.before_id {
+            query.push(("before_id", before_id.clone()));
+        }
+        if let Some(after_id) = &self.after_id {
+            query.push(("after_id", after_id.clone()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        query
+    }
+
+    /// Moves the cursors to the next page of the page in the requested page direction,
+    /// i.e. backward by the `before_id` when it is set, or forward by the `after_id`.
+    ///
+    /// Returns `None` when there is no next page.
+    pub(crate) fn next_page<U>(
+        mut self,
+        page: &Page<U>,
+    ) -> Option<Self> {
+        if !page.has_more {
+            return None;
+        }
+
+        let (cursor, id) = if self.before_id.is_some() {
+            (&mut self.before_id, &page.first_id)
+        } else {
+            (&mut self.after_id, &page.last_id)
+        };
+        *cursor = Some(id.clone()?);
+        Some(self)
+    }
+}
+
+/// The parameters of a list endpoint to be paginated by [`Client::paginate`].
+pub trait Paginate: Clone + Send + 'static {
+    /// The type of the listed objects.
+    type Item: Send + 'static;
+    /// The error of the list endpoint.
+    type Error: Send + 'static;
+
+    /// Gets a page with the parameters.
+    fn list(
+        self,
+        client: &Client,
+        options: RequestOptions,
+    ) -> BoxFuture<'_, Result<Page<Self::Item>, Self::Error>>;
+
+    /// Gets the parameters of the next page after the page, or `None` when there is no next page.
+    fn next_page(
+        self,
+        page: &Page<Self::Item>,
+    ) -> Option<Self>;
+}
+
+/// The future of a page of the paginated list endpoint.
+type PageFuture<'a, P> =
+    BoxFuture<'a, Result<Page<<P as Paginate>::Item>, <P as Paginate>::Error>>;
+
+/// The stream of the objects of a list endpoint, fetching the pages lazily.
+///
+/// The next page is fetched only when the objects of the current page are consumed,
+/// so dropping the stream stops the pagination.
+/// An error is yielded once and then the stream ends.
+pub struct PageStream<'a, P>
+where
+    P: Paginate,
+{
+    client: &'a Client,
+    options: RequestOptions,
+    next: Option<P>,
+    current: Option<P>,
+    pending: Option<PageFuture<'a, P>>,
+    items: VecDeque<P::Item>,
+    remaining: Option<usize>,
+}
+
+// No field is pinned structurally.
+impl<P> Unpin for PageStream<'_, P> where P: Paginate {}
+
+impl<'a, P> PageStream<'a, P>
+where
+    P: Paginate,
+{
+    /// Creates a new stream from the parameters of the first page.
+    pub(crate) fn new(
+        client: &'a Client,
+        parameters: P,
+        options: RequestOptions,
+    ) -> Self {
+        Self {
+            client,
+            options,
+            next: Some(parameters),
+            current: None,
+            pending: None,
+            items: VecDeque::new(),
+            remaining: None,
+        }
+    }
+
+    /// Sets the maximum number of objects to yield across pages.
+    ///
+    /// No more page is fetched after the limit is reached.
+    pub fn limit(
+        mut self,
+        limit: usize,
+    ) -> Self {
+        self.remaining = Some(limit);
+        self
+    }
+}
+
+impl<P> Stream for PageStream<'_, P>
+where
+    P: Paginate,
+{
+    type Item = Result<P::Item, P::Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.remaining == Some(0) {
+                return Poll::Ready(None);
+            }
+
+            if let Some(item) = this.items.pop_front() {
+                if let Some(remaining) = this.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if this.pending.is_none() {
+                let Some(parameters) = this.next.take() else {
+                    return Poll::Ready(None);
+                };
+                this.current = Some(parameters.clone());
+                this.pending = Some(parameters.list(
+                    this.client,
+                    this.options.clone(),
+                ));
+            }
+
+            let Some(pending) = this.pending.as_mut() else {
+                return Poll::Ready(None);
+            };
+            let result = match pending.as_mut().poll(cx) {
+                | Poll::Ready(result) => result,
+                | Poll::Pending => return Poll::Pending,
+            };
+            this.pending = None;
+
+            match result {
+                | Ok(page) => {
+                    this.next = this
+                        .current
+                        .take()
+                        .and_then(|parameters| parameters.next_page(&page));
+                    this.items.extend(page.data);
+                },
+                | Err(error) => {
+                    this.current = None;
+                    return Poll::Ready(Some(Err(error)));
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ListModelsParameters, ModelsError};
+    use crate::test_server::{StubResponse, StubServer};
+    use crate::{ApiKey, ClientBuilder};
+    use futures_util::StreamExt;
+
+    fn model(id: &str) -> String {
+        format!(
+            r#"{{"type":"model","id":"{id}","display_name":"{id}","created_at":"2024-10-22T00:00:00Z"}}"#
+        )
+    }
+
+    fn page(
+        ids: &[&str],
+        has_more: bool,
+    ) -> StubResponse {
+        let data = ids
+            .iter()
+            .map(|id| model(id))
+            .collect::<Vec<_>>()
+            .join(",");
+        StubResponse::json(
+            200,
+            format!(
+                r#"{{"data":[{data}],"has_more":{has_more},"first_id":"{}","last_id":"{}"}}"#,
+                ids[0],
+                ids[ids.len() - 1],
+            ),
+        )
+    }
+
+    async fn client(responses: Vec<StubResponse>) -> (Client, StubServer) {
+        let server = StubServer::start(responses).await;
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .base_url(server.url(""))
+            .build();
+
+        (client, server)
+    }
+
+    #[test]
+    fn next_page() {
+        let page = Page::<()> {
+            data: vec![],
+            has_more: true,
+            first_id: Some("first".to_string()),
+            last_id: Some("last".to_string()),
+        };
+
+        let parameters = CursorParameters::<()>::new()
+            .next_page(&page)
+            .unwrap();
+        assert_eq!(parameters.before_id, None);
+        assert_eq!(parameters.after_id, Some("last".to_string()));
+
+        let parameters = CursorParameters::<()>::new()
+            .before_id("cursor")
+            .next_page(&page)
+            .unwrap();
+        assert_eq!(parameters.before_id, Some("first".to_string()));
+
+        let last_page = Page::<()> {
+            has_more: false,
+            ..page
+        };
+        assert!(CursorParameters::<()>::new()
+            .next_page(&last_page)
+            .is_none());
+    }
+
+    #[test]
+    fn query() {
+        let parameters = CursorParameters::<()>::new()
+            .before_id("before")
+            .after_id("after")
+            .limit(10);
+
+        assert_eq!(
+            parameters.query(),
+            [
+                ("before_id", "before".to_string()),
+                ("after_id", "after".to_string()),
+                ("limit", "10".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn paginate_all_pages() {
+        let (client, server) = client(vec![
+            page(&["a", "b"], true),
+            page(&["c"], false),
+        ])
+        .await;
+
+        let ids = client
+            .paginate(ListModelsParameters::new().limit(2))
+            .map(|model| model.unwrap().id)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(ids, ["a", "b", "c"]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].request_line,
+            "GET /v1/models?limit=2 HTTP/1.1"
+        );
+        assert_eq!(
+            requests[1].request_line,
+            "GET /v1/models?after_id=b&limit=2 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn limit_stops_fetching() {
+        let (client, server) = client(vec![
+            page(&["a", "b"], true),
+            page(&["c", "d"], true),
+        ])
+        .await;
+
+        let ids = client
+            .paginate(ListModelsParameters::new())
+            .limit(3)
+            .map(|model| model.unwrap().id)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn lazy_pages() {
+        let (client, server) = client(vec![
+            page(&["a", "b"], true),
+            page(&["c"], false),
+        ])
+        .await;
+
+        let mut models = client.paginate(ListModelsParameters::new());
+        assert_eq!(models.next().await.unwrap().unwrap().id, "a");
+        assert_eq!(models.next().await.unwrap().unwrap().id, "b");
+        drop(models);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn error_ends_stream() {
+        let (client, _server) = client(vec![
+            page(&["a"], true),
+            StubResponse::json(
+                400,
+                r#"{"type":"error","error":{"type":"invalid_request_error","message":"invalid cursor"}}"#,
+            ),
+        ])
+        .await;
+
+        let results = client
+            .paginate(ListModelsParameters::new())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(ModelsError::ApiError(_))
+        ));
+    }
+}
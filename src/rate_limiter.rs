@@ -0,0 +1,469 @@
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+use crate::messages::{
+    MessageChunk, MessagesRequestBody, StreamError, Usage,
+};
+use crate::RateLimitInfo;
+
+/// The approximate number of bytes of the request body per input token.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// The client-side rate limiter of API calling.
+///
+/// The limiter caps the number of in-flight requests and enforces the budgets of requests and tokens per minute by token buckets.
+///
+/// The token cost of a request is estimated from `MaxTokens` and the size of the request body before sending,
+/// then reconciled with the `Usage` of the response.
+/// The remaining budgets are also lowered when the `anthropic-ratelimit-*` headers of a response report less capacity.
+///
+/// Clones of a limiter share the same budgets, so that a limiter can be shared by multiple clients.
+///
+/// ## Example
+/// ```
+/// use clust::{ApiKey, ClientBuilder, RateLimiter};
+///
+/// let rate_limiter = RateLimiter::new()
+///     .max_concurrency(8)
+///     .requests_per_minute(50)
+///     .tokens_per_minute(40_000);
+///
+/// let client = ClientBuilder::new(ApiKey::new("api-key"))
+///     .rate_limiter(rate_limiter)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    /// The slots of in-flight requests.
+    concurrency: Option<Arc<Semaphore>>,
+    /// The budget of requests.
+    requests: Option<Arc<Mutex<TokenBucket>>>,
+    /// The budget of tokens.
+    tokens: Option<Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of in-flight requests.
+    ///
+    /// A streaming request is in flight until the stream is finished or dropped.
+    ///
+    /// A value less than `1` is treated as `1`.
+    pub fn max_concurrency(
+        mut self,
+        max_concurrency: usize,
+    ) -> Self {
+        self.concurrency = Some(Arc::new(Semaphore::new(
+            max_concurrency.max(1),
+        )));
+        self
+    }
+
+    /// Sets the budget of requests per minute including retries.
+    pub fn requests_per_minute(
+        mut self,
+        requests_per_minute: u32,
+    ) -> Self {
+        self.requests = Some(Arc::new(Mutex::new(
+            TokenBucket::per_minute(requests_per_minute),
+        )));
+        self
+    }
+
+    /// Sets the budget of input and output tokens per minute.
+    pub fn tokens_per_minute(
+        mut self,
+        tokens_per_minute: u32,
+    ) -> Self {
+        self.tokens = Some(Arc::new(Mutex::new(
+            TokenBucket::per_minute(tokens_per_minute),
+        )));
+        self
+    }
+
+    /// Estimates the number of tokens of the request from the maximum tokens and the size of the request body.
+    pub(crate) fn estimate_tokens(request_body: &MessagesRequestBody) -> u32 {
+        let input_bytes = serde_json::to_vec(request_body)
+            .map(|body| body.len())
+            .unwrap_or_default();
+        let input_tokens = (input_bytes / BYTES_PER_TOKEN)
+            .try_into()
+            .unwrap_or(u32::MAX);
+
+        request_body
+            .max_tokens
+            .value()
+            .saturating_add(input_tokens)
+    }
+
+    /// Waits for a slot of in-flight requests and the budgets of a request and the estimated tokens.
+    pub(crate) async fn acquire(
+        &self,
+        estimated_tokens: u32,
+    ) -> RateLimitPermit {
+        let concurrency = match &self.concurrency {
+            | Some(semaphore) => semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .ok(),
+            | None => None,
+        };
+
+        self.acquire_request().await;
+
+        if let Some(tokens) = &self.tokens {
+            take(tokens, estimated_tokens).await;
+        }
+
+        RateLimitPermit {
+            _concurrency: concurrency,
+            tokens: self.tokens.clone(),
+            estimated_tokens,
+        }
+    }
+
+    /// Waits for the budget of a request, e.g. before retrying.
+    pub(crate) async fn acquire_request(&self) {
+        if let Some(requests) = &self.requests {
+            take(requests, 1).await;
+        }
+    }
+
+    /// Lowers the remaining budgets to the capacities reported by the server.
+    pub(crate) fn observe(
+        &self,
+        rate_limit: &RateLimitInfo,
+    ) {
+        if let (Some(requests), Some(remaining)) =
+            (&self.requests, rate_limit.requests.remaining)
+        {
+            lock(requests).lower(remaining);
+        }
+
+        if let (Some(tokens), Some(remaining)) =
+            (&self.tokens, rate_limit.tokens.remaining)
+        {
+            lock(tokens).lower(remaining);
+        }
+    }
+}
+
+/// The permit of a request acquired from the rate limiter.
+///
+/// The slot of in-flight requests is released when the permit is dropped,
+/// and the estimated tokens are given back if the permit is not reconciled, e.g. on an error.
+pub(crate) struct RateLimitPermit {
+    _concurrency: Option<OwnedSemaphorePermit>,
+    tokens: Option<Arc<Mutex<TokenBucket>>>,
+    estimated_tokens: u32,
+}
+
+impl RateLimitPermit {
+    /// Reconciles the estimated tokens with the actual usage.
+    pub(crate) fn reconcile(
+        mut self,
+        usage: &Usage,
+    ) {
+        if let Some(tokens) = self.tokens.take() {
+            let actual_tokens = usage.input_tokens as f64
+                + usage.output_tokens as f64
+                + usage
+                    .cache_read_input_tokens
+                    .unwrap_or_default() as f64
+                + usage
+                    .cache_creation_input_tokens
+                    .unwrap_or_default() as f64;
+
+            lock(&tokens).give_back(self.estimated_tokens as f64 - actual_tokens);
+        }
+    }
+}
+
+impl Drop for RateLimitPermit {
+    fn drop(&mut self) {
+        if let Some(tokens) = self.tokens.take() {
+            lock(&tokens).give_back(self.estimated_tokens as f64);
+        }
+    }
+}
+
+/// The stream of chunks holding the permit until the end of the stream.
+///
+/// The permit is reconciled with the usage received so far if the stream is dropped before the end.
+pub(crate) struct RateLimitedStream<S> {
+    stream: S,
+    permit: Option<RateLimitPermit>,
+    usage: Usage,
+}
+
+impl<S> RateLimitedStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    /// Creates a new stream holding the permit.
+    pub(crate) fn new(
+        stream: S,
+        permit: Option<RateLimitPermit>,
+    ) -> Self {
+        Self {
+            stream,
+            permit,
+            usage: Usage::default(),
+        }
+    }
+}
+
+impl<S> Stream for RateLimitedStream<S>
+where
+    S: Stream<Item = Result<MessageChunk, StreamError>> + Unpin,
+{
+    type Item = Result<MessageChunk, StreamError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.stream).poll_next(cx);
+
+        match &poll {
+            | Poll::Ready(Some(Ok(MessageChunk::MessageStart(
+                message_start,
+            )))) => {
+                self.usage = message_start.message.usage;
+            },
+            | Poll::Ready(Some(Ok(MessageChunk::MessageDelta(
+                message_delta,
+            )))) => {
+                self.usage.output_tokens = message_delta.usage.output_tokens;
+            },
+            | Poll::Ready(Some(Ok(MessageChunk::MessageStop(_))))
+            | Poll::Ready(None) => {
+                if let Some(permit) = self.permit.take() {
+                    permit.reconcile(&self.usage);
+                }
+            },
+            | _ => {},
+        }
+
+        poll
+    }
+}
+
+impl<S> Drop for RateLimitedStream<S> {
+    fn drop(&mut self) {
+        if let Some(permit) = self.permit.take() {
+            if self.usage != Usage::default() {
+                permit.reconcile(&self.usage);
+            }
+        }
+    }
+}
+
+/// A token bucket refilled continuously up to the capacity.
+#[derive(Debug)]
+struct TokenBucket {
+    /// The maximum number of tokens.
+    capacity: f64,
+    /// The number of available tokens, which may be negative after reconciliation.
+    available: f64,
+    /// The number of tokens refilled per second.
+    refill_per_second: f64,
+    /// The time of the last refill.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket refilled by the capacity per minute.
+    fn per_minute(capacity: u32) -> Self {
+        let capacity = capacity.max(1) as f64;
+
+        Self {
+            capacity,
+            available: capacity,
+            refill_per_second: capacity / 60.0,
+            refilled_at: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now
+            .duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_second)
+            .min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Takes the amount of tokens, or returns the time to wait until they are available.
+    ///
+    /// An amount greater than the capacity is treated as the capacity.
+    fn try_take(
+        &mut self,
+        amount: f64,
+    ) -> Result<(), Duration> {
+        self.refill();
+
+        let amount = amount.min(self.capacity);
+        if self.available >= amount {
+            self.available -= amount;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (amount - self.available) / self.refill_per_second,
+            ))
+        }
+    }
+
+    /// Gives back the amount of tokens, or takes them if negative.
+    fn give_back(
+        &mut self,
+        amount: f64,
+    ) {
+        self.refill();
+        self.available = (self.available + amount).min(self.capacity);
+    }
+
+    /// Lowers the available tokens to the remaining capacity.
+    fn lower(
+        &mut self,
+        remaining: u64,
+    ) {
+        self.refill();
+        self.available = self
+            .available
+            .min(remaining as f64);
+    }
+}
+
+fn lock(bucket: &Mutex<TokenBucket>) -> std::sync::MutexGuard<'_, TokenBucket> {
+    bucket
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Waits until the amount of tokens is taken from the bucket.
+async fn take(
+    bucket: &Mutex<TokenBucket>,
+    amount: u32,
+) {
+    loop {
+        let result = lock(bucket).try_take(amount as f64);
+        match result {
+            | Ok(()) => return,
+            | Err(wait) => tokio::time::sleep(wait).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RateLimit;
+
+    #[tokio::test(start_paused = true)]
+    async fn requests_per_minute() {
+        let rate_limiter = RateLimiter::new().requests_per_minute(2);
+
+        let started_at = Instant::now();
+        rate_limiter.acquire(0).await;
+        rate_limiter.acquire(0).await;
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
+
+        rate_limiter.acquire(0).await;
+        assert_eq!(started_at.elapsed(), Duration::from_secs(30));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tokens_per_minute_reconciled() {
+        let rate_limiter = RateLimiter::new().tokens_per_minute(600);
+
+        let started_at = Instant::now();
+        let permit = rate_limiter.acquire(500).await;
+        permit.reconcile(&Usage {
+            input_tokens: 50,
+            output_tokens: 50,
+            ..Default::default()
+        });
+
+        // 500 tokens are available after reconciliation.
+        let _permit = rate_limiter.acquire(500).await;
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
+
+        // Waits 10 seconds for 100 tokens refilled at 10 tokens per second.
+        rate_limiter.acquire(100).await;
+        assert_eq!(started_at.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn tokens_given_back_on_drop() {
+        let rate_limiter = RateLimiter::new().tokens_per_minute(600);
+
+        let started_at = Instant::now();
+        drop(rate_limiter.acquire(500).await);
+        rate_limiter.acquire(500).await;
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_concurrency() {
+        let rate_limiter = RateLimiter::new().max_concurrency(1);
+
+        let permit = rate_limiter.acquire(0).await;
+        assert!(tokio::time::timeout(
+            Duration::from_secs(1),
+            rate_limiter.acquire(0)
+        )
+        .await
+        .is_err());
+
+        drop(permit);
+        assert!(tokio::time::timeout(
+            Duration::from_secs(1),
+            rate_limiter.acquire(0)
+        )
+        .await
+        .is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn observe_lower_remaining() {
+        let rate_limiter = RateLimiter::new().requests_per_minute(60);
+
+        rate_limiter.observe(&RateLimitInfo {
+            requests: RateLimit {
+                remaining: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let started_at = Instant::now();
+        rate_limiter.acquire(0).await;
+        assert_eq!(started_at.elapsed(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn estimate_tokens() {
+        let request_body = MessagesRequestBody::default();
+        let input_bytes = serde_json::to_vec(&request_body)
+            .unwrap()
+            .len() as u32;
+
+        assert_eq!(
+            RateLimiter::estimate_tokens(&request_body),
+            4096 + input_bytes / 4
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! Internal sending of API requests shared by the APIs.
+
+use std::future::Future;
+use std::time::Duration;
+
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+
+use crate::instrument::CallSpan;
+use crate::middleware;
+use crate::transport::{
+    TransportError, TransportErrorKind, TransportRequest, TransportResponse,
+};
+use crate::{
+    ApiError, Client, ClientError, RateLimitInfo, RequestOptions, RetryPolicy,
+};
+
+/// The error of sending a request, converted into the error of each API.
+#[derive(Debug)]
+pub(crate) enum SendError {
+    /// The client error.
+    ClientError(ClientError),
+    /// The API error.
+    ApiError(ApiError),
+    /// Timed out connecting to the server.
+    ConnectTimeout(TransportError),
+    /// Timed out until the response headers are received.
+    HeadersTimeout(Duration),
+}
+
+impl From<ClientError> for SendError {
+    fn from(error: ClientError) -> Self {
+        Self::ClientError(error)
+    }
+}
+
+impl From<ApiError> for SendError {
+    fn from(error: ApiError) -> Self {
+        Self::ApiError(error)
+    }
+}
+
+/// The error of an API call that can be created from the errors of sending a request.
+pub(crate) trait CallError:
+    From<SendError> + From<ClientError> + std::fmt::Display
+{
+    /// Creates the error of timing out the whole call.
+    fn timeout(timeout: Duration) -> Self;
+}
+
+/// Implements `From<SendError>` and [`CallError`] for the error type of an API,
+/// which has the `ClientError`, `ApiError`, `ConnectTimeout`, `HeadersTimeout` and `Timeout` variants.
+macro_rules! impl_call_error {
+    ($error:ident) => {
+        impl From<$crate::request::SendError> for $error {
+            fn from(error: $crate::request::SendError) -> Self {
+                match error {
+                    | $crate::request::SendError::ClientError(error) => {
+                        Self::ClientError(error)
+                    },
+                    | $crate::request::SendError::ApiError(error) => {
+                        Self::ApiError(error)
+                    },
+                    | $crate::request::SendError::ConnectTimeout(error) => {
+                        Self::ConnectTimeout(error)
+                    },
+                    | $crate::request::SendError::HeadersTimeout(timeout) => {
+                        Self::HeadersTimeout(timeout)
+                    },
+                }
+            }
+        }
+
+        impl $crate::request::CallError for $error {
+            fn timeout(timeout: std::time::Duration) -> Self {
+                Self::Timeout(timeout)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_call_error;
+
+/// Calls the API and reads the successful response body as JSON in the span of the operation, e.g. `models.list`.
+pub(crate) async fn call_json<T, E, F>(
+    client: &Client,
+    operation: &'static str,
+    path: &str,
+    build_request: F,
+    options: &RequestOptions,
+) -> Result<T, E>
+where
+    T: DeserializeOwned,
+    E: CallError,
+    F: Fn(&str) -> Result<TransportRequest, ClientError>,
+{
+    let span = CallSpan::api(operation);
+    let call = with_timeout(options.timeout, async {
+        let response =
+            send_request(client, path, build_request, options, &span).await?;
+
+        Ok(read_json(response).await?)
+    });
+
+    match span.instrument(call).await {
+        | Ok(result) => result,
+        | Err(timeout) => Err(E::timeout(timeout)),
+    }
+    .inspect_err(|error| {
+        span.record_error(error);
+    })
+}
+
+/// Sends the request and returns the successful response,
+/// retrying by the retry policy of the client.
+///
+/// The request is built by `build_request` from the endpoint for each attempt,
+/// where the endpoint is the path on the base URL or the endpoint of the options.
+pub(crate) async fn send_request<F>(
+    client: &Client,
+    path: &str,
+    build_request: F,
+    options: &RequestOptions,
+    span: &CallSpan,
+) -> Result<TransportResponse, SendError>
+where
+    F: Fn(&str) -> Result<TransportRequest, ClientError>,
+{
+    let endpoint = options.resolve_endpoint(|| client.url(path));
+    let retry_policy = client.retry_policy();
+    let max_failovers = client.max_failovers();
+    let mut attempt = 1;
+    let mut failovers = 0;
+
+    loop {
+        // Retries also consume the budget of requests.
+        if attempt > 1 {
+            if let Some(rate_limiter) = client.rate_limiter() {
+                rate_limiter.acquire_request().await;
+            }
+        }
+
+        let mut request = build_request(&endpoint)?;
+        let credentials = client.authorize(&mut request).await?;
+        middleware::run_request(client.middlewares(), &mut request);
+        let idempotent = request.method.is_idempotent();
+
+        // Send the request.
+        let response = match with_timeout(
+            options.headers_timeout,
+            client.transport().send(request),
+        )
+        .await
+        {
+            | Ok(Ok(response)) => response,
+            | Ok(Err(error)) => {
+                if retry_policy.can_retry(attempt)
+                    && RetryPolicy::is_retryable_error(&error, idempotent)
+                {
+                    let delay = retry_policy.delay(attempt, None);
+                    span.record_retry(attempt, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                if error.kind() == TransportErrorKind::ConnectTimeout {
+                    return Err(SendError::ConnectTimeout(error));
+                }
+
+                return Err(ClientError::HttpRequestError(error).into());
+            },
+            | Err(timeout) => {
+                // The server may have processed a non-idempotent request, e.g. billed a generation.
+                if idempotent && retry_policy.can_retry(attempt) {
+                    let delay = retry_policy.delay(attempt, None);
+                    span.record_retry(attempt, delay);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+
+                return Err(SendError::HeadersTimeout(timeout));
+            },
+        };
+
+        // Check the response status code.
+        let status_code = response.status;
+        span.record_response(
+            status_code,
+            response
+                .headers
+                .get("request-id")
+                .and_then(|value| value.to_str().ok()),
+        );
+        if let Some(rate_limiter) = client.rate_limiter() {
+            rate_limiter.observe(&RateLimitInfo::from_headers(&response.headers));
+        }
+
+        // Ok
+        if status_code.is_success() {
+            return Ok(response);
+        }
+
+        // Error
+        let retry_after = crate::retry::retry_after(&response.headers);
+        let error = read_error_response(status_code, response).await?;
+
+        // Fail over to other credentials immediately.
+        if client.report_credential_error(&credentials, &error, retry_after)
+            && failovers < max_failovers
+        {
+            span.record_retry(attempt, Duration::ZERO);
+            attempt += 1;
+            failovers += 1;
+            continue;
+        }
+
+        if retry_policy.can_retry(attempt)
+            && RetryPolicy::is_retryable_status(status_code)
+        {
+            let delay = retry_policy.delay(attempt, retry_after);
+            span.record_retry(attempt, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+            continue;
+        }
+
+        return Err(error.into());
+    }
+}
+
+/// Runs the future with the timeout if specified,
+/// returning the timeout as an error when it has elapsed.
+pub(crate) async fn with_timeout<F>(
+    timeout: Option<Duration>,
+    future: F,
+) -> Result<F::Output, Duration>
+where
+    F: Future,
+{
+    match timeout {
+        | Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| timeout),
+        | None => Ok(future.await),
+    }
+}
+
+/// Reads the error response of the API.
+async fn read_error_response(
+    status_code: StatusCode,
+    response: TransportResponse,
+) -> Result<ApiError, ClientError> {
+    let request_id = crate::response_meta::request_id(&response.headers);
+
+    // Read the response text.
+    let response_text = response
+        .text()
+        .await
+        .map_err(ClientError::ReadResponseTextFailed)?;
+
+    // Deserialize the error response.
+    let error_response =
+        serde_json::from_str(&response_text).map_err(|error| {
+            ClientError::ErrorResponseDeserializationFailed {
+                error,
+                text: response_text,
+            }
+        })?;
+
+    Ok(ApiError::new(
+        status_code,
+        error_response,
+        request_id,
+    ))
+}
+
+/// Appends the query parameters to the endpoint.
+pub(crate) fn with_query(
+    endpoint: &str,
+    query: &[(&str, String)],
+) -> String {
+    if query.is_empty() {
+        return endpoint.to_string();
+    }
+
+    match reqwest::Url::parse_with_params(endpoint, query) {
+        | Ok(url) => url.to_string(),
+        | Err(_) => endpoint.to_string(),
+    }
+}
+
+/// Percent-encodes the path segment, e.g. an ID given by the caller,
+/// so that `/`, `?`, `#` and dot segments do not change the endpoint.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    if segment == "." || segment == ".." {
+        return segment.replace('.', "%2E");
+    }
+
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            | b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_'
+            | b'~' => (byte as char).to_string(),
+            | _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Reads the response body as JSON.
+pub(crate) async fn read_json<T>(
+    response: TransportResponse
+) -> Result<T, ClientError>
+where
+    T: DeserializeOwned,
+{
+    // Read the response text.
+    let response_text = response
+        .text()
+        .await
+        .map_err(ClientError::ReadResponseTextFailed)?;
+
+    // Deserialize the response.
+    serde_json::from_str(&response_text).map_err(|error| {
+        ClientError::ResponseDeserializationFailed {
+            error,
+            text: response_text,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::{BoxFuture, Transport};
+    use crate::{ApiKey, ClientBuilder};
+    use http::Method;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// The transport failing every attempt with the error kind,
+    /// or never responding without the error kind.
+    struct FailingTransport {
+        kind: Option<TransportErrorKind>,
+        attempts: AtomicU32,
+    }
+
+    impl Transport for FailingTransport {
+        fn send(
+            &self,
+            _request: TransportRequest,
+        ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+            self.attempts
+                .fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async {
+                match self.kind {
+                    | Some(kind) => Err(TransportError::new(kind, "error")),
+                    | None => std::future::pending().await,
+                }
+            })
+        }
+    }
+
+    /// Sends a request with the method to the transport retrying up to 3 attempts,
+    /// and returns the result and the number of attempts.
+    async fn send(
+        kind: Option<TransportErrorKind>,
+        method: Method,
+        options: &RequestOptions,
+    ) -> (Result<TransportResponse, SendError>, u32) {
+        let transport = Arc::new(FailingTransport {
+            kind,
+            attempts: AtomicU32::new(0),
+        });
+        let client = ClientBuilder::new(ApiKey::new("api-key"))
+            .transport(transport.clone())
+            .retry_policy(
+                RetryPolicy::new()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::ZERO)
+                    .jitter(false),
+            )
+            .build();
+
+        let result = send_request(
+            &client,
+            "/v1/messages",
+            |endpoint| client.request(method.clone(), endpoint, &[]),
+            options,
+            &CallSpan::api("test"),
+        )
+        .await;
+
+        (
+            result,
+            transport
+                .attempts
+                .load(Ordering::SeqCst),
+        )
+    }
+
+    /// Sends a request failing with the transport error, and returns the number of attempts.
+    async fn attempts(
+        kind: TransportErrorKind,
+        method: Method,
+    ) -> u32 {
+        let (result, attempts) =
+            send(Some(kind), method, &RequestOptions::default()).await;
+        assert!(matches!(
+            result,
+            Err(SendError::ClientError(ClientError::HttpRequestError(error)))
+                if error.kind() == kind
+        ));
+
+        attempts
+    }
+
+    #[test]
+    fn encode_path_segment() {
+        assert_eq!(
+            super::encode_path_segment("msgbatch_01-A.b~c"),
+            "msgbatch_01-A.b~c"
+        );
+        assert_eq!(
+            super::encode_path_segment("a/b?c#d e%"),
+            "a%2Fb%3Fc%23d%20e%25"
+        );
+        assert_eq!(super::encode_path_segment(".."), "%2E%2E");
+    }
+
+    #[tokio::test]
+    async fn transport_timeout_of_post_is_not_retried() {
+        assert_eq!(
+            attempts(TransportErrorKind::Timeout, Method::POST).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn transport_timeout_of_get_is_retried() {
+        assert_eq!(
+            attempts(TransportErrorKind::Timeout, Method::GET).await,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_error_of_post_is_retried() {
+        assert_eq!(
+            attempts(TransportErrorKind::Connect, Method::POST).await,
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn other_error_is_not_retried() {
+        // e.g. a request that the provider transport fails to prepare.
+        assert_eq!(
+            attempts(TransportErrorKind::Other, Method::GET).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_timeout_is_retried_and_surfaced() {
+        let (result, attempts) = send(
+            Some(TransportErrorKind::ConnectTimeout),
+            Method::POST,
+            &RequestOptions::default(),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SendError::ConnectTimeout(error))
+                if error.kind() == TransportErrorKind::ConnectTimeout
+        ));
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn headers_timeout_of_get_is_retried() {
+        let (result, attempts) = send(
+            None,
+            Method::GET,
+            &RequestOptions::new().headers_timeout(Duration::from_secs(10)),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SendError::HeadersTimeout(timeout))
+                if timeout == Duration::from_secs(10)
+        ));
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn headers_timeout_of_post_is_not_retried() {
+        let (result, attempts) = send(
+            None,
+            Method::POST,
+            &RequestOptions::new().headers_timeout(Duration::from_secs(10)),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SendError::HeadersTimeout(_))
+        ));
+        // The generation may have been billed.
+        assert_eq!(attempts, 1);
+    }
+}
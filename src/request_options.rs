@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+/// The per-request options of API calling.
+///
+/// Options that are not specified fall back to the configuration of the client.
+///
+/// ## Example
+/// ```
+/// use std::time::Duration;
+/// use clust::RequestOptions;
+///
+/// let options = RequestOptions::new()
+///     .endpoint("https://gateway.example.com/anthropic/v1/messages")
+///     .headers_timeout(Duration::from_secs(10))
+///     .timeout(Duration::from_secs(60))
+///     .first_chunk_timeout(Duration::from_secs(30))
+///     .idle_timeout(Duration::from_secs(15));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestOptions {
+    /// The full URL overriding the one composed from the base URL of the client.
+    endpoint: Option<String>,
+    /// The timeout of each attempt until the response headers are received.
+    pub(crate) headers_timeout: Option<Duration>,
+    /// The timeout of the whole call.
+    pub(crate) timeout: Option<Duration>,
+    /// The timeout until the first chunk of a stream is received.
+    pub(crate) first_chunk_timeout: Option<Duration>,
+    /// The timeout between chunks of a stream.
+    pub(crate) idle_timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+    /// Creates new request options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the full URL of the endpoint overriding the one composed from the base URL of the client.
+    pub fn endpoint<S>(
+        mut self,
+        endpoint: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the timeout of each attempt from sending the request until the response headers are received,
+    /// which includes connecting to the server and, for a non-streaming call, generating the whole response.
+    ///
+    /// A timed out attempt of an idempotent request, e.g. `GET`, is retried by the retry policy of the client.
+    /// A timed out `POST` request is not retried because the server may have processed it.
+    /// `MessagesError::HeadersTimeout` is returned when the attempt is not retried.
+    ///
+    /// The timeout of connecting to the server alone is set by `ClientBuilder::connect_timeout`.
+    pub fn headers_timeout(
+        mut self,
+        headers_timeout: Duration,
+    ) -> Self {
+        self.headers_timeout = Some(headers_timeout);
+        self
+    }
+
+    /// Sets the timeout of the whole call including retries.
+    ///
+    /// For a streaming call, this covers until the stream is returned, not the stream itself.
+    ///
+    /// `MessagesError::Timeout` is returned when the call times out.
+    pub fn timeout(
+        mut self,
+        timeout: Duration,
+    ) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout of a stream waiting for the first chunk.
+    ///
+    /// `StreamError::FirstChunkTimeout` is yielded when the stream times out.
+    pub fn first_chunk_timeout(
+        mut self,
+        first_chunk_timeout: Duration,
+    ) -> Self {
+        self.first_chunk_timeout = Some(first_chunk_timeout);
+        self
+    }
+
+    /// Sets the timeout of a stream waiting for the next chunk after receiving a chunk.
+    ///
+    /// `StreamError::IdleTimeout` is yielded when the stream times out.
+    pub fn idle_timeout(
+        mut self,
+        idle_timeout: Duration,
+    ) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Resolves the URL of the request from the overriding endpoint or the default URL.
+    pub(crate) fn resolve_endpoint<F>(
+        &self,
+        default: F,
+    ) -> String
+    where
+        F: FnOnce() -> String,
+    {
+        self.endpoint
+            .clone()
+            .unwrap_or_else(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_endpoint() {
+        let options = RequestOptions::new();
+        assert_eq!(
+            options.resolve_endpoint(|| "https://default".to_string()),
+            "https://default"
+        );
+
+        let options = RequestOptions::new().endpoint("https://override");
+        assert_eq!(
+            options.resolve_endpoint(|| "https://default".to_string()),
+            "https://override"
+        );
+    }
+}
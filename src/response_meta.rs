@@ -0,0 +1,207 @@
+use http::HeaderMap;
+
+use crate::macros::impl_display_for_serialize;
+
+/// The metadata of an API response read from the response headers.
+///
+/// See also [rate limits](https://docs.anthropic.com/en/api/rate-limits#response-headers).
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct ResponseMeta {
+    /// The globally unique identifier of the request from the `request-id` header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// The rate limit information from the `anthropic-ratelimit-*` headers.
+    pub rate_limit: RateLimitInfo,
+}
+
+impl_display_for_serialize!(ResponseMeta);
+
+impl ResponseMeta {
+    /// Reads the metadata from the response headers.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            request_id: request_id(headers),
+            rate_limit: RateLimitInfo::from_headers(headers),
+        }
+    }
+}
+
+/// The rate limit information of the response.
+#[derive(
+    Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize,
+)]
+pub struct RateLimitInfo {
+    /// The rate limit of requests.
+    pub requests: RateLimit,
+    /// The rate limit of tokens, the most restrictive one currently in effect.
+    pub tokens: RateLimit,
+    /// The rate limit of input tokens.
+    pub input_tokens: RateLimit,
+    /// The rate limit of output tokens.
+    pub output_tokens: RateLimit,
+}
+
+impl_display_for_serialize!(RateLimitInfo);
+
+impl RateLimitInfo {
+    /// Reads the rate limit information from the response headers.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            requests: RateLimit::from_headers(headers, "requests"),
+            tokens: RateLimit::from_headers(headers, "tokens"),
+            input_tokens: RateLimit::from_headers(headers, "input-tokens"),
+            output_tokens: RateLimit::from_headers(
+                headers,
+                "output-tokens",
+            ),
+        }
+    }
+}
+
+/// A rate limit of a resource.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct RateLimit {
+    /// The maximum number allowed within the rate limit period.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// The number remaining before being rate limited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining: Option<u64>,
+    /// The time when the rate limit will be fully replenished in RFC 3339 format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset: Option<String>,
+}
+
+impl_display_for_serialize!(RateLimit);
+
+impl RateLimit {
+    /// Reads the rate limit of the resource from the response headers.
+    fn from_headers(
+        headers: &HeaderMap,
+        resource: &str,
+    ) -> Self {
+        let read = |suffix: &str| {
+            header_str(
+                headers,
+                &format!("anthropic-ratelimit-{resource}-{suffix}"),
+            )
+        };
+
+        Self {
+            limit: read("limit").and_then(|value| value.parse().ok()),
+            remaining: read("remaining").and_then(|value| value.parse().ok()),
+            reset: read("reset").map(str::to_string),
+        }
+    }
+}
+
+/// Reads the request ID from the response headers.
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    header_str(headers, "request-id").map(str::to_string)
+}
+
+fn header_str<'a>(
+    headers: &'a HeaderMap,
+    name: &str,
+) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("request-id", "req_01".parse().unwrap());
+        headers.insert(
+            "anthropic-ratelimit-requests-limit",
+            "50".parse().unwrap(),
+        );
+        headers.insert(
+            "anthropic-ratelimit-requests-remaining",
+            "49".parse().unwrap(),
+        );
+        headers.insert(
+            "anthropic-ratelimit-requests-reset",
+            "2024-06-30T00:00:00Z".parse().unwrap(),
+        );
+        headers.insert(
+            "anthropic-ratelimit-tokens-remaining",
+            "1000".parse().unwrap(),
+        );
+        headers.insert(
+            "anthropic-ratelimit-input-tokens-limit",
+            "40000".parse().unwrap(),
+        );
+        headers.insert(
+            "anthropic-ratelimit-output-tokens-limit",
+            "8000".parse().unwrap(),
+        );
+
+        let meta = ResponseMeta::from_headers(&headers);
+        assert_eq!(
+            meta,
+            ResponseMeta {
+                request_id: Some("req_01".to_string()),
+                rate_limit: RateLimitInfo {
+                    requests: RateLimit {
+                        limit: Some(50),
+                        remaining: Some(49),
+                        reset: Some("2024-06-30T00:00:00Z".to_string()),
+                    },
+                    tokens: RateLimit {
+                        limit: None,
+                        remaining: Some(1000),
+                        reset: None,
+                    },
+                    input_tokens: RateLimit {
+                        limit: Some(40000),
+                        remaining: None,
+                        reset: None,
+                    },
+                    output_tokens: RateLimit {
+                        limit: Some(8000),
+                        remaining: None,
+                        reset: None,
+                    },
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn from_empty_headers() {
+        assert_eq!(
+            ResponseMeta::from_headers(&HeaderMap::new()),
+            ResponseMeta::default()
+        );
+    }
+
+    #[test]
+    fn invalid_number() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "anthropic-ratelimit-requests-limit",
+            "unknown".parse().unwrap(),
+        );
+
+        let rate_limit = RateLimitInfo::from_headers(&headers);
+        assert_eq!(rate_limit.requests.limit, None);
+    }
+}
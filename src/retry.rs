@@ -0,0 +1,409 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use http::{HeaderMap, StatusCode};
+
+use crate::transport::{TransportError, TransportErrorKind};
+
+/// The retry policy of API calling.
+///
+/// Retries are performed for rate limit errors (429), overloaded errors (529), server errors (5xx) and connection errors.
+///
+/// The delay of each retry is calculated by exponential backoff with jitter,
+/// or by the `retry-after` header of the response if it is provided and honored,
+/// which is capped by the maximum retry-after delay.
+///
+/// ## Example
+/// ```
+/// use std::time::Duration;
+/// use clust::RetryPolicy;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .initial_backoff(Duration::from_millis(200))
+///     .max_backoff(Duration::from_secs(10))
+///     .jitter(true)
+///     .honor_retry_after(true)
+///     .max_retry_after(Duration::from_secs(60));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts including the first one.
+    max_attempts: u32,
+    /// The backoff of the first retry.
+    initial_backoff: Duration,
+    /// The upper bound of the backoff.
+    max_backoff: Duration,
+    /// Whether to apply jitter to the backoff.
+    jitter: bool,
+    /// Whether to honor the `retry-after` header of the response.
+    honor_retry_after: bool,
+    /// The upper bound of the delay requested by the `retry-after` header.
+    max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            jitter: true,
+            honor_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a retry policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum number of attempts including the first one.
+    ///
+    /// A value less than `1` is treated as `1`.
+    pub fn max_attempts(
+        mut self,
+        max_attempts: u32,
+    ) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the backoff of the first retry.
+    pub fn initial_backoff(
+        mut self,
+        initial_backoff: Duration,
+    ) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound of the backoff.
+    pub fn max_backoff(
+        mut self,
+        max_backoff: Duration,
+    ) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets whether to apply jitter to the backoff.
+    pub fn jitter(
+        mut self,
+        jitter: bool,
+    ) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets whether to honor the `retry-after` header of the response.
+    pub fn honor_retry_after(
+        mut self,
+        honor_retry_after: bool,
+    ) -> Self {
+        self.honor_retry_after = honor_retry_after;
+        self
+    }
+
+    /// Sets the upper bound of the delay requested by the `retry-after` header of the response.
+    ///
+    /// A longer delay is shortened to this bound. Defaults to 60 seconds.
+    pub fn max_retry_after(
+        mut self,
+        max_retry_after: Duration,
+    ) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    /// Checks whether another attempt is allowed after the `attempt`-th attempt failed.
+    pub(crate) fn can_retry(
+        &self,
+        attempt: u32,
+    ) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// Calculates the delay before the next attempt after the `attempt`-th attempt failed.
+    ///
+    /// ## Arguments
+    /// - `attempt` - The number of the failed attempt starting from `1`.
+    /// - `retry_after` - The delay requested by the server.
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        if self.honor_retry_after {
+            if let Some(retry_after) = retry_after {
+                return retry_after.min(self.max_retry_after);
+            }
+        }
+
+        let exponent = attempt
+            .saturating_sub(1)
+            .min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            // Reduce the backoff by up to 25% to spread out retries.
+            backoff.mul_f64(1.0 - 0.25 * random_fraction())
+        } else {
+            backoff
+        }
+    }
+
+    /// Checks whether the response status code is retryable.
+    pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            || status.as_u16() == 529
+            || status.is_server_error()
+    }
+
+    /// Checks whether the transport error is retryable.
+    ///
+    /// A timed out or failed request may have been processed by the server,
+    /// so it is retryable only if the request is idempotent.
+    pub(crate) fn is_retryable_error(
+        error: &TransportError,
+        idempotent: bool,
+    ) -> bool {
+        match error.kind() {
+            | TransportErrorKind::Connect
+            | TransportErrorKind::ConnectTimeout => true,
+            | TransportErrorKind::Timeout | TransportErrorKind::Request => {
+                idempotent
+            },
+            | TransportErrorKind::Body | TransportErrorKind::Other => false,
+        }
+    }
+}
+
+/// Reads the delay requested by the server from the `retry-after-ms` or `retry-after` header.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let read = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| value.is_finite() && *value >= 0.0)
+    };
+
+    if let Some(milliseconds) = read("retry-after-ms") {
+        return Some(Duration::from_secs_f64(milliseconds / 1000.0));
+    }
+
+    read("retry-after").map(Duration::from_secs_f64)
+}
+
+/// Generates a random number in `[0, 1)` without an extra dependency.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts, 3);
+        assert!(policy.jitter);
+        assert!(policy.honor_retry_after);
+    }
+
+    #[test]
+    fn none() {
+        let policy = RetryPolicy::none();
+        assert!(!policy.can_retry(1));
+    }
+
+    #[test]
+    fn can_retry() {
+        let policy = RetryPolicy::new().max_attempts(3);
+        assert!(policy.can_retry(1));
+        assert!(policy.can_retry(2));
+        assert!(!policy.can_retry(3));
+
+        let policy = RetryPolicy::new().max_attempts(0);
+        assert!(!policy.can_retry(1));
+    }
+
+    #[test]
+    fn delay_without_jitter() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(
+            policy.delay(1, None),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.delay(2, None),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            policy.delay(3, None),
+            Duration::from_millis(350)
+        );
+        assert_eq!(
+            policy.delay(100, None),
+            Duration::from_millis(350)
+        );
+    }
+
+    #[test]
+    fn delay_with_jitter() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .jitter(true);
+
+        for _ in 0..100 {
+            let delay = policy.delay(1, None);
+            assert!(delay <= Duration::from_millis(100));
+            assert!(delay >= Duration::from_millis(75));
+        }
+    }
+
+    #[test]
+    fn delay_with_retry_after() {
+        let policy = RetryPolicy::new().jitter(false);
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+
+        let policy = policy.honor_retry_after(false);
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3))),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn delay_with_long_retry_after() {
+        let policy = RetryPolicy::new().jitter(false);
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3600))),
+            Duration::from_secs(60)
+        );
+
+        let policy = policy.max_retry_after(Duration::from_secs(10));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(30))),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn is_retryable_status() {
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::TOO_MANY_REQUESTS
+        ));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::from_u16(529).unwrap()
+        ));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+        assert!(RetryPolicy::is_retryable_status(
+            StatusCode::BAD_GATEWAY
+        ));
+        assert!(!RetryPolicy::is_retryable_status(
+            StatusCode::BAD_REQUEST
+        ));
+        assert!(!RetryPolicy::is_retryable_status(
+            StatusCode::UNAUTHORIZED
+        ));
+    }
+
+    #[test]
+    fn is_retryable_error() {
+        let error = |kind| TransportError::new(kind, "error");
+        for idempotent in [true, false] {
+            assert!(RetryPolicy::is_retryable_error(
+                &error(TransportErrorKind::Connect),
+                idempotent
+            ));
+            assert!(RetryPolicy::is_retryable_error(
+                &error(TransportErrorKind::ConnectTimeout),
+                idempotent
+            ));
+            assert_eq!(
+                RetryPolicy::is_retryable_error(
+                    &error(TransportErrorKind::Timeout),
+                    idempotent
+                ),
+                idempotent
+            );
+            assert_eq!(
+                RetryPolicy::is_retryable_error(
+                    &error(TransportErrorKind::Request),
+                    idempotent
+                ),
+                idempotent
+            );
+            assert!(!RetryPolicy::is_retryable_error(
+                &error(TransportErrorKind::Body),
+                idempotent
+            ));
+            assert!(!RetryPolicy::is_retryable_error(
+                &error(TransportErrorKind::Other),
+                idempotent
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert("retry-after", "2".parse().unwrap());
+        assert_eq!(
+            retry_after(&headers),
+            Some(Duration::from_secs(2))
+        );
+
+        headers.insert("retry-after-ms", "1500".parse().unwrap());
+        assert_eq!(
+            retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "retry-after",
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,267 @@
+//! A minimal HTTP/1.1 stub server to test API calling without the real API.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A canned response of the stub server.
+#[derive(Debug, Clone)]
+pub(crate) enum StubResponse {
+    /// Responds with the status, headers and body.
+    Http {
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+    /// Closes the connection without any response.
+    Disconnect,
+}
+
+impl StubResponse {
+    /// Creates a response with the status and the JSON body.
+    pub(crate) fn json<S>(
+        status: u16,
+        body: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Http {
+            status,
+            headers: vec![(
+                "content-type".to_string(),
+                "application/json".to_string(),
+            )],
+            body: body.into(),
+        }
+    }
+
+    /// Creates a response with the status and the server-sent events body.
+    pub(crate) fn event_stream<S>(body: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::Http {
+            status: 200,
+            headers: vec![(
+                "content-type".to_string(),
+                "text/event-stream".to_string(),
+            )],
+            body: body.into(),
+        }
+    }
+
+    /// Adds a header to the response.
+    pub(crate) fn header<K, V>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        if let StubResponse::Http {
+            headers, ..
+        } = &mut self
+        {
+            headers.push((key.into(), value.into()));
+        }
+        self
+    }
+}
+
+/// A request received by the stub server.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    /// The request line, e.g. `POST /v1/messages HTTP/1.1`.
+    pub(crate) request_line: String,
+    /// The headers with lowercase names.
+    pub(crate) headers: Vec<(String, String)>,
+    /// The request body.
+    pub(crate) body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Gets the first header value by the name.
+    pub(crate) fn header(
+        &self,
+        name: &str,
+    ) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Gets the body as a string.
+    pub(crate) fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+}
+
+/// The stub server that responds the canned responses in order.
+///
+/// The last response is repeated when the responses run out.
+pub(crate) struct StubServer {
+    address: std::net::SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl StubServer {
+    /// Starts the stub server on a random local port.
+    pub(crate) async fn start(responses: Vec<StubResponse>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(VecDeque::from(
+            responses,
+        )));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await
+                else {
+                    return;
+                };
+
+                let response = {
+                    let mut responses = responses.lock().unwrap();
+                    if responses.len() > 1 {
+                        responses.pop_front()
+                    } else {
+                        responses.front().cloned()
+                    }
+                };
+
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    handle(stream, response, recorded).await;
+                });
+            }
+        });
+
+        Self {
+            address,
+            requests,
+        }
+    }
+
+    /// Gets the URL of the path on this server.
+    pub(crate) fn url(
+        &self,
+        path: &str,
+    ) -> String {
+        format!("http://{}{}", self.address, path)
+    }
+
+    /// Gets the requests received so far.
+    pub(crate) fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .clone()
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    response: Option<StubResponse>,
+    recorded: Arc<Mutex<Vec<RecordedRequest>>>,
+) {
+    let Some(request) = read_request(&mut stream).await
+    else {
+        return;
+    };
+    recorded
+        .lock()
+        .unwrap()
+        .push(request);
+
+    match response {
+        | Some(StubResponse::Http {
+            status,
+            headers,
+            body,
+        }) => {
+            let mut head = format!(
+                "HTTP/1.1 {status} Stub\r\ncontent-length: {}\r\nconnection: close\r\n",
+                body.len()
+            );
+            for (key, value) in headers {
+                head.push_str(&format!("{key}: {value}\r\n"));
+            }
+            head.push_str("\r\n");
+
+            let _ = stream
+                .write_all(head.as_bytes())
+                .await;
+            let _ = stream
+                .write_all(body.as_bytes())
+                .await;
+            let _ = stream.shutdown().await;
+        },
+        | Some(StubResponse::Disconnect) | None => {},
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<RecordedRequest> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    // Read until the end of the headers.
+    let header_end = loop {
+        if let Some(position) = buffer
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+        {
+            break position + 4;
+        }
+
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next()?.to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| {
+            (
+                key.trim().to_ascii_lowercase(),
+                value.trim().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    // Read the rest of the body.
+    let mut body = buffer[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    Some(RecordedRequest {
+        request_line,
+        headers,
+        body,
+    })
+}
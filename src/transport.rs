@@ -0,0 +1,462 @@
+//! The HTTP transport abstraction to send prepared requests.
+//!
+//! [`ReqwestTransport`] is the default implementation, and you can plug in your own transport
+//! by implementing [`Transport`] and setting it by [`crate::ClientBuilder::transport`].
+
+use std::error::Error;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use futures_core::Stream;
+use http::{HeaderMap, Method, StatusCode};
+
+/// A boxed future returned by [`Transport`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A boxed stream of the response body bytes.
+pub type ByteStream =
+    Pin<Box<dyn Stream<Item = Result<Bytes, TransportError>> + Send>>;
+
+/// An HTTP transport that sends a prepared request and returns the response.
+///
+/// ## Example
+/// ```
+/// use clust::http::{HeaderMap, StatusCode};
+/// use clust::transport::{BoxFuture, Transport, TransportError, TransportRequest, TransportResponse};
+///
+/// struct MyTransport;
+///
+/// impl Transport for MyTransport {
+///     fn send(
+///         &self,
+///         request: TransportRequest,
+///     ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+///         Box::pin(async move {
+///             // Send the request by your HTTP client.
+///             Ok(TransportResponse::from_bytes(
+///                 StatusCode::OK,
+///                 HeaderMap::new(),
+///                 "{}",
+///             ))
+///         })
+///     }
+/// }
+/// ```
+pub trait Transport: Send + Sync {
+    /// Sends the request and returns the response whose body has not been read yet.
+    fn send(
+        &self,
+        request: TransportRequest,
+    ) -> BoxFuture<'_, Result<TransportResponse, TransportError>>;
+}
+
+impl<T> Transport for std::sync::Arc<T>
+where
+    T: Transport + ?Sized,
+{
+    fn send(
+        &self,
+        request: TransportRequest,
+    ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+        self.as_ref().send(request)
+    }
+}
+
+/// A prepared HTTP request.
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    /// The HTTP method.
+    pub method: Method,
+    /// The URL of the request.
+    pub url: String,
+    /// The request headers.
+    pub headers: HeaderMap,
+    /// The request body.
+    pub body: Bytes,
+}
+
+impl TransportRequest {
+    /// Creates a new request without headers and body.
+    pub fn new<S>(
+        method: Method,
+        url: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            method,
+            url: url.into(),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        }
+    }
+}
+
+/// An HTTP response with the streaming body.
+pub struct TransportResponse {
+    /// The HTTP status code.
+    pub status: StatusCode,
+    /// The response headers.
+    pub headers: HeaderMap,
+    /// The stream of the response body.
+    pub body: ByteStream,
+}
+
+impl TransportResponse {
+    /// Creates a new response.
+    pub fn new(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: ByteStream,
+    ) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Creates a new response with the whole body.
+    pub fn from_bytes<B>(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: B,
+    ) -> Self
+    where
+        B: Into<Bytes>,
+    {
+        let body: Bytes = body.into();
+        Self::new(
+            status,
+            headers,
+            Box::pin(Once {
+                item: Some(Ok(body)),
+            }),
+        )
+    }
+
+    /// Reads the whole response body.
+    pub async fn bytes(mut self) -> Result<Bytes, TransportError> {
+        let mut buffer = BytesMut::new();
+
+        while let Some(chunk) = std::future::poll_fn(|cx| {
+            self.body
+                .as_mut()
+                .poll_next(cx)
+        })
+        .await
+        {
+            buffer.extend_from_slice(&chunk?);
+        }
+
+        Ok(buffer.freeze())
+    }
+
+    /// Reads the whole response body as a UTF-8 text.
+    pub async fn text(self) -> Result<String, TransportError> {
+        let bytes = self.bytes().await?;
+
+        String::from_utf8(bytes.to_vec())
+            .map_err(|error| TransportError::new(TransportErrorKind::Body, error))
+    }
+}
+
+impl std::fmt::Debug for TransportResponse {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("TransportResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The kind of a transport error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransportErrorKind {
+    /// Failed to connect to the server.
+    Connect,
+    /// Timed out connecting to the server.
+    ConnectTimeout,
+    /// The request timed out.
+    Timeout,
+    /// Failed to send the request or receive the response.
+    Request,
+    /// Failed to read the response body.
+    Body,
+    /// Other errors.
+    Other,
+}
+
+impl Display for TransportErrorKind {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            | TransportErrorKind::Connect => write!(f, "connect"),
+            | TransportErrorKind::ConnectTimeout => write!(f, "connect timeout"),
+            | TransportErrorKind::Timeout => write!(f, "timeout"),
+            | TransportErrorKind::Request => write!(f, "request"),
+            | TransportErrorKind::Body => write!(f, "body"),
+            | TransportErrorKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// The error of a transport.
+#[derive(Debug, thiserror::Error)]
+#[error("Transport error ({kind}): {source}")]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl TransportError {
+    /// Creates a new transport error.
+    pub fn new<E>(
+        kind: TransportErrorKind,
+        source: E,
+    ) -> Self
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    /// Gets the kind of this error.
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+
+    /// Checks whether the error is related to connecting.
+    pub fn is_connect(&self) -> bool {
+        matches!(
+            self.kind,
+            TransportErrorKind::Connect | TransportErrorKind::ConnectTimeout
+        )
+    }
+
+    /// Checks whether the error is related to a timeout.
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.kind,
+            TransportErrorKind::Timeout | TransportErrorKind::ConnectTimeout
+        )
+    }
+
+    /// Gets the source error.
+    pub fn get_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+        self.source.as_ref()
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(error: reqwest::Error) -> Self {
+        let kind = if error.is_connect() && error.is_timeout() {
+            TransportErrorKind::ConnectTimeout
+        } else if error.is_connect() {
+            TransportErrorKind::Connect
+        } else if error.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if error.is_body() || error.is_decode() {
+            TransportErrorKind::Body
+        } else if error.is_request() {
+            TransportErrorKind::Request
+        } else {
+            TransportErrorKind::Other
+        };
+
+        Self::new(kind, error)
+    }
+}
+
+/// The default transport implemented by [`reqwest::Client`].
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a new transport with the HTTP client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+        }
+    }
+
+    /// Creates a new transport with the default HTTP client timing out connecting to the server.
+    ///
+    /// A timed out connection fails with [`TransportErrorKind::ConnectTimeout`].
+    ///
+    /// ## Panics
+    /// Panics in the same cases as `reqwest::Client::new`,
+    /// i.e. if a TLS backend cannot be initialized or the resolver cannot load the system configuration.
+    pub fn with_connect_timeout(connect_timeout: std::time::Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .build()
+            .expect("Failed to build the HTTP client");
+
+        Self::new(client)
+    }
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> Self {
+        Self::new(client)
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(
+        &self,
+        request: TransportRequest,
+    ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers)
+                .body(request.body)
+                .send()
+                .await?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = ReqwestByteStream {
+                stream: Box::pin(response.bytes_stream()),
+            };
+
+            Ok(TransportResponse::new(
+                status,
+                headers,
+                Box::pin(body),
+            ))
+        })
+    }
+}
+
+/// Maps the byte stream of [`reqwest::Response`] into [`ByteStream`].
+struct ReqwestByteStream {
+    stream: Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>,
+}
+
+impl Stream for ReqwestByteStream {
+    type Item = Result<Bytes, TransportError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.stream
+            .as_mut()
+            .poll_next(cx)
+            .map(|item| item.map(|result| result.map_err(Into::into)))
+    }
+}
+
+/// A stream that yields a single item.
+struct Once {
+    item: Option<Result<Bytes, TransportError>>,
+}
+
+impl Stream for Once {
+    type Item = Result<Bytes, TransportError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.item.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{StubResponse, StubServer};
+
+    #[tokio::test]
+    async fn reqwest_transport() {
+        let server = StubServer::start(vec![StubResponse::json(
+            200,
+            r#"{"ok":true}"#,
+        )
+        .header("request-id", "req_01")])
+        .await;
+
+        let mut request =
+            TransportRequest::new(Method::POST, server.url("/v1/test"));
+        request
+            .headers
+            .insert("x-test", "value".parse().unwrap());
+        request.body = Bytes::from_static(b"body");
+
+        let response = ReqwestTransport::default()
+            .send(request)
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+        assert_eq!(
+            response
+                .headers
+                .get("request-id")
+                .unwrap(),
+            "req_01"
+        );
+        assert_eq!(
+            response.text().await.unwrap(),
+            r#"{"ok":true}"#
+        );
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[0].request_line,
+            "POST /v1/test HTTP/1.1"
+        );
+        assert_eq!(
+            requests[0].header("x-test"),
+            Some("value")
+        );
+        assert_eq!(requests[0].body_text(), "body");
+    }
+
+    #[tokio::test]
+    async fn reqwest_transport_connect_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let error = ReqwestTransport::default()
+            .send(TransportRequest::new(
+                Method::GET,
+                format!("http://{address}/"),
+            ))
+            .await
+            .unwrap_err();
+        assert!(error.is_connect());
+    }
+
+    #[tokio::test]
+    async fn response_from_bytes() {
+        let response = TransportResponse::from_bytes(
+            StatusCode::OK,
+            HeaderMap::new(),
+            "text",
+        );
+        assert_eq!(
+            response.text().await.unwrap(),
+            "text"
+        );
+    }
+}
@@ -0,0 +1,625 @@
+//! The [Google Vertex AI](https://cloud.google.com/vertex-ai/generative-ai/docs/partner-models/use-claude) provider.
+//!
+//! The provider rewrites requests of the Messages API for Vertex AI:
+//! - The model is moved from the request body to the URL with `:rawPredict` or `:streamRawPredict`.
+//! - `anthropic_version` is set in the request body.
+//! - Requests are authorized by a bearer access token instead of the API key.
+//!
+//! Streaming responses are server-sent events as same as the Anthropic API.
+//!
+//! ## Example
+//! ```no_run
+//! use clust::vertex::{StaticAccessToken, Vertex};
+//! use clust::ClientBuilder;
+//!
+//! let vertex = Vertex::new(
+//!     "my-project",
+//!     "us-east5",
+//!     StaticAccessToken::new("access-token"),
+//! );
+//!
+//! // The requests are authorized by the access token instead of an API key.
+//! let client = ClientBuilder::default()
+//!     .vertex(vertex)
+//!     .build();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use http::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+
+use crate::messages::ClaudeModel;
+use crate::transport::{
+    BoxFuture, Transport, TransportError, TransportErrorKind,
+    TransportRequest, TransportResponse,
+};
+use crate::ApiErrorType;
+
+/// The `anthropic_version` of the Vertex AI request body.
+const VERTEX_VERSION: &str = "vertex-2023-10-16";
+
+/// The provider of access tokens for Vertex AI, e.g. from the metadata server or `gcloud auth print-access-token`.
+///
+/// The provider is asked for an access token on every request,
+/// so that it can cache and refresh the token by itself.
+///
+/// ## Example
+/// ```
+/// use clust::transport::{BoxFuture, TransportError};
+/// use clust::vertex::AccessTokenProvider;
+///
+/// struct MyTokenProvider;
+///
+/// impl AccessTokenProvider for MyTokenProvider {
+///     fn access_token(&self) -> BoxFuture<'_, Result<String, TransportError>> {
+///         Box::pin(async {
+///             // Fetch or refresh the access token.
+///             Ok("access-token".to_string())
+///         })
+///     }
+/// }
+/// ```
+pub trait AccessTokenProvider: Send + Sync {
+    /// Gets the access token.
+    fn access_token(&self) -> BoxFuture<'_, Result<String, TransportError>>;
+}
+
+/// The fixed access token.
+///
+/// The value is redacted in the `Debug` format.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StaticAccessToken {
+    value: String,
+}
+
+impl StaticAccessToken {
+    /// Creates a new fixed access token.
+    pub fn new<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: value.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for StaticAccessToken {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_tuple("StaticAccessToken")
+            .field(&"[REDACTED]")
+            .finish()
+    }
+}
+
+impl AccessTokenProvider for StaticAccessToken {
+    fn access_token(&self) -> BoxFuture<'_, Result<String, TransportError>> {
+        Box::pin(async { Ok(self.value.clone()) })
+    }
+}
+
+/// The configuration of the Google Vertex AI provider set by [`crate::ClientBuilder::vertex`].
+#[derive(Clone)]
+pub struct Vertex {
+    /// The Google Cloud project ID.
+    project_id: String,
+    /// The region, e.g. `us-east5` or `global`.
+    region: String,
+    /// The provider of access tokens.
+    access_token_provider: Arc<dyn AccessTokenProvider>,
+    /// The endpoint overriding the default one of the region.
+    endpoint: Option<String>,
+    /// The Vertex AI model IDs overriding the default ones.
+    model_ids: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for Vertex {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Vertex")
+            .field("project_id", &self.project_id)
+            .field("region", &self.region)
+            .field("endpoint", &self.endpoint)
+            .field("model_ids", &self.model_ids)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Vertex {
+    /// Creates a new Vertex AI configuration.
+    ///
+    /// ## Arguments
+    /// - `project_id` - The Google Cloud project ID.
+    /// - `region` - The region, e.g. `us-east5` or `global`.
+    /// - `access_token_provider` - The provider of access tokens.
+    pub fn new<S, T, P>(
+        project_id: S,
+        region: T,
+        access_token_provider: P,
+    ) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+        P: AccessTokenProvider + 'static,
+    {
+        Self {
+            project_id: project_id.into(),
+            region: region.into(),
+            access_token_provider: Arc::new(access_token_provider),
+            endpoint: None,
+            model_ids: HashMap::new(),
+        }
+    }
+
+    /// Sets the endpoint, e.g. a private service connect endpoint.
+    ///
+    /// The default is `https://{region}-aiplatform.googleapis.com`, or `https://aiplatform.googleapis.com` for the `global` region.
+    pub fn endpoint<S>(
+        mut self,
+        endpoint: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the Vertex AI model ID of the model.
+    ///
+    /// The default model ID replaces the date suffix of the model, e.g. `claude-3-opus-20240229` into `claude-3-opus@20240229`,
+    /// and `ClaudeModel::Other` containing `@` is used as a Vertex AI model ID as it is.
+    pub fn model_id<S>(
+        mut self,
+        model: ClaudeModel,
+        model_id: S,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.model_ids
+            .insert(model.to_string(), model_id.into());
+        self
+    }
+
+    /// Resolves the Vertex AI model ID of the model.
+    fn resolve_model_id(
+        &self,
+        model: &str,
+    ) -> String {
+        if let Some(model_id) = self.model_ids.get(model) {
+            return model_id.clone();
+        }
+
+        match model.rsplit_once('-') {
+            | Some((name, date))
+                if !model.contains('@')
+                    && date.len() == 8
+                    && date
+                        .bytes()
+                        .all(|byte| byte.is_ascii_digit()) =>
+            {
+                format!("{name}@{date}")
+            },
+            | _ => model.to_string(),
+        }
+    }
+
+    /// Rewrites the request of the Messages API into the request of Vertex AI.
+    async fn prepare(
+        &self,
+        request: &mut TransportRequest,
+    ) -> Result<(), TransportError> {
+        let count_tokens = if request.url.ends_with("/v1/messages/count_tokens") {
+            true
+        } else if request.url.ends_with("/v1/messages") {
+            false
+        } else {
+            return Err(TransportError::new(
+                TransportErrorKind::Other,
+                format!(
+                    "Vertex AI supports only the Messages API: {}",
+                    request.url
+                ),
+            ));
+        };
+
+        let mut body: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&request.body).map_err(|error| {
+                TransportError::new(TransportErrorKind::Other, error)
+            })?;
+
+        let model = match body.remove("model") {
+            | Some(serde_json::Value::String(model)) => model,
+            | _ => {
+                return Err(TransportError::new(
+                    TransportErrorKind::Other,
+                    "Vertex AI supports only requests with a model",
+                ));
+            },
+        };
+        let stream = body
+            .get("stream")
+            .and_then(|stream| stream.as_bool())
+            .unwrap_or(false);
+
+        let model_id = self.resolve_model_id(&model);
+        let (model, method) = if count_tokens {
+            // The model is specified in the request body for counting tokens.
+            body.insert("model".to_string(), model_id.into());
+            ("count-tokens".to_string(), "rawPredict")
+        } else {
+            body.insert(
+                "anthropic_version".to_string(),
+                VERTEX_VERSION.into(),
+            );
+            let method = if stream {
+                "streamRawPredict"
+            } else {
+                "rawPredict"
+            };
+            (model_id, method)
+        };
+
+        let endpoint = self
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| {
+                if self.region == "global" {
+                    "https://aiplatform.googleapis.com".to_string()
+                } else {
+                    format!("https://{}-aiplatform.googleapis.com", self.region)
+                }
+            });
+        request.url = format!(
+            "{}/v1/projects/{}/locations/{}/publishers/anthropic/models/{model}:{method}",
+            endpoint.trim_end_matches('/'),
+            self.project_id,
+            self.region,
+        );
+        request.body = serde_json::to_vec(&body)
+            .map_err(|error| {
+                TransportError::new(TransportErrorKind::Other, error)
+            })?
+            .into();
+
+        let access_token = self
+            .access_token_provider
+            .access_token()
+            .await?;
+        let mut authorization =
+            HeaderValue::from_str(&format!("Bearer {access_token}")).map_err(
+                |error| TransportError::new(TransportErrorKind::Other, error),
+            )?;
+        authorization.set_sensitive(true);
+
+        request.headers.remove("x-api-key");
+        request
+            .headers
+            .insert(AUTHORIZATION, authorization);
+        request.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        Ok(())
+    }
+}
+
+/// The transport sending requests to Vertex AI through the inner transport.
+pub(crate) struct VertexTransport {
+    vertex: Vertex,
+    inner: Arc<dyn Transport>,
+}
+
+impl VertexTransport {
+    /// Creates a new Vertex AI transport.
+    pub(crate) fn new(
+        vertex: Vertex,
+        inner: Arc<dyn Transport>,
+    ) -> Self {
+        Self {
+            vertex,
+            inner,
+        }
+    }
+}
+
+impl Transport for VertexTransport {
+    fn send(
+        &self,
+        mut request: TransportRequest,
+    ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+        Box::pin(async move {
+            self.vertex
+                .prepare(&mut request)
+                .await?;
+            let response = self.inner.send(request).await?;
+
+            if response.status.is_success() {
+                Ok(response)
+            } else {
+                into_api_error_response(response).await
+            }
+        })
+    }
+}
+
+/// Converts the error response of Google Cloud: `{"error": {"message": "..."}}` into the one of the Anthropic API.
+///
+/// Errors of the Anthropic API returned through Vertex AI are kept as they are.
+async fn into_api_error_response(
+    response: TransportResponse
+) -> Result<TransportResponse, TransportError> {
+    let status = response.status;
+    let headers = response.headers.clone();
+    let body = response.bytes().await?;
+
+    let error = serde_json::from_slice::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|body| {
+            let error = body.get("error")?;
+            if body.get("type").is_some() {
+                // The error of the Anthropic API.
+                return None;
+            }
+
+            let message = error
+                .get("message")
+                .and_then(|message| message.as_str())?;
+            Some(match error.get("status").and_then(|status| status.as_str()) {
+                | Some(status) => format!("{status}: {message}"),
+                | None => message.to_string(),
+            })
+        });
+
+    let body = match error {
+        | Some(message) => {
+            let error_type = match ApiErrorType::from(status) {
+                | ApiErrorType::Unknown(_) => ApiErrorType::ApiError,
+                | error_type => error_type,
+            };
+
+            serde_json::json!({
+                "type": "error",
+                "error": {
+                    "type": error_type.to_string(),
+                    "message": message,
+                },
+            })
+            .to_string()
+            .into()
+        },
+        | None => body,
+    };
+
+    Ok(TransportResponse::from_bytes(status, headers, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        MessageChunk, MessagesError, MessagesRequestBody, StreamOption,
+    };
+    use crate::{ApiKey, ClientBuilder};
+    use http::{HeaderMap, StatusCode};
+    use std::sync::Mutex;
+
+    /// The transport recording requests and responding the canned response.
+    struct CannedTransport {
+        status: StatusCode,
+        body: &'static str,
+        requests: Arc<Mutex<Vec<TransportRequest>>>,
+    }
+
+    impl Transport for CannedTransport {
+        fn send(
+            &self,
+            request: TransportRequest,
+        ) -> BoxFuture<'_, Result<TransportResponse, TransportError>> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push(request);
+
+            Box::pin(async {
+                Ok(TransportResponse::from_bytes(
+                    self.status,
+                    HeaderMap::new(),
+                    self.body,
+                ))
+            })
+        }
+    }
+
+    fn client(
+        status: StatusCode,
+        body: &'static str,
+    ) -> (crate::Client, Arc<Mutex<Vec<TransportRequest>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        // Built as documented in the module, without an API key.
+        let client = ClientBuilder::default()
+            .transport(CannedTransport {
+                status,
+                body,
+                requests: requests.clone(),
+            })
+            .vertex(Vertex::new(
+                "my-project",
+                "us-east5",
+                StaticAccessToken::new("access-token"),
+            ))
+            .build();
+
+        (client, requests)
+    }
+
+    const MESSAGE_RESPONSE: &str = r#"{"id":"msg_01","type":"message","role":"assistant","content":[{"type":"text","text":"Hello!"}],"model":"claude-3-opus-20240229","stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":5}}"#;
+
+    const STREAM_RESPONSE: &str = r#"event: message_start
+data: {"type": "message_start", "message": {"id": "msg_01", "type": "message", "role": "assistant", "content": [], "model": "claude-3-opus-20240229", "stop_reason": null, "stop_sequence": null, "usage": {"input_tokens": 10, "output_tokens": 1}}}
+
+event: message_stop
+data: {"type": "message_stop"}
+
+"#;
+
+    #[tokio::test]
+    async fn create_a_message() {
+        let (client, requests) = client(StatusCode::OK, MESSAGE_RESPONSE);
+
+        let response = client
+            .create_a_message(MessagesRequestBody {
+                model: ClaudeModel::Claude3Opus20240229,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.id, "msg_01");
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(
+            request.url,
+            "https://us-east5-aiplatform.googleapis.com/v1/projects/my-project/locations/us-east5/publishers/anthropic/models/claude-3-opus@20240229:rawPredict"
+        );
+        assert!(request.headers.get("x-api-key").is_none());
+        assert_eq!(
+            request.headers["authorization"],
+            "Bearer access-token"
+        );
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&request.body).unwrap();
+        assert!(body.get("model").is_none());
+        assert_eq!(body["anthropic_version"], "vertex-2023-10-16");
+    }
+
+    #[tokio::test]
+    async fn create_a_message_stream() {
+        use futures_util::StreamExt;
+
+        let (client, requests) = client(StatusCode::OK, STREAM_RESPONSE);
+
+        let stream = client
+            .create_a_message_stream(MessagesRequestBody {
+                model: ClaudeModel::Other("claude-sonnet-4@20250514".to_string()),
+                stream: Some(StreamOption::ReturnStream),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let chunks = stream
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(matches!(
+            chunks.as_slice(),
+            [
+                MessageChunk::MessageStart(_),
+                MessageChunk::MessageStop(_)
+            ]
+        ));
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0]
+            .url
+            .ends_with("/models/claude-sonnet-4@20250514:streamRawPredict"));
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["stream"], true);
+    }
+
+    #[tokio::test]
+    async fn count_tokens() {
+        let (client, requests) =
+            client(StatusCode::OK, r#"{"input_tokens":10}"#);
+
+        let response = client
+            .count_tokens(MessagesRequestBody {
+                model: ClaudeModel::Claude3Opus20240229,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.input_tokens, 10);
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0]
+            .url
+            .ends_with("/publishers/anthropic/models/count-tokens:rawPredict"));
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["model"], "claude-3-opus@20240229");
+        assert!(body.get("anthropic_version").is_none());
+    }
+
+    #[tokio::test]
+    async fn google_error_response() {
+        let (client, _) = client(
+            StatusCode::FORBIDDEN,
+            r#"{"error": {"code": 403, "message": "Permission denied on resource project my-project.", "status": "PERMISSION_DENIED"}}"#,
+        );
+
+        let error = client
+            .create_a_message(MessagesRequestBody::default())
+            .await
+            .unwrap_err();
+
+        match error {
+            | MessagesError::ApiError(error) => {
+                assert_eq!(error._type, ApiErrorType::PermissionError);
+                assert_eq!(
+                    error.response.error.message,
+                    "PERMISSION_DENIED: Permission denied on resource project my-project."
+                );
+            },
+            | _ => panic!("unexpected error: {error:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unsupported_request_error_is_not_retryable() {
+        let vertex = Vertex::new(
+            "my-project",
+            "us-east5",
+            StaticAccessToken::new("access-token"),
+        );
+        let mut request = TransportRequest::new(
+            http::Method::GET,
+            "https://api.anthropic.com/v1/models",
+        );
+
+        let error = vertex.prepare(&mut request).await.unwrap_err();
+        assert_eq!(error.kind(), TransportErrorKind::Other);
+    }
+
+    #[test]
+    fn resolve_model_id() {
+        let vertex = Vertex::new("project", "global", StaticAccessToken::new(""))
+            .model_id(
+                ClaudeModel::Claude35Sonnet20240620,
+                "claude-3-5-sonnet-v2@20241022",
+            );
+
+        assert_eq!(
+            vertex.resolve_model_id("claude-3-haiku-20240307"),
+            "claude-3-haiku@20240307"
+        );
+        assert_eq!(
+            vertex.resolve_model_id("claude-3-5-sonnet-20240620"),
+            "claude-3-5-sonnet-v2@20241022"
+        );
+        assert_eq!(
+            vertex.resolve_model_id("claude-opus-4@20250514"),
+            "claude-opus-4@20250514"
+        );
+    }
+}